@@ -0,0 +1,41 @@
+//! Angle units. The rotation methods of the vector and rotation types, such
+//! as `Vector2::from_angle`, `Vector2::rotate_towards` and
+//! `Vector3::rotate_around`, take a `Rad` or `Deg` rather than a bare float,
+//! so the unit is explicit where they are called. Headings, view cones and
+//! slope limits in the other modules are plain radians.
+
+use crate::Float;
+
+/// An angle in radians.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Default)]
+pub struct Rad<T>(pub T);
+
+/// An angle in degrees, converted to radians where rotation functions
+/// take it.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Default)]
+pub struct Deg<T>(pub T);
+
+impl<T: Float> From<Deg<T>> for Rad<T> {
+    fn from(d: Deg<T>) -> Self {
+        Rad(d.0.to_radians())
+    }
+}
+
+impl<T: Float> From<Rad<T>> for Deg<T> {
+    fn from(r: Rad<T>) -> Self {
+        Deg(r.0.to_degrees())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_angle_conversions() {
+        let r: Rad<f64> = Deg(180.0).into();
+        assert!((r.0 - std::f64::consts::PI).abs() < 1e-12);
+        let d: Deg<f64> = Rad(std::f64::consts::FRAC_PI_2).into();
+        assert!((d.0 - 90.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,149 @@
+//! Keyframe tracks animating any value that can be interpolated: scalars,
+//! positions, scales or colors stored as vectors.
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::interpolation::lerp;
+
+/// How a track blends between two keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Hold the value of the previous key.
+    Step,
+    #[default]
+    Linear,
+    /// Smooth Catmull-Rom style curve through the keys, with tangents taken
+    /// from the neighboring keys and scaled for uneven key spacing.
+    Cubic,
+}
+
+/// Keys of `(time, value)` sorted by time.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Track<V> {
+    keys: Vec<(f32, V)>,
+    pub interpolation: Interpolation,
+}
+
+impl<V> Track<V>
+where
+    V: Copy + Add<Output = V> + Sub<Output = V> + Mul<f32, Output = V>,
+{
+    pub fn new(interpolation: Interpolation) -> Track<V> {
+        Track {
+            keys: Vec::new(),
+            interpolation,
+        }
+    }
+
+    /// Adds a key, replacing any key at exactly the same time.
+    pub fn insert(&mut self, time: f32, value: V) {
+        match self.keys.binary_search_by(|k| k.0.total_cmp(&time)) {
+            Ok(i) => self.keys[i].1 = value,
+            Err(i) => self.keys.insert(i, (time, value)),
+        }
+    }
+
+    pub fn keys(&self) -> &[(f32, V)] {
+        &self.keys
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Time span `(start, end)` covered by the keys, `None` without keys.
+    pub fn time_range(&self) -> Option<(f32, f32)> {
+        Some((self.keys.first()?.0, self.keys.last()?.0))
+    }
+
+    /// Value at time `t`. Before the first and after the last key the track
+    /// holds the value of that key. `None` if the track has no keys.
+    pub fn sample(&self, t: f32) -> Option<V> {
+        let (first, last) = (self.keys.first()?, self.keys.last()?);
+        if t <= first.0 {
+            return Some(first.1);
+        }
+        if t >= last.0 {
+            return Some(last.1);
+        }
+        // Index of the first key after t; there is at least one before it
+        let i = self.keys.partition_point(|k| k.0 <= t);
+        let (t0, v0) = self.keys[i - 1];
+        let (t1, v1) = self.keys[i];
+        let dt = t1 - t0;
+        let s = (t - t0) / dt;
+        Some(match self.interpolation {
+            Interpolation::Step => v0,
+            Interpolation::Linear => lerp(v0, v1, s),
+            Interpolation::Cubic => {
+                let m0 = self.tangent(i - 1) * dt;
+                let m1 = self.tangent(i) * dt;
+                let (s2, s3) = (s * s, s * s * s);
+                v0 * (2.0 * s3 - 3.0 * s2 + 1.0)
+                    + m0 * (s3 - 2.0 * s2 + s)
+                    + v1 * (3.0 * s2 - 2.0 * s3)
+                    + m1 * (s3 - s2)
+            }
+        })
+    }
+
+    /// Rate of change at key `i`, from its neighbors or one-sided at the ends.
+    fn tangent(&self, i: usize) -> V {
+        let prev = self.keys[i.saturating_sub(1)];
+        let next = self.keys[(i + 1).min(self.keys.len() - 1)];
+        (next.1 - prev.1) * (next.0 - prev.0).recip()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector2;
+
+    fn track(interpolation: Interpolation) -> Track<Vector2<f32>> {
+        let mut track = Track::new(interpolation);
+        track.insert(2.0, Vector2::new(4.0, 0.0));
+        track.insert(0.0, Vector2::new(0.0, 0.0));
+        track.insert(1.0, Vector2::new(1.0, 2.0));
+        track
+    }
+
+    #[test]
+    fn test_track_keys() {
+        let mut t = track(Interpolation::Linear);
+        assert_eq!(t.time_range(), Some((0.0, 2.0)));
+        t.insert(1.0, Vector2::new(2.0, 2.0));
+        assert_eq!(t.keys().len(), 3);
+        assert_eq!(t.keys()[1], (1.0, Vector2::new(2.0, 2.0)));
+        assert_eq!(Track::<f32>::new(Interpolation::Step).sample(1.0), None);
+    }
+
+    #[test]
+    fn test_sample() {
+        let step = track(Interpolation::Step);
+        assert_eq!(step.sample(0.9), Some(Vector2::new(0.0, 0.0)));
+        assert_eq!(step.sample(1.0), Some(Vector2::new(1.0, 2.0)));
+
+        let linear = track(Interpolation::Linear);
+        assert_eq!(linear.sample(-1.0), Some(Vector2::new(0.0, 0.0)));
+        assert_eq!(linear.sample(1.5), Some(Vector2::new(2.5, 1.0)));
+        assert_eq!(linear.sample(3.0), Some(Vector2::new(4.0, 0.0)));
+
+        let cubic = track(Interpolation::Cubic);
+        for (time, value) in cubic.keys() {
+            assert_eq!(cubic.sample(*time), Some(*value));
+        }
+        // The curve is rounder than the straight lines between the keys
+        assert!(cubic.sample(0.5).unwrap().y > linear.sample(0.5).unwrap().y);
+    }
+
+    #[test]
+    fn test_cubic_reproduces_lines() {
+        let mut t = Track::new(Interpolation::Cubic);
+        t.insert(0.0, 0.0);
+        t.insert(1.0, 2.0);
+        t.insert(3.0, 6.0);
+        assert!((t.sample(0.5).unwrap() - 1.0).abs() < 1e-6);
+        assert!((t.sample(2.0).unwrap() - 4.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,128 @@
+//! Property-based testing support: `Arbitrary` for the vector types and
+//! strategies for choosing their components. Requires the `proptest`
+//! feature.
+//!
+//! `any::<Vector3<f32>>()` draws components from proptest's default float
+//! strategy, which is finite but spans the whole `f32` range, so sums and
+//! products can still overflow. Geometry code is usually better fuzzed with
+//! bounded components, such as `vector3(-1e3f32..1e3)`.
+
+use proptest::arbitrary::{any_with, Arbitrary};
+use proptest::strategy::{Map, Strategy};
+
+use crate::{Vector2, Vector3, Vector4};
+
+fn to_vector2<T>((x, y): (T, T)) -> Vector2<T> {
+    Vector2 { x, y }
+}
+
+fn to_vector3<T>((x, y, z): (T, T, T)) -> Vector3<T> {
+    Vector3 { x, y, z }
+}
+
+fn to_vector4<T>((x, y, z, w): (T, T, T, T)) -> Vector4<T> {
+    Vector4 { x, y, z, w }
+}
+
+impl<T: Arbitrary> Arbitrary for Vector2<T>
+where
+    T::Parameters: Clone,
+{
+    type Parameters = T::Parameters;
+    type Strategy = Map<(T::Strategy, T::Strategy), fn((T, T)) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        (any_with::<T>(args.clone()), any_with::<T>(args)).prop_map(to_vector2)
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vector3<T>
+where
+    T::Parameters: Clone,
+{
+    type Parameters = T::Parameters;
+    type Strategy = Map<(T::Strategy, T::Strategy, T::Strategy), fn((T, T, T)) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        (
+            any_with::<T>(args.clone()),
+            any_with::<T>(args.clone()),
+            any_with::<T>(args),
+        )
+            .prop_map(to_vector3)
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vector4<T>
+where
+    T::Parameters: Clone,
+{
+    type Parameters = T::Parameters;
+    type Strategy =
+        Map<(T::Strategy, T::Strategy, T::Strategy, T::Strategy), fn((T, T, T, T)) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        (
+            any_with::<T>(args.clone()),
+            any_with::<T>(args.clone()),
+            any_with::<T>(args.clone()),
+            any_with::<T>(args),
+        )
+            .prop_map(to_vector4)
+    }
+}
+
+/// Vectors with every component drawn from `component`, e.g. a range.
+pub fn vector2<S: Strategy + Clone>(component: S) -> impl Strategy<Value = Vector2<S::Value>> {
+    (component.clone(), component).prop_map(to_vector2)
+}
+
+/// Vectors with every component drawn from `component`, e.g. a range.
+pub fn vector3<S: Strategy + Clone>(component: S) -> impl Strategy<Value = Vector3<S::Value>> {
+    (component.clone(), component.clone(), component).prop_map(to_vector3)
+}
+
+/// Vectors with every component drawn from `component`, e.g. a range.
+pub fn vector4<S: Strategy + Clone>(component: S) -> impl Strategy<Value = Vector4<S::Value>> {
+    (
+        component.clone(),
+        component.clone(),
+        component.clone(),
+        component,
+    )
+        .prop_map(to_vector4)
+}
+
+/// Unit length directions, roughly uniform over the sphere.
+pub fn unit_vector3() -> impl Strategy<Value = Vector3<f32>> {
+    vector3(-1.0f32..1.0)
+        .prop_filter("too short to normalize", |v| {
+            let len_sq = v.dot(v);
+            len_sq > 1e-4 && len_sq <= 1.0
+        })
+        .prop_map(|v| v.normalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_any_vector(v in any::<Vector3<i16>>(), w in any::<Vector4<f64>>()) {
+            prop_assert_eq!(Vector3::from([v.x, v.y, v.z]), v);
+            prop_assert!(w.x.is_finite() && w.w.is_finite());
+        }
+
+        #[test]
+        fn test_bounded_components(v in vector2(-10.0f32..10.0)) {
+            prop_assert!(v.x.abs() < 10.0 && v.y.abs() < 10.0);
+        }
+
+        #[test]
+        fn test_unit_vector3(v in unit_vector3()) {
+            prop_assert!(v.is_normalized(1e-5));
+        }
+    }
+}
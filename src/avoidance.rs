@@ -0,0 +1,169 @@
+//! Local collision avoidance for crowds of disc-shaped agents, based on
+//! reciprocal velocity obstacles (RVO).
+
+use crate::Vector2;
+
+/// Position, current velocity and radius of a disc-shaped agent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Agent {
+    pub position: Vector2<f32>,
+    pub velocity: Vector2<f32>,
+    pub radius: f32,
+}
+
+/// Tuning of the velocity selection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Avoidance {
+    pub max_speed: f32,
+    /// Collisions further in the future than this (in seconds) are ignored.
+    pub time_horizon: f32,
+    /// Trade-off between keeping the preferred velocity and avoiding collisions.
+    pub collision_weight: f32,
+}
+
+impl Default for Avoidance {
+    fn default() -> Self {
+        Avoidance {
+            max_speed: 1.0,
+            time_horizon: 2.0,
+            collision_weight: 1.0,
+        }
+    }
+}
+
+/// Time until two discs with relative position `p` (other minus self) and
+/// velocity `w` of self relative to other touch, or `None` if they never do.
+/// Overlapping discs collide at time 0.
+pub fn time_to_collision(p: Vector2<f32>, w: Vector2<f32>, radius: f32) -> Option<f32> {
+    let c = p.dot(&p) - radius * radius;
+    if c < 0.0 {
+        return Some(0.0);
+    }
+    let a = w.dot(&w);
+    let b = p.dot(&w);
+    let disc = b * b - a * c;
+    if a == 0.0 || disc < 0.0 || b <= 0.0 {
+        return None;
+    }
+    Some((b - disc.sqrt()) / a)
+}
+
+impl Avoidance {
+    // Penalized cost of choosing velocity `v`
+    fn cost(
+        &self,
+        agent: &Agent,
+        v: Vector2<f32>,
+        preferred: Vector2<f32>,
+        neighbors: &[Agent],
+    ) -> f32 {
+        let mut min_time = f32::INFINITY;
+        for other in neighbors {
+            // Each agent takes half the responsibility: test 2v - v_self against v_other
+            let w = v * 2.0 - agent.velocity - other.velocity;
+            let p = other.position - agent.position;
+            if let Some(t) = time_to_collision(p, w, agent.radius + other.radius) {
+                min_time = min_time.min(t);
+            }
+        }
+        let penalty = if min_time < self.time_horizon {
+            self.collision_weight / min_time.max(1e-3)
+        } else {
+            0.0
+        };
+        penalty + (v - preferred).magnitude()
+    }
+
+    /// Picks a new velocity close to `preferred` that avoids collisions with
+    /// `neighbors` within the time horizon, assuming they do the same.
+    pub fn velocity(
+        &self,
+        agent: &Agent,
+        preferred: Vector2<f32>,
+        neighbors: &[Agent],
+    ) -> Vector2<f32> {
+        const RINGS: usize = 8;
+        const DIRECTIONS: usize = 32;
+
+        let speed = preferred.magnitude();
+        let preferred = if speed > self.max_speed {
+            preferred * (self.max_speed / speed)
+        } else {
+            preferred
+        };
+
+        let mut best = preferred;
+        let mut best_cost = self.cost(agent, preferred, preferred, neighbors);
+        if best_cost == 0.0 {
+            return best;
+        }
+        for ring in 0..=RINGS {
+            let radius = self.max_speed * ring as f32 / RINGS as f32;
+            let count = if ring == 0 { 1 } else { DIRECTIONS };
+            for i in 0..count {
+                let angle = std::f32::consts::TAU * i as f32 / DIRECTIONS as f32;
+                let v = Vector2::new(angle.cos(), angle.sin()) * radius;
+                let cost = self.cost(agent, v, preferred, neighbors);
+                if cost < best_cost {
+                    best = v;
+                    best_cost = cost;
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_to_collision() {
+        let p = Vector2::new(10.0, 0.0);
+        assert_eq!(time_to_collision(p, Vector2::new(2.0, 0.0), 2.0), Some(4.0));
+        assert_eq!(time_to_collision(p, Vector2::new(-2.0, 0.0), 2.0), None);
+        assert_eq!(time_to_collision(p, Vector2::new(0.0, 2.0), 2.0), None);
+        assert_eq!(
+            time_to_collision(Vector2::new(1.0, 0.0), Vector2::new(0.0, 0.0), 2.0),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_no_neighbors_keeps_preferred_velocity() {
+        let avoidance = Avoidance::default();
+        let agent = Agent {
+            position: Vector2::new(0.0, 0.0),
+            velocity: Vector2::new(0.0, 0.0),
+            radius: 0.5,
+        };
+        let v = avoidance.velocity(&agent, Vector2::new(0.5, 0.0), &[]);
+        assert_eq!(v, Vector2::new(0.5, 0.0));
+        let v = avoidance.velocity(&agent, Vector2::new(3.0, 4.0), &[]);
+        assert_eq!(v, Vector2::new(0.6, 0.8));
+    }
+
+    #[test]
+    fn test_head_on_agents_swerve() {
+        let avoidance = Avoidance::default();
+        let a = Agent {
+            position: Vector2::new(0.0, 0.0),
+            velocity: Vector2::new(1.0, 0.0),
+            radius: 0.5,
+        };
+        let b = Agent {
+            position: Vector2::new(3.0, 0.0),
+            velocity: Vector2::new(-1.0, 0.0),
+            radius: 0.5,
+        };
+        let v = avoidance.velocity(&a, Vector2::new(1.0, 0.0), &[b]);
+        assert!(v.y.abs() > 0.1);
+
+        // With both agents taking their new velocity, the collision is delayed
+        // beyond the horizon
+        let w = avoidance.velocity(&b, Vector2::new(-1.0, 0.0), &[a]);
+        let ttc = time_to_collision(b.position - a.position, v - w, 1.0);
+        assert!(ttc.is_none_or(|t| t >= avoidance.time_horizon));
+    }
+}
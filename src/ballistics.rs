@@ -0,0 +1,240 @@
+//! Flight of arrows and other projectiles under gravity, quadratic air drag
+//! and wind, and aiming to hit a target despite them.
+//!
+//! Wind is given as a closure returning the air velocity at a position, so a
+//! constant breeze is `|_| breeze` and gusty or spatially varying wind fields
+//! plug in the same way.
+
+use crate::integrate::rk4_motion;
+use crate::Vector3;
+
+/// Most steps `Ballistics::trajectory` simulates, about 18 minutes of flight
+/// at 60 steps per second. Longer durations are cut short.
+pub const MAX_TRAJECTORY_STEPS: usize = 1 << 16;
+
+/// Physical parameters of a projectile's flight.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ballistics {
+    pub gravity: Vector3<f32>,
+    /// Quadratic drag constant `k = 0.5 * rho * cd * area / mass`, so the drag
+    /// deceleration is `k * |v_air|^2` against the velocity relative to the air.
+    pub drag: f32,
+    /// Integration time step, in seconds.
+    pub dt: f32,
+}
+
+impl Default for Ballistics {
+    fn default() -> Self {
+        Ballistics {
+            gravity: Vector3::new(0.0, -9.81, 0.0),
+            drag: 0.0,
+            dt: 1.0 / 60.0,
+        }
+    }
+}
+
+impl Ballistics {
+    pub const fn new(gravity: Vector3<f32>, drag: f32, dt: f32) -> Ballistics {
+        Ballistics { gravity, drag, dt }
+    }
+
+    /// Acceleration of a projectile at `position` moving with `velocity`.
+    pub fn acceleration<W>(
+        &self,
+        position: Vector3<f32>,
+        velocity: Vector3<f32>,
+        wind: &W,
+    ) -> Vector3<f32>
+    where
+        W: Fn(Vector3<f32>) -> Vector3<f32>,
+    {
+        let air = velocity - wind(position);
+        self.gravity - air * (self.drag * air.magnitude())
+    }
+
+    /// Advances the projectile by one time step with fourth order Runge-Kutta.
+    /// Returns the new `(position, velocity)`.
+    pub fn step<W>(
+        &self,
+        position: Vector3<f32>,
+        velocity: Vector3<f32>,
+        wind: &W,
+    ) -> (Vector3<f32>, Vector3<f32>)
+    where
+        W: Fn(Vector3<f32>) -> Vector3<f32>,
+    {
+        rk4_motion(position, velocity, 0.0, self.dt, |_, p, v| {
+            self.acceleration(p, v, wind)
+        })
+    }
+
+    /// Positions of the projectile at every time step for `duration` seconds,
+    /// starting with the launch position. Only the launch position is returned
+    /// if `dt` is not positive or the step count is not finite, and at most
+    /// `MAX_TRAJECTORY_STEPS` steps are taken.
+    pub fn trajectory<W>(
+        &self,
+        position: Vector3<f32>,
+        velocity: Vector3<f32>,
+        duration: f32,
+        wind: W,
+    ) -> Vec<Vector3<f32>>
+    where
+        W: Fn(Vector3<f32>) -> Vector3<f32>,
+    {
+        let steps = (duration / self.dt).ceil();
+        if self.dt <= 0.0 || !steps.is_finite() {
+            return vec![position];
+        }
+        let steps = (steps as usize).min(MAX_TRAJECTORY_STEPS);
+        let (mut p, mut v) = (position, velocity);
+        let mut points = Vec::with_capacity(steps + 1);
+        points.push(p);
+        for _ in 0..steps {
+            (p, v) = self.step(p, v, &wind);
+            points.push(p);
+        }
+        points
+    }
+
+    /// Point of closest approach to `target` of a projectile launched from
+    /// `origin` with `velocity`, simulated for at most `max_time` seconds.
+    fn closest_approach<W>(
+        &self,
+        origin: Vector3<f32>,
+        velocity: Vector3<f32>,
+        target: Vector3<f32>,
+        max_time: f32,
+        wind: &W,
+    ) -> Vector3<f32>
+    where
+        W: Fn(Vector3<f32>) -> Vector3<f32>,
+    {
+        let (mut p, mut v) = (origin, velocity);
+        let mut closest = p;
+        let mut best = f32::INFINITY;
+        let mut t = 0.0;
+        while t < max_time {
+            (p, v) = self.step(p, v, wind);
+            t += self.dt;
+            let d = (p - target).magnitude();
+            if d < best {
+                best = d;
+                closest = p;
+            } else if v.dot(&(target - p)) < 0.0 {
+                // Moving away and past the closest point
+                break;
+            }
+        }
+        closest
+    }
+
+    /// Unit launch direction for a projectile fired from `origin` at `speed`
+    /// to pass within `tolerance` of `target`, or `None` if no direction was
+    /// found, typically because the target is out of range. Also `None` if the
+    /// target is at the origin or `dt` is not positive.
+    ///
+    /// The aim point starts at the target and is repeatedly shifted against
+    /// the simulated miss, which finds the flat (low) arc.
+    pub fn solve_launch<W>(
+        &self,
+        origin: Vector3<f32>,
+        target: Vector3<f32>,
+        speed: f32,
+        tolerance: f32,
+        wind: W,
+    ) -> Option<Vector3<f32>>
+    where
+        W: Fn(Vector3<f32>) -> Vector3<f32>,
+    {
+        let distance = (target - origin).magnitude();
+        if distance == 0.0 || speed <= 0.0 || self.dt <= 0.0 {
+            return None;
+        }
+        // Generous flight time limit: drag and lofted arcs slow the arrival
+        let max_time = 4.0 * distance / speed + 2.0 * speed / self.gravity.magnitude().max(1e-6);
+
+        let mut aim = target;
+        for _ in 0..32 {
+            // The aim point can be shifted back onto the origin
+            let dir = (aim - origin).try_normalize().ok()?;
+            let miss = self.closest_approach(origin, dir * speed, target, max_time, &wind) - target;
+            if miss.magnitude() <= tolerance {
+                return Some(dir);
+            }
+            aim -= miss;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calm(_: Vector3<f32>) -> Vector3<f32> {
+        Vector3::default()
+    }
+
+    #[test]
+    fn test_vacuum_trajectory() {
+        let b = Ballistics::new(Vector3::new(0.0, -10.0, 0.0), 0.0, 0.1);
+        let points = b.trajectory(Vector3::default(), Vector3::new(5.0, 10.0, 0.0), 2.0, calm);
+        assert_eq!(points.len(), 21);
+        // Lands back at the launch height after 2 seconds, 10 units away
+        let end = points[20];
+        assert!((end - Vector3::new(10.0, 0.0, 0.0)).magnitude() < 1e-4);
+    }
+
+    #[test]
+    fn test_drag_and_wind() {
+        let b = Ballistics::new(Vector3::default(), 0.1, 0.01);
+        let v = Vector3::new(10.0, 0.0, 0.0);
+        let a = b.acceleration(Vector3::default(), v, &calm);
+        assert_eq!(a, Vector3::new(-10.0, 0.0, 0.0));
+        // A tailwind as fast as the arrow removes the drag
+        assert_eq!(
+            b.acceleration(Vector3::default(), v, &|_| v),
+            Vector3::default()
+        );
+        // A crosswind pushes the arrow sideways
+        let (_, v) = b.step(Vector3::default(), v, &|_| Vector3::new(0.0, 0.0, 5.0));
+        assert!(v.z > 0.0);
+    }
+
+    #[test]
+    fn test_solve_launch() {
+        let b = Ballistics::new(Vector3::new(0.0, -9.81, 0.0), 0.002, 1.0 / 120.0);
+        let origin = Vector3::new(0.0, 1.5, 0.0);
+        let target = Vector3::new(60.0, 2.0, 10.0);
+        let wind = |_: Vector3<f32>| Vector3::new(0.0, 0.0, -4.0);
+        let dir = b.solve_launch(origin, target, 50.0, 0.05, wind).unwrap();
+        assert!((dir.magnitude() - 1.0).abs() < 1e-5);
+        // Aims above the target and upwind of it
+        assert!(dir.y > 0.0 && dir.z > 10.0 / 60.0 * dir.x);
+
+        let hit = b.closest_approach(origin, dir * 50.0, target, 10.0, &wind);
+        assert!((hit - target).magnitude() <= 0.05);
+
+        assert_eq!(
+            b.solve_launch(origin, Vector3::new(5000.0, 0.0, 0.0), 50.0, 0.05, calm),
+            None
+        );
+        assert_eq!(b.solve_launch(origin, origin, 50.0, 0.05, calm), None);
+        let frozen = Ballistics { dt: 0.0, ..b };
+        assert_eq!(frozen.solve_launch(origin, target, 50.0, 0.05, calm), None);
+    }
+
+    #[test]
+    fn test_trajectory_invalid_step() {
+        let b = Ballistics::new(Vector3::new(0.0, -10.0, 0.0), 0.0, 0.0);
+        let p = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(b.trajectory(p, Vector3::default(), 2.0, calm), vec![p]);
+        let b = Ballistics { dt: -0.1, ..b };
+        assert_eq!(b.trajectory(p, Vector3::default(), 2.0, calm), vec![p]);
+        // Finite, but far more steps than fit in memory
+        let b = Ballistics { dt: 1e-6, ..b };
+        let points = b.trajectory(p, Vector3::default(), 1e30, calm);
+        assert_eq!(points.len(), MAX_TRAJECTORY_STEPS + 1);
+    }
+}
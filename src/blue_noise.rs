@@ -0,0 +1,237 @@
+//! Blue noise dither masks, for stippling, dithering and sample placement
+//! that look random but have no clumps or gaps.
+//!
+//! A mask is generated once, typically offline, from a seed, and then tiles
+//! the plane: thresholds are looked up by integer cell, wrapping around.
+
+use crate::hash::hash_u32;
+use crate::Vector2;
+
+/// A tileable square dither mask of thresholds in [0, 1) with a blue noise
+/// spectrum, built offline with Ulichney's void-and-cluster method.
+///
+/// Thresholding the mask at any density `d` keeps roughly `d * size * size`
+/// cells that are evenly spread out without visible regular structure.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlueNoiseMask {
+    size: u32,
+    thresholds: Vec<f32>,
+}
+
+// Toroidal Gaussian energy of a binary pattern, updated incrementally
+#[derive(Clone)]
+struct Energy {
+    size: usize,
+    kernel: Vec<f32>,
+    values: Vec<f32>,
+}
+
+impl Energy {
+    fn new(size: usize, sigma: f32) -> Energy {
+        let mut kernel = vec![0.0; size * size];
+        for dy in 0..size {
+            for dx in 0..size {
+                let wx = dx.min(size - dx) as f32;
+                let wy = dy.min(size - dy) as f32;
+                kernel[dy * size + dx] = (-(wx * wx + wy * wy) / (2.0 * sigma * sigma)).exp();
+            }
+        }
+        Energy {
+            size,
+            kernel,
+            values: vec![0.0; size * size],
+        }
+    }
+
+    fn splat(&mut self, index: usize, sign: f32) {
+        let (px, py) = (index % self.size, index / self.size);
+        for y in 0..self.size {
+            let dy = (y + self.size - py) % self.size;
+            for x in 0..self.size {
+                let dx = (x + self.size - px) % self.size;
+                self.values[y * self.size + x] += sign * self.kernel[dy * self.size + dx];
+            }
+        }
+    }
+
+    // Set cell with the highest energy
+    fn tightest_cluster(&self, pattern: &[bool]) -> usize {
+        let mut best = 0;
+        let mut best_energy = f32::NEG_INFINITY;
+        for (i, &e) in self.values.iter().enumerate() {
+            if pattern[i] && e > best_energy {
+                best = i;
+                best_energy = e;
+            }
+        }
+        best
+    }
+
+    // Unset cell with the lowest energy
+    fn largest_void(&self, pattern: &[bool]) -> usize {
+        let mut best = 0;
+        let mut best_energy = f32::INFINITY;
+        for (i, &e) in self.values.iter().enumerate() {
+            if !pattern[i] && e < best_energy {
+                best = i;
+                best_energy = e;
+            }
+        }
+        best
+    }
+}
+
+impl BlueNoiseMask {
+    /// Generates a `size` x `size` mask. `sigma` is the width of the Gaussian
+    /// energy filter in cells (1.5 is the usual choice) and `seed` selects the
+    /// initial random pattern. Generation is O(size^4), meant for offline use.
+    /// Panics if `size` is zero, since an empty mask has no threshold to tile.
+    pub fn generate(size: u32, sigma: f32, seed: u32) -> BlueNoiseMask {
+        assert!(size > 0, "blue noise mask size must be positive");
+        let n = (size * size) as usize;
+        let mut energy = Energy::new(size as usize, sigma);
+        let mut pattern = vec![false; n];
+
+        // Initial random pattern covering about a tenth of the cells
+        let ones = (n / 10).max(1);
+        let mut placed = 0;
+        let mut round = 0;
+        while placed < ones {
+            let i = hash_u32(round, 0, 0, seed) as usize % n;
+            if !pattern[i] {
+                pattern[i] = true;
+                energy.splat(i, 1.0);
+                placed += 1;
+            }
+            round += 1;
+        }
+
+        // Move points from the tightest cluster to the largest void until stable
+        for _ in 0..n {
+            let cluster = energy.tightest_cluster(&pattern);
+            pattern[cluster] = false;
+            energy.splat(cluster, -1.0);
+            let void = energy.largest_void(&pattern);
+            pattern[void] = true;
+            energy.splat(void, 1.0);
+            if void == cluster {
+                break;
+            }
+        }
+
+        let mut ranks = vec![0usize; n];
+
+        // Ranks below the initial density: remove the tightest clusters
+        let mut removing = pattern.clone();
+        let mut removing_energy = energy.clone();
+        for rank in (0..ones).rev() {
+            let cluster = removing_energy.tightest_cluster(&removing);
+            removing[cluster] = false;
+            removing_energy.splat(cluster, -1.0);
+            ranks[cluster] = rank;
+        }
+
+        // Remaining ranks: fill the largest voids
+        for rank in ones..n {
+            let void = energy.largest_void(&pattern);
+            pattern[void] = true;
+            energy.splat(void, 1.0);
+            ranks[void] = rank;
+        }
+
+        BlueNoiseMask {
+            size,
+            thresholds: ranks.into_iter().map(|r| r as f32 / n as f32).collect(),
+        }
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Threshold at the given cell. The mask tiles, so any coordinate is valid.
+    pub fn threshold(&self, p: Vector2<i32>) -> f32 {
+        let size = self.size as i32;
+        let x = p.x.rem_euclid(size);
+        let y = p.y.rem_euclid(size);
+        self.thresholds[(y * size + x) as usize]
+    }
+
+    /// Cells of one tile whose threshold is below `density`.
+    pub fn points(&self, density: f32) -> Vec<Vector2<i32>> {
+        let size = self.size as i32;
+        let mut points = Vec::new();
+        for y in 0..size {
+            for x in 0..size {
+                let p = Vector2::new(x, y);
+                if self.threshold(p) < density {
+                    points.push(p);
+                }
+            }
+        }
+        points
+    }
+
+    /// Sample points of one tile at the given density, placed at cell centers
+    /// and scaled to the unit square.
+    pub fn sample_points(&self, density: f32) -> Vec<Vector2<f32>> {
+        let inv_size = (self.size as f32).recip();
+        self.points(density)
+            .into_iter()
+            .map(|p| Vector2::new(p.x as f32 + 0.5, p.y as f32 + 0.5) * inv_size)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thresholds_are_a_permutation() {
+        let mask = BlueNoiseMask::generate(8, 1.5, 1);
+        let mut ranks: Vec<u32> = mask.thresholds.iter().map(|t| (t * 64.0) as u32).collect();
+        ranks.sort();
+        assert_eq!(ranks, (0..64).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "size must be positive")]
+    fn test_generate_empty() {
+        BlueNoiseMask::generate(0, 1.5, 1);
+    }
+
+    #[test]
+    fn test_threshold_tiles() {
+        let mask = BlueNoiseMask::generate(8, 1.5, 2);
+        assert_eq!(
+            mask.threshold(Vector2::new(-1, 3)),
+            mask.threshold(Vector2::new(7, 11))
+        );
+    }
+
+    #[test]
+    fn test_points_density() {
+        let mask = BlueNoiseMask::generate(16, 1.5, 3);
+        assert_eq!(mask.points(0.25).len(), 64);
+        assert_eq!(mask.sample_points(0.5).len(), 128);
+        assert!(mask
+            .sample_points(1.0)
+            .iter()
+            .all(|p| (0.0..1.0).contains(&p.x) && (0.0..1.0).contains(&p.y)));
+    }
+
+    #[test]
+    fn test_points_are_spread_out() {
+        // At 1/8 density blue noise keeps points apart, unlike white noise
+        let mask = BlueNoiseMask::generate(16, 1.5, 4);
+        let points = mask.points(0.125);
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                let dx = (a.x - b.x).abs().min(16 - (a.x - b.x).abs());
+                let dy = (a.y - b.y).abs().min(16 - (a.y - b.y).abs());
+                assert!(dx * dx + dy * dy > 1);
+            }
+        }
+    }
+}
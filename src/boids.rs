@@ -0,0 +1,147 @@
+//! Craig Reynolds' boids flocking rules, in 2D or 3D.
+//!
+//! Each rule takes the boid's own state and slices with its neighbors, as
+//! gathered by a spatial query such as
+//! [`SpatialHash::within`](crate::spatial_hash::SpatialHash::within), and
+//! returns an unweighted steering vector. A flock typically adds them with
+//! tuned weights and feeds the result to [`crate::steering::Steering`].
+//! All rules return zero when there are no neighbors.
+
+use crate::components::{delta, dot};
+use crate::Components;
+
+fn zero<V: Components>() -> V {
+    V::from_components(|_| 0.0)
+}
+
+/// Mean of `points`, accumulated one component at a time.
+fn mean<V: Components>(points: &[V]) -> V {
+    let n = points.len() as f32;
+    V::from_components(|i| points.iter().map(|p| p.component(i)).sum::<f32>() / n)
+}
+
+/// Pushes away from neighbors closer than `radius`, each weighted by the
+/// inverse of its distance so the closest dominate. Neighbors at exactly the
+/// boid's position are skipped, which also excludes the boid itself.
+pub fn separation<V: Components>(position: &V, neighbors: &[V], radius: f32) -> V {
+    let pushes: Vec<(V, f32)> = neighbors
+        .iter()
+        .map(|n| {
+            let d = delta(n, position);
+            (d, dot(&d, &d))
+        })
+        .filter(|&(_, d_sq)| d_sq > 0.0 && d_sq < radius * radius)
+        .collect();
+    // Away direction over distance: (p - n) / |p - n|^2, one axis at a time
+    // so any `V::DIM` works
+    V::from_components(|i| pushes.iter().map(|(d, d_sq)| d.component(i) / d_sq).sum())
+}
+
+/// Matches the neighbors' heading: the difference between their mean
+/// velocity and the boid's own.
+pub fn alignment<V: Components>(velocity: &V, neighbor_velocities: &[V]) -> V {
+    if neighbor_velocities.is_empty() {
+        return zero();
+    }
+    let avg = mean(neighbor_velocities);
+    delta(velocity, &avg)
+}
+
+/// Draws toward the neighbors' center of mass: the offset from the boid to
+/// the mean of their positions.
+pub fn cohesion<V: Components>(position: &V, neighbors: &[V]) -> V {
+    if neighbors.is_empty() {
+        return zero();
+    }
+    let center = mean(neighbors);
+    delta(position, &center)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector2, Vector3};
+
+    #[test]
+    fn test_separation() {
+        let p = Vector2::new(0.0, 0.0);
+        let neighbors = [
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, -2.0),
+            Vector2::new(5.0, 5.0),
+            p,
+        ];
+        assert_eq!(separation(&p, &neighbors, 3.0), Vector2::new(-1.0, 0.5));
+        assert_eq!(separation(&p, &neighbors, 0.5), Vector2::new(0.0, 0.0));
+    }
+
+    // A user vector type with more axes than the crate's own
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Vector6([f32; 6]);
+
+    impl Components for Vector6 {
+        const DIM: usize = 6;
+
+        fn component(&self, axis: usize) -> f32 {
+            self.0[axis]
+        }
+
+        fn from_components<F: FnMut(usize) -> f32>(f: F) -> Self {
+            Vector6(std::array::from_fn(f))
+        }
+    }
+
+    #[test]
+    fn test_separation_high_dim() {
+        let p = Vector6([0.0; 6]);
+        let n = Vector6([0.0, 0.0, 0.0, 0.0, 0.0, 2.0]);
+        assert_eq!(
+            separation(&p, &[n], 3.0),
+            Vector6([0.0, 0.0, 0.0, 0.0, 0.0, -0.5])
+        );
+    }
+
+    #[test]
+    fn test_neighbors_from_spatial_hash() {
+        use crate::spatial_hash::SpatialHash;
+
+        let flock = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.5, 0.0, 0.0),
+            Vector3::new(0.0, -1.0, 0.0),
+            Vector3::new(4.0, 4.0, 4.0),
+        ];
+        let grid = SpatialHash::from_points(&flock, 1.5);
+        let neighbors: Vec<_> = grid
+            .within(&flock, flock[0], 1.5)
+            .map(|i| flock[i])
+            .collect();
+        assert_eq!(neighbors.len(), 3);
+        assert_eq!(
+            separation(&flock[0], &neighbors, 1.5),
+            Vector3::new(-2.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_alignment_and_cohesion() {
+        let velocities = [Vector3::new(2.0, 0.0, 0.0), Vector3::new(0.0, 2.0, 4.0)];
+        assert_eq!(
+            alignment(&Vector3::new(1.0, 0.0, 0.0), &velocities),
+            Vector3::new(0.0, 1.0, 2.0)
+        );
+        let positions = [Vector3::new(4.0, 0.0, 2.0), Vector3::new(0.0, 4.0, 2.0)];
+        assert_eq!(
+            cohesion(&Vector3::new(1.0, 1.0, 1.0), &positions),
+            Vector3::new(1.0, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_no_neighbors() {
+        let p = Vector2::new(3.0, 4.0);
+        assert_eq!(separation(&p, &[], 1.0), Vector2::new(0.0, 0.0));
+        assert_eq!(alignment(&p, &[]), Vector2::new(0.0, 0.0));
+        assert_eq!(cohesion(&p, &[]), Vector2::new(0.0, 0.0));
+    }
+}
@@ -0,0 +1,56 @@
+//! Component type conversions with `as` semantics, and the rounding modes
+//! applied before casting floats to integers.
+
+use crate::Float;
+
+/// Conversion between primitive numeric types with the semantics of `as`:
+/// float to integer truncates toward zero and saturates, NaN becomes 0, and
+/// integer to integer wraps.
+pub trait AsPrimitive<U>: Copy {
+    fn as_(self) -> U;
+}
+
+macro_rules! impl_as_primitive {
+    ($from:ty => $($to:ty),*) => {
+        $(
+            impl AsPrimitive<$to> for $from {
+                #[inline]
+                fn as_(self) -> $to {
+                    self as $to
+                }
+            }
+        )*
+    };
+    ($($from:ty),*) => {
+        $(
+            impl_as_primitive!($from => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+        )*
+    };
+}
+
+impl_as_primitive!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// How float components are rounded to whole numbers before a cast.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// Toward zero, like a plain `as` cast.
+    #[default]
+    Truncate,
+    /// Toward negative infinity, e.g. world positions to tile coordinates.
+    Floor,
+    /// Toward positive infinity.
+    Ceil,
+    /// To the nearest integer, halfway cases away from zero.
+    Round,
+}
+
+impl Rounding {
+    pub(crate) fn apply<T: Float>(self, v: T) -> T {
+        match self {
+            Rounding::Truncate => v.trunc(),
+            Rounding::Floor => v.floor(),
+            Rounding::Ceil => v.ceil(),
+            Rounding::Round => v.round(),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Vector math of kinematic character controllers: which surfaces can be
+//! walked on, sliding along the rest, snapping to the ground, climbing steps
+//! and grabbing ledges. The world up axis is +y.
+
+use crate::geometry3d::RayHit;
+use crate::Vector3;
+
+/// How a character treats a surface it touches, by the slope of its normal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Surface {
+    /// Flat enough to stand on.
+    Walkable,
+    /// Too steep to stand on, including vertical walls.
+    Steep,
+    /// Facing down.
+    Ceiling,
+}
+
+/// Classifies a surface by the angle between its `normal` and +y, walkable up
+/// to `max_slope` radians.
+pub fn classify_surface(normal: Vector3<f32>, max_slope: f32) -> Surface {
+    let cos = normal.y / normal.magnitude();
+    if cos >= max_slope.cos() {
+        Surface::Walkable
+    } else if cos >= 0.0 {
+        Surface::Steep
+    } else {
+        Surface::Ceiling
+    }
+}
+
+/// Redirects `velocity` after touching a surface with `normal`.
+///
+/// On walkable ground the motion follows the slope at the same speed, so
+/// running up or down a ramp is not slower than on flat ground. Against steep
+/// surfaces the character slides along them horizontally, without being
+/// lifted up the wall; against ceilings it loses the upward part.
+pub fn project_velocity(
+    velocity: Vector3<f32>,
+    normal: Vector3<f32>,
+    max_slope: f32,
+) -> Vector3<f32> {
+    match classify_surface(normal, max_slope) {
+        Surface::Walkable => {
+            let along = velocity.project_onto_plane(&normal);
+            let len = along.magnitude();
+            if len > 0.0 {
+                along * (velocity.magnitude() / len)
+            } else {
+                along
+            }
+        }
+        Surface::Steep => {
+            let wall = Vector3::new(normal.x, 0.0, normal.z);
+            if velocity.dot(&wall) >= 0.0 || wall.dot(&wall) == 0.0 {
+                velocity
+            } else {
+                velocity.project_onto_plane(&wall)
+            }
+        }
+        Surface::Ceiling => {
+            if velocity.dot(&normal) >= 0.0 {
+                velocity
+            } else {
+                velocity.project_onto_plane(&normal)
+            }
+        }
+    }
+}
+
+/// Moves the feet `position` down onto the ground found by a downward probe
+/// at `hit_distance` below it, if the ground is within `max_snap_distance`.
+/// Keeps characters glued to the floor when walking down slopes and stairs
+/// instead of launching off each edge. Returns `None` when airborne.
+pub fn snap_to_ground(
+    position: Vector3<f32>,
+    hit_distance: Option<f32>,
+    max_snap_distance: f32,
+) -> Option<Vector3<f32>> {
+    let distance = hit_distance.filter(|d| *d <= max_snap_distance)?;
+    Some(position - Vector3::new(0.0, distance, 0.0))
+}
+
+/// Height to climb to step over an obstacle hit by a horizontal capsule
+/// sweep, given the character's `feet` position and the sweep's contact
+/// `hit_point` and `hit_normal`. Only obstacles too steep to walk up whose
+/// contact is at most `max_step_height` above the feet are steps; returns
+/// `None` otherwise. The caller should still sweep up and forward by this
+/// height to check the space above the step is free.
+pub fn step_height(
+    feet: Vector3<f32>,
+    hit_point: Vector3<f32>,
+    hit_normal: Vector3<f32>,
+    max_step_height: f32,
+    max_slope: f32,
+) -> Option<f32> {
+    let height = hit_point.y - feet.y;
+    let is_step = classify_surface(hit_normal, max_slope) == Surface::Steep
+        && height > 0.0
+        && height <= max_step_height;
+    is_step.then_some(height)
+}
+
+/// A ledge the character can hang from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ledge {
+    /// Point on the top edge of the wall, where the hands go.
+    pub grab_point: Vector3<f32>,
+    /// Horizontal unit vector from the character toward the wall.
+    pub facing: Vector3<f32>,
+    /// Height of the edge above the character's feet.
+    pub height: f32,
+}
+
+/// Detects a ledge from two probes: `wall` from a horizontal ray cast forward
+/// from the character, and `top` from a ray cast down from above the wall just
+/// past the wall hit point. A ledge needs a steep wall, a walkable top, and an
+/// edge between `min_height` and `max_height` above `feet`.
+pub fn detect_ledge(
+    feet: Vector3<f32>,
+    wall: &RayHit,
+    top: Option<&RayHit>,
+    min_height: f32,
+    max_height: f32,
+    max_slope: f32,
+) -> Option<Ledge> {
+    let top = top?;
+    if classify_surface(wall.normal, max_slope) != Surface::Steep
+        || classify_surface(top.normal, max_slope) != Surface::Walkable
+    {
+        return None;
+    }
+    let height = top.point.y - feet.y;
+    if height < min_height || height > max_height {
+        return None;
+    }
+    let facing = Vector3::new(-wall.normal.x, 0.0, -wall.normal.z).normalize();
+    Some(Ledge {
+        grab_point: Vector3::new(wall.point.x, top.point.y, wall.point.z),
+        facing,
+        height,
+    })
+}
+
+/// Where to cast the downward `top` probe of `detect_ledge`: `inset` past the
+/// wall hit point into the wall, from `reach` above the feet.
+pub fn ledge_probe_origin(
+    feet: Vector3<f32>,
+    wall: &RayHit,
+    inset: f32,
+    reach: f32,
+) -> Vector3<f32> {
+    let into = Vector3::new(-wall.normal.x, 0.0, -wall.normal.z).normalize();
+    Vector3::new(wall.point.x, feet.y + reach, wall.point.z) + into * inset
+}
+
+/// Whether a downward probe cast just ahead of the character reveals a drop
+/// deeper than `max_drop` below the feet at `feet_height`, such as the edge
+/// of a platform. A probe that hit nothing is a drop too.
+pub fn is_drop_edge(feet_height: f32, probe: Option<&RayHit>, max_drop: f32) -> bool {
+    probe.is_none_or(|hit| feet_height - hit.point.y > max_drop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    #[test]
+    fn test_classify_surface() {
+        let max = FRAC_PI_4;
+        assert_eq!(
+            classify_surface(Vector3::new(0.0, 2.0, 0.0), max),
+            Surface::Walkable
+        );
+        assert_eq!(
+            classify_surface(Vector3::new(0.5, 1.0, 0.0), max),
+            Surface::Walkable
+        );
+        assert_eq!(
+            classify_surface(Vector3::new(1.0, 0.5, 0.0), max),
+            Surface::Steep
+        );
+        assert_eq!(
+            classify_surface(Vector3::new(1.0, 0.0, 0.0), max),
+            Surface::Steep
+        );
+        assert_eq!(
+            classify_surface(Vector3::new(0.0, -1.0, 0.0), max),
+            Surface::Ceiling
+        );
+    }
+
+    #[test]
+    fn test_project_velocity() {
+        let max = FRAC_PI_4;
+        // Up a 30 degree ramp rising along +x, keeping the speed
+        let ramp = Vector3::new(-0.5, 0.75f32.sqrt(), 0.0);
+        let v = project_velocity(Vector3::new(2.0, 0.0, 0.0), ramp, max);
+        assert!((v.magnitude() - 2.0).abs() < 1e-5);
+        assert!(v.dot(&ramp).abs() < 1e-5 && v.y > 0.0);
+
+        // Sliding along a steep wall facing -x does not climb it
+        let wall = Vector3::new(-1.0, 0.2, 0.0);
+        let v = project_velocity(Vector3::new(1.0, 0.0, 1.0), wall, max);
+        assert_eq!(v, Vector3::new(0.0, 0.0, 1.0));
+        // Moving away from the wall is unaffected
+        let away = Vector3::new(-1.0, 0.0, 1.0);
+        assert_eq!(project_velocity(away, wall, max), away);
+
+        let v = project_velocity(
+            Vector3::new(1.0, 3.0, 0.0),
+            Vector3::new(0.0, -1.0, 0.0),
+            max,
+        );
+        assert_eq!(v, Vector3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_snap_and_step() {
+        let feet = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(
+            snap_to_ground(feet, Some(0.25), 0.5),
+            Some(Vector3::new(1.0, 1.75, 3.0))
+        );
+        assert_eq!(snap_to_ground(feet, Some(1.0), 0.5), None);
+        assert_eq!(snap_to_ground(feet, None, 0.5), None);
+
+        let riser = Vector3::new(-1.0, 0.0, 0.0);
+        let max = FRAC_PI_4;
+        assert_eq!(
+            step_height(feet, Vector3::new(1.5, 2.25, 3.0), riser, 0.5, max),
+            Some(0.25)
+        );
+        assert_eq!(
+            step_height(feet, Vector3::new(1.5, 3.0, 3.0), riser, 0.5, max),
+            None
+        );
+        let ramp = Vector3::new(-0.2, 1.0, 0.0);
+        assert_eq!(
+            step_height(feet, Vector3::new(1.5, 2.25, 3.0), ramp, 0.5, max),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ledges() {
+        let feet = Vector3::new(0.0, 0.0, 0.0);
+        let wall = RayHit::new(
+            Vector3::new(0.0, 1.0, 1.0),
+            Vector3::new(0.0, 0.0, -1.0),
+            1.0,
+        );
+        let origin = ledge_probe_origin(feet, &wall, 0.25, 3.0);
+        assert_eq!(origin, Vector3::new(0.0, 3.0, 1.25));
+
+        let top = RayHit::new(
+            Vector3::new(0.0, 2.0, 1.25),
+            Vector3::new(0.0, 1.0, 0.0),
+            1.0,
+        );
+        let ledge = detect_ledge(feet, &wall, Some(&top), 1.0, 2.5, FRAC_PI_4).unwrap();
+        assert_eq!(ledge.grab_point, Vector3::new(0.0, 2.0, 1.0));
+        assert_eq!(ledge.facing, Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(ledge.height, 2.0);
+
+        // Too high, no top, or a sloped top
+        assert_eq!(
+            detect_ledge(feet, &wall, Some(&top), 1.0, 1.5, FRAC_PI_4),
+            None
+        );
+        assert_eq!(detect_ledge(feet, &wall, None, 1.0, 2.5, FRAC_PI_4), None);
+        let slope = RayHit::new(top.point, Vector3::new(0.0, 1.0, -2.0), 1.0);
+        assert_eq!(
+            detect_ledge(feet, &wall, Some(&slope), 1.0, 2.5, FRAC_PI_4),
+            None
+        );
+
+        let floor = RayHit::new(
+            Vector3::new(0.0, -0.25, 1.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            1.0,
+        );
+        assert!(!is_drop_edge(0.0, Some(&floor), 0.5));
+        assert!(is_drop_edge(1.0, Some(&floor), 0.5));
+        assert!(is_drop_edge(0.0, None, 0.5));
+    }
+}
@@ -0,0 +1,57 @@
+//! Per-axis access to vector components, for algorithms written once for
+//! every dimension.
+
+use crate::{Float, Vector2, Vector3, Vector4};
+
+/// Access to the components of a vector by axis index. `T` defaults to
+/// `f32`, the component type most helpers in this crate work with.
+pub trait Components<T = f32>: Copy {
+    const DIM: usize;
+
+    fn component(&self, axis: usize) -> T;
+
+    /// Vector whose component on each axis index is `f(index)`.
+    fn from_components<F: FnMut(usize) -> T>(f: F) -> Self;
+}
+
+macro_rules! impl_components {
+    ($($v:ident => $dim:expr, [$($c:ident),*]);*) => {
+        $(
+            impl<T: Float> Components<T> for $v<T> {
+                const DIM: usize = $dim;
+
+                fn component(&self, axis: usize) -> T {
+                    [$(self.$c),*][axis]
+                }
+
+                fn from_components<F: FnMut(usize) -> T>(f: F) -> Self {
+                    let [$($c),*]: [T; $dim] = std::array::from_fn(f);
+                    $v { $($c),* }
+                }
+            }
+        )*
+    };
+}
+
+impl_components!(
+    Vector2 => 2, [x, y];
+    Vector3 => 3, [x, y, z];
+    Vector4 => 4, [x, y, z, w]
+);
+
+// Helpers for the modules written over `Components`, so each doesn't carry
+// its own copy
+
+pub(crate) fn dot<V: Components>(a: &V, b: &V) -> f32 {
+    (0..V::DIM).map(|i| a.component(i) * b.component(i)).sum()
+}
+
+// Vector from `from` to `to`
+pub(crate) fn delta<V: Components>(from: &V, to: &V) -> V {
+    V::from_components(|i| to.component(i) - from.component(i))
+}
+
+pub(crate) fn distance<V: Components>(a: &V, b: &V) -> f32 {
+    let d = delta(a, b);
+    dot(&d, &d).sqrt()
+}
@@ -0,0 +1,235 @@
+//! View frustum culling of bounding boxes and spheres, one at a time or in
+//! batches.
+
+use crate::distance::Plane;
+use crate::{Matrix4, Vector3};
+
+/// Axis-aligned bounding box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vector3<f32>,
+    pub max: Vector3<f32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sphere {
+    pub center: Vector3<f32>,
+    pub radius: f32,
+}
+
+impl Aabb {
+    pub const fn new(min: Vector3<f32>, max: Vector3<f32>) -> Aabb {
+        Aabb { min, max }
+    }
+
+    pub fn center(&self) -> Vector3<f32> {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Half the size of the box along each axis.
+    pub fn extents(&self) -> Vector3<f32> {
+        (self.max - self.min) * 0.5
+    }
+
+    /// Whether `point` is inside the box or on its boundary.
+    pub fn contains(&self, point: &Vector3<f32>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+}
+
+impl Sphere {
+    pub const fn new(center: Vector3<f32>, radius: f32) -> Sphere {
+        Sphere { center, radius }
+    }
+
+    /// Whether `point` is inside the sphere or on its surface.
+    pub fn contains(&self, point: &Vector3<f32>) -> bool {
+        let d = *point - self.center;
+        d.dot(&d) <= self.radius * self.radius
+    }
+}
+
+/// Convex volume bounded by six planes with normals pointing inward, in the
+/// order left, right, bottom, top, near, far.
+///
+/// The tests are conservative: a shape is only culled when it lies entirely
+/// outside one plane, so some shapes near the frustum corners are kept even
+/// though they are not visible.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    pub const fn new(planes: [Plane; 6]) -> Frustum {
+        Frustum { planes }
+    }
+
+    /// Whether `point` is inside the frustum or on its boundary.
+    pub fn contains(&self, point: &Vector3<f32>) -> bool {
+        self.planes.iter().all(|p| p.distance(*point) >= 0.0)
+    }
+
+    /// Extracts the frustum from a view-projection matrix with depth mapped to
+    /// [-1, 1], such as `Matrix4::perspective` times a view matrix. The planes
+    /// are in the space the matrix transforms from.
+    pub fn from_matrix(m: &Matrix4<f32>) -> Frustum {
+        let rows = [m.row(0), m.row(1), m.row(2)];
+        let w = m.row(3);
+        let plane = |i: usize, sign: f32| {
+            let p = w + rows[i] * sign;
+            let normal = Vector3::new(p.x, p.y, p.z);
+            let len = normal.magnitude();
+            Plane::new(normal * len.recip(), p.w / len)
+        };
+        Frustum::new([
+            plane(0, 1.0),
+            plane(0, -1.0),
+            plane(1, 1.0),
+            plane(1, -1.0),
+            plane(2, 1.0),
+            plane(2, -1.0),
+        ])
+    }
+
+    /// Whether `aabb` is at least partially inside the frustum.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let (c, e) = (aabb.center(), aabb.extents());
+        self.planes.iter().all(|p| {
+            let n = p.normal;
+            // Distance of the box corner furthest along the normal
+            p.distance(c) + n.x.abs() * e.x + n.y.abs() * e.y + n.z.abs() * e.z >= 0.0
+        })
+    }
+
+    /// Whether `sphere` is at least partially inside the frustum.
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|p| p.distance(sphere.center) >= -sphere.radius)
+    }
+
+    /// Visibility of many boxes, `true` for those at least partially inside.
+    ///
+    /// The planes are unpacked into per-component arrays once and the plane
+    /// loop is branch free, so the compiler can vectorize it.
+    pub fn cull_aabbs(&self, aabbs: &[Aabb]) -> Vec<bool> {
+        let planes = PlaneSet::new(&self.planes);
+        aabbs
+            .iter()
+            .map(|b| {
+                let (c, e) = (b.center(), b.extents());
+                let mut inside = true;
+                for i in 0..6 {
+                    let dist =
+                        planes.nx[i] * c.x + planes.ny[i] * c.y + planes.nz[i] * c.z + planes.d[i];
+                    let radius = planes.nx[i].abs() * e.x
+                        + planes.ny[i].abs() * e.y
+                        + planes.nz[i].abs() * e.z;
+                    inside &= dist + radius >= 0.0;
+                }
+                inside
+            })
+            .collect()
+    }
+
+    /// Visibility of many spheres, `true` for those at least partially inside.
+    pub fn cull_spheres(&self, spheres: &[Sphere]) -> Vec<bool> {
+        let planes = PlaneSet::new(&self.planes);
+        spheres
+            .iter()
+            .map(|s| {
+                let c = s.center;
+                let mut inside = true;
+                for i in 0..6 {
+                    let dist =
+                        planes.nx[i] * c.x + planes.ny[i] * c.y + planes.nz[i] * c.z + planes.d[i];
+                    inside &= dist >= -s.radius;
+                }
+                inside
+            })
+            .collect()
+    }
+}
+
+/// Frustum planes in structure-of-arrays layout for the batch tests.
+struct PlaneSet {
+    nx: [f32; 6],
+    ny: [f32; 6],
+    nz: [f32; 6],
+    d: [f32; 6],
+}
+
+impl PlaneSet {
+    fn new(planes: &[Plane; 6]) -> PlaneSet {
+        PlaneSet {
+            nx: planes.map(|p| p.normal.x),
+            ny: planes.map(|p| p.normal.y),
+            nz: planes.map(|p| p.normal.z),
+            d: planes.map(|p| p.d),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn frustum() -> Frustum {
+        // Camera at the origin looking down -z
+        Frustum::from_matrix(&Matrix4::perspective(FRAC_PI_2, 1.0, 1.0, 100.0))
+    }
+
+    fn cube(x: f32, y: f32, z: f32) -> Aabb {
+        Aabb::new(
+            Vector3::new(x - 1.0, y - 1.0, z - 1.0),
+            Vector3::new(x + 1.0, y + 1.0, z + 1.0),
+        )
+    }
+
+    #[test]
+    fn test_from_matrix() {
+        let f = frustum();
+        let near = f.planes[4];
+        assert!((near.normal - Vector3::new(0.0, 0.0, -1.0)).magnitude() < 1e-6);
+        assert!((near.d + 1.0).abs() < 1e-5);
+        let far = f.planes[5];
+        assert!((far.normal - Vector3::new(0.0, 0.0, 1.0)).magnitude() < 1e-6);
+        assert!((far.d - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_cull_aabbs() {
+        let f = frustum();
+        let boxes = [
+            cube(0.0, 0.0, -10.0),
+            cube(0.0, 0.0, 10.0),
+            cube(0.0, 0.0, -100.5),
+            cube(-10.5, 0.0, -10.0),
+            cube(-13.0, 0.0, -10.0),
+            cube(0.0, 0.0, -200.0),
+        ];
+        let visible = f.cull_aabbs(&boxes);
+        assert_eq!(visible, vec![true, false, true, true, false, false]);
+        let single: Vec<bool> = boxes.iter().map(|b| f.intersects_aabb(b)).collect();
+        assert_eq!(single, visible);
+    }
+
+    #[test]
+    fn test_cull_spheres() {
+        let f = frustum();
+        let spheres = [
+            Sphere::new(Vector3::new(0.0, 0.0, -10.0), 1.0),
+            Sphere::new(Vector3::new(0.0, 0.0, -0.5), 1.0),
+            Sphere::new(Vector3::new(0.0, 0.0, -0.5), 0.25),
+            Sphere::new(Vector3::new(0.0, 20.0, -10.0), 5.0),
+        ];
+        let visible = f.cull_spheres(&spheres);
+        assert_eq!(visible, vec![true, true, false, false]);
+        let single: Vec<bool> = spheres.iter().map(|s| f.intersects_sphere(s)).collect();
+        assert_eq!(single, visible);
+    }
+}
@@ -0,0 +1,176 @@
+//! Distances from points to lines, segments and planes, for hit-testing and
+//! snapping.
+
+use crate::components::{delta, distance, dot};
+use crate::{Components, Vector3, VectorError};
+
+/// Parameter of the projection of `point` onto the line through `a` and `b`,
+/// with 0 at `a` and 1 at `b`. Zero if `a` and `b` coincide.
+fn project<V: Components>(point: &V, a: &V, b: &V) -> f32 {
+    let ab = delta(a, b);
+    let len_sq = dot(&ab, &ab);
+    if len_sq == 0.0 {
+        return 0.0;
+    }
+    let ap = delta(a, point);
+    dot(&ap, &ab) / len_sq
+}
+
+fn point_at<V: Components>(a: &V, b: &V, t: f32) -> V {
+    V::from_components(|i| a.component(i) + (b.component(i) - a.component(i)) * t)
+}
+
+/// Point of the infinite line through `a` and `b` closest to `point`.
+pub fn closest_point_on_line<V: Components>(point: &V, a: &V, b: &V) -> V {
+    point_at(a, b, project(point, a, b))
+}
+
+/// Distance from `point` to the infinite line through `a` and `b`.
+pub fn distance_to_line<V: Components>(point: &V, a: &V, b: &V) -> f32 {
+    distance(point, &closest_point_on_line(point, a, b))
+}
+
+/// Point of the segment from `a` to `b` closest to `point`.
+pub fn closest_point_on_segment<V: Components>(point: &V, a: &V, b: &V) -> V {
+    point_at(a, b, project(point, a, b).clamp(0.0, 1.0))
+}
+
+/// Distance from `point` to the segment from `a` to `b`.
+pub fn distance_to_segment<V: Components>(point: &V, a: &V, b: &V) -> f32 {
+    distance(point, &closest_point_on_segment(point, a, b))
+}
+
+/// Plane of points `p` with `normal.dot(p) + d == 0`, using a unit normal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    pub normal: Vector3<f32>,
+    pub d: f32,
+}
+
+impl Plane {
+    pub const fn new(normal: Vector3<f32>, d: f32) -> Plane {
+        Plane { normal, d }
+    }
+
+    /// Plane through `point` perpendicular to `normal`, which need not be
+    /// normalized.
+    pub fn from_point_normal(point: Vector3<f32>, normal: Vector3<f32>) -> Plane {
+        let normal = normal.normalize();
+        Plane {
+            normal,
+            d: -normal.dot(&point),
+        }
+    }
+
+    /// Plane through three points, facing the side from which they appear
+    /// counter-clockwise. Collinear points give NaN components; see
+    /// `try_from_points`.
+    pub fn from_points(a: Vector3<f32>, b: Vector3<f32>, c: Vector3<f32>) -> Plane {
+        Plane::from_point_normal(a, (b - a).cross(&(c - a)))
+    }
+
+    /// Like `from_points`, but an error for collinear or coincident points
+    /// and for non-finite input.
+    pub fn try_from_points(
+        a: Vector3<f32>,
+        b: Vector3<f32>,
+        c: Vector3<f32>,
+    ) -> Result<Plane, VectorError> {
+        let normal = (b - a).cross(&(c - a)).try_normalize()?;
+        Ok(Plane {
+            normal,
+            d: -normal.dot(&a),
+        })
+    }
+
+    /// Signed distance of `point`, positive on the side the normal points to.
+    pub fn distance(&self, point: Vector3<f32>) -> f32 {
+        self.normal.dot(&point) + self.d
+    }
+
+    /// Point of the plane closest to `point`.
+    pub fn closest_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+        point - self.normal * self.distance(point)
+    }
+
+    /// Mirror image of `point` on the other side of the plane.
+    pub fn reflect_point(&self, point: Vector3<f32>) -> Vector3<f32> {
+        point - self.normal * (2.0 * self.distance(point))
+    }
+
+    /// Mirror image of the direction `v`, which unlike a point is not
+    /// affected by the plane's offset from the origin.
+    pub fn reflect_vector(&self, v: Vector3<f32>) -> Vector3<f32> {
+        v - self.normal * (2.0 * self.normal.dot(&v))
+    }
+}
+
+impl Vector3<f32> {
+    /// Mirror image of this point across `plane`. Use `Plane::reflect_vector`
+    /// for directions.
+    pub fn reflect_across(&self, plane: &Plane) -> Vector3<f32> {
+        plane.reflect_point(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector2;
+
+    #[test]
+    fn test_line_and_segment() {
+        let a = Vector2::new(0.0, 0.0);
+        let b = Vector2::new(4.0, 0.0);
+        let p = Vector2::new(6.0, 3.0);
+        assert_eq!(closest_point_on_line(&p, &a, &b), Vector2::new(6.0, 0.0));
+        assert_eq!(distance_to_line(&p, &a, &b), 3.0);
+        assert_eq!(closest_point_on_segment(&p, &a, &b), b);
+        assert!((distance_to_segment(&p, &a, &b) - 13.0f32.sqrt()).abs() < 1e-6);
+
+        // Degenerate segment behaves like a point
+        let q = Vector3::new(1.0, 2.0, 2.0);
+        let o = Vector3::new(0.0, 0.0, 0.0);
+        assert_eq!(distance_to_segment(&q, &o, &o), 3.0);
+        assert_eq!(distance_to_line(&q, &o, &o), 3.0);
+    }
+
+    #[test]
+    fn test_plane() {
+        let plane =
+            Plane::from_point_normal(Vector3::new(0.0, 2.0, 0.0), Vector3::new(0.0, 5.0, 0.0));
+        assert_eq!(plane, Plane::new(Vector3::new(0.0, 1.0, 0.0), -2.0));
+        let p = Vector3::new(3.0, -1.0, 4.0);
+        assert_eq!(plane.distance(p), -3.0);
+        assert_eq!(plane.closest_point(p), Vector3::new(3.0, 2.0, 4.0));
+    }
+
+    #[test]
+    fn test_plane_reflection() {
+        let plane = Plane::new(Vector3::new(0.0, 1.0, 0.0), -2.0);
+        let p = Vector3::new(3.0, -1.0, 4.0);
+        assert_eq!(p.reflect_across(&plane), Vector3::new(3.0, 5.0, 4.0));
+        assert_eq!(
+            plane.reflect_vector(Vector3::new(1.0, -1.0, 0.0)),
+            Vector3::new(1.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_plane_from_points() {
+        let a = Vector3::new(0.0, 2.0, 0.0);
+        let b = Vector3::new(0.0, 2.0, 1.0);
+        let c = Vector3::new(1.0, 2.0, 0.0);
+        let plane = Plane::try_from_points(a, b, c).unwrap();
+        assert_eq!(plane, Plane::new(Vector3::new(0.0, 1.0, 0.0), -2.0));
+        assert_eq!(plane, Plane::from_points(a, b, c));
+        assert_eq!(
+            Plane::try_from_points(a, b, b * 2.0 - a),
+            Err(VectorError::ZeroLength)
+        );
+        assert_eq!(
+            Plane::try_from_points(a, b, Vector3::new(f32::NAN, 0.0, 0.0)),
+            Err(VectorError::NonFinite)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Easing curves for tweening, mapping progress `t` in [0, 1] to an eased
+//! interpolation factor. All curves start at 0 and end at 1; elastic and
+//! bounce curves overshoot or rebound in between.
+//!
+//! The formulas follow Robert Penner's easing equations.
+
+use std::f32::consts::PI;
+use std::ops::{Add, Mul, Sub};
+
+use crate::interpolation::lerp;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+fn elastic_out(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t.clamp(0.0, 1.0);
+    }
+    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+}
+
+/// Mirrors an ease-out curve into the matching ease-in curve.
+fn ease_in(out: fn(f32) -> f32, t: f32) -> f32 {
+    1.0 - out(1.0 - t)
+}
+
+/// Runs the ease-in curve for the first half and ease-out for the second.
+fn ease_in_out(out: fn(f32) -> f32, t: f32) -> f32 {
+    if t < 0.5 {
+        0.5 * ease_in(out, 2.0 * t)
+    } else {
+        0.5 + 0.5 * out(2.0 * t - 1.0)
+    }
+}
+
+impl Easing {
+    /// Eased factor for progress `t`, which is clamped to [0, 1].
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        let quad_out = |t: f32| 1.0 - (1.0 - t).powi(2);
+        let cubic_out = |t: f32| 1.0 - (1.0 - t).powi(3);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => ease_in(quad_out, t),
+            Easing::QuadOut => quad_out(t),
+            Easing::QuadInOut => ease_in_out(quad_out, t),
+            Easing::CubicIn => ease_in(cubic_out, t),
+            Easing::CubicOut => cubic_out(t),
+            Easing::CubicInOut => ease_in_out(cubic_out, t),
+            Easing::ElasticIn => ease_in(elastic_out, t),
+            Easing::ElasticOut => elastic_out(t),
+            Easing::ElasticInOut => ease_in_out(elastic_out, t),
+            Easing::BounceIn => ease_in(bounce_out, t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => ease_in_out(bounce_out, t),
+        }
+    }
+}
+
+/// Interpolates from `a` to `b` with progress `t` shaped by `easing`.
+pub fn lerp_eased<V>(a: V, b: V, t: f32, easing: Easing) -> V
+where
+    V: Copy + Add<Output = V> + Sub<Output = V> + Mul<f32, Output = V>,
+{
+    lerp(a, b, easing.apply(t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector2;
+
+    const ALL: [Easing; 13] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    #[test]
+    fn test_endpoints() {
+        for easing in ALL {
+            assert!(easing.apply(0.0).abs() < 1e-6, "{easing:?}");
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-6, "{easing:?}");
+            assert_eq!(easing.apply(-1.0), easing.apply(0.0));
+            assert_eq!(easing.apply(2.0), easing.apply(1.0));
+        }
+        for easing in [Easing::QuadInOut, Easing::CubicInOut, Easing::BounceInOut] {
+            assert!((easing.apply(0.5) - 0.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_curve_shapes() {
+        assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+        assert_eq!(Easing::QuadOut.apply(0.5), 0.75);
+        assert_eq!(Easing::CubicIn.apply(0.5), 0.125);
+        assert!(Easing::ElasticOut.apply(0.1) > 1.0);
+        assert!(Easing::ElasticIn.apply(0.9) < 0.0);
+        assert!((Easing::BounceOut.apply(1.0 / 2.75) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_lerp_eased() {
+        let v = lerp_eased(
+            Vector2::new(0.0, 0.0),
+            Vector2::new(4.0, 8.0),
+            0.5,
+            Easing::QuadIn,
+        );
+        assert_eq!(v, Vector2::new(1.0, 2.0));
+    }
+}
@@ -0,0 +1,60 @@
+//! Errors returned by the fallible vector constructors and operations.
+
+use std::fmt;
+
+/// Error converting a slice whose length differs from the vector dimension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrongLength {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for WrongLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a slice of length {}, found length {}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for WrongLength {}
+
+/// Reasons a fallible vector operation can fail instead of producing NaNs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VectorError {
+    /// The vector (or the normal derived from the input) has zero length.
+    ZeroLength,
+    /// A component is infinite or NaN.
+    NonFinite,
+    /// A slice did not have as many elements as the vector has components.
+    WrongDimension { expected: usize, found: usize },
+    /// Weights sum to zero, up to rounding, so no weighted average exists.
+    ZeroWeight,
+}
+
+impl fmt::Display for VectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VectorError::ZeroLength => write!(f, "vector has zero length"),
+            VectorError::NonFinite => write!(f, "vector has a non-finite component"),
+            VectorError::WrongDimension { expected, found } => {
+                write!(f, "expected {} components, found {}", expected, found)
+            }
+            VectorError::ZeroWeight => write!(f, "weights sum to zero"),
+        }
+    }
+}
+
+impl std::error::Error for VectorError {}
+
+impl From<WrongLength> for VectorError {
+    fn from(e: WrongLength) -> Self {
+        VectorError::WrongDimension {
+            expected: e.expected,
+            found: e.found,
+        }
+    }
+}
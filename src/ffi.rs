@@ -0,0 +1,86 @@
+//! C ABI for backing C and C++ plugin APIs with this crate. Requires the
+//! `ffi` feature.
+//!
+//! The vector types are `#[repr(C)]`, so the `f32` aliases below are plain
+//! structs of floats on the C side and are passed by value. The functions
+//! never panic or return NaN for a zero vector, and the surface is laid out
+//! for cbindgen (see `cbindgen.toml`), which emits `Vector2_f32` style
+//! structs for the aliases.
+
+use crate::{Vector2, Vector3, Vector4};
+
+pub type Vector2f = Vector2<f32>;
+pub type Vector3f = Vector3<f32>;
+pub type Vector4f = Vector4<f32>;
+
+#[no_mangle]
+pub extern "C" fn vector2f_dot(a: Vector2f, b: Vector2f) -> f32 {
+    a.x * b.x + a.y * b.y
+}
+
+#[no_mangle]
+pub extern "C" fn vector3f_dot(a: Vector3f, b: Vector3f) -> f32 {
+    a.dot(&b)
+}
+
+#[no_mangle]
+pub extern "C" fn vector4f_dot(a: Vector4f, b: Vector4f) -> f32 {
+    a.dot(&b)
+}
+
+#[no_mangle]
+pub extern "C" fn vector3f_cross(a: Vector3f, b: Vector3f) -> Vector3f {
+    a.cross(&b)
+}
+
+#[no_mangle]
+pub extern "C" fn vector2f_magnitude(v: Vector2f) -> f32 {
+    v.magnitude()
+}
+
+#[no_mangle]
+pub extern "C" fn vector3f_magnitude(v: Vector3f) -> f32 {
+    v.magnitude()
+}
+
+/// Unit vector in the direction of `v`, or the zero vector if `v` has zero
+/// length or a non-finite component.
+#[no_mangle]
+pub extern "C" fn vector2f_normalize(v: Vector2f) -> Vector2f {
+    v.try_normalize().unwrap_or_default()
+}
+
+/// Unit vector in the direction of `v`, or the zero vector if `v` has zero
+/// length or a non-finite component.
+#[no_mangle]
+pub extern "C" fn vector3f_normalize(v: Vector3f) -> Vector3f {
+    v.try_normalize().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi_functions() {
+        let a = Vector3::new(1.0, 0.0, 0.0);
+        let b = Vector3::new(0.0, 2.0, 0.0);
+        assert_eq!(vector3f_dot(a, b), 0.0);
+        assert_eq!(vector3f_cross(a, b), Vector3::new(0.0, 0.0, 2.0));
+        assert_eq!(vector3f_normalize(b), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(vector3f_normalize(Vector3::default()), Vector3::default());
+        assert_eq!(
+            vector3f_normalize(Vector3::new(0.0, -1e20, 0.0)),
+            Vector3::new(0.0, -1.0, 0.0)
+        );
+        assert_eq!(
+            vector2f_dot(Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0)),
+            11.0
+        );
+        assert_eq!(vector2f_magnitude(Vector2::new(3.0, 4.0)), 5.0);
+        assert_eq!(
+            vector2f_normalize(Vector2::new(f32::NAN, 1.0)),
+            Vector2::default()
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Numerical derivatives of scalar fields such as signed distance functions,
+//! noise and terrain heights.
+
+use crate::{Vector2, Vector3};
+
+/// Derivative of `f` at `p` along the unit direction `dir` by central
+/// differences with step `h`, i.e. two samples. Cheaper than a full
+/// `gradient` when only some partial derivatives are needed.
+pub fn directional_derivative<F: Fn(Vector3<f32>) -> f32>(
+    f: F,
+    p: Vector3<f32>,
+    dir: Vector3<f32>,
+    h: f32,
+) -> f32 {
+    (f(p + dir * h) - f(p - dir * h)) / (2.0 * h)
+}
+
+/// Gradient of `f` at `p` by central differences with step `h`, i.e. six
+/// samples. Normalized, this is the surface normal of an SDF.
+pub fn gradient<F: Fn(Vector3<f32>) -> f32>(f: F, p: Vector3<f32>, h: f32) -> Vector3<f32> {
+    let diff = |dir: Vector3<f32>| directional_derivative(&f, p, dir, h);
+    Vector3::new(
+        diff(Vector3::new(1.0, 0.0, 0.0)),
+        diff(Vector3::new(0.0, 1.0, 0.0)),
+        diff(Vector3::new(0.0, 0.0, 1.0)),
+    )
+}
+
+/// Gradient of `f` at `p` by central differences with step `h`, pointing
+/// uphill, e.g. on a heightmap.
+pub fn gradient2<F: Fn(Vector2<f32>) -> f32>(f: F, p: Vector2<f32>, h: f32) -> Vector2<f32> {
+    let diff = |d: Vector2<f32>| (f(p + d) - f(p - d)) / (2.0 * h);
+    Vector2::new(diff(Vector2::new(h, 0.0)), diff(Vector2::new(0.0, h)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient() {
+        // Exact for quadratics
+        let f = |p: Vector3<f32>| p.x * p.x + 3.0 * p.y - p.z * p.y;
+        let g = gradient(f, Vector3::new(1.0, 2.0, -1.0), 0.5);
+        assert_eq!(g, Vector3::new(2.0, 4.0, -2.0));
+
+        let sphere = |p: Vector3<f32>| p.magnitude() - 1.0;
+        let n = gradient(sphere, Vector3::new(0.0, 0.0, 2.0), 1e-3);
+        assert!((n - Vector3::new(0.0, 0.0, 1.0)).magnitude() < 1e-3);
+
+        let d = directional_derivative(
+            f,
+            Vector3::new(1.0, 2.0, -1.0),
+            Vector3::new(0.6, 0.0, 0.8),
+            0.5,
+        );
+        assert!((d - (0.6 * 2.0 - 0.8 * 2.0)).abs() < 1e-6);
+
+        let g = gradient2(|p| p.x * p.y, Vector2::new(3.0, -2.0), 0.25);
+        assert_eq!(g, Vector2::new(-2.0, 3.0));
+    }
+}
@@ -0,0 +1,97 @@
+//! Aviation-style attitude angles and coordinated turn math for flight games
+//! and HUDs.
+//!
+//! The world is y up with heading 0 looking down -z and increasing toward +x,
+//! like a compass seen from above with north at -z. Pitch is positive nose
+//! up and bank is positive right wing down. All angles are in radians; use
+//! `to_degrees` for display.
+
+use crate::Vector3;
+
+/// Heading and pitch of a velocity. A vertical velocity has heading 0.
+pub fn heading_pitch(velocity: Vector3<f32>) -> (f32, f32) {
+    let horizontal = (velocity.x * velocity.x + velocity.z * velocity.z).sqrt();
+    let heading = if horizontal > 0.0 {
+        velocity.x.atan2(-velocity.z)
+    } else {
+        0.0
+    };
+    (heading, velocity.y.atan2(horizontal))
+}
+
+/// Velocity of `speed` along `heading` and `pitch`.
+pub fn from_heading_pitch(heading: f32, pitch: f32, speed: f32) -> Vector3<f32> {
+    let (sin_h, cos_h) = heading.sin_cos();
+    let (sin_p, cos_p) = pitch.sin_cos();
+    Vector3::new(sin_h * cos_p, sin_p, -cos_h * cos_p) * speed
+}
+
+/// Bank angle of a coordinated turn producing `acceleration` while flying
+/// with `velocity`, where `gravity` is the magnitude of gravity. Only the
+/// horizontal acceleration to the side of the flight path counts.
+pub fn bank_angle(velocity: Vector3<f32>, acceleration: Vector3<f32>, gravity: f32) -> f32 {
+    let (heading, _) = heading_pitch(velocity);
+    let (sin_h, cos_h) = heading.sin_cos();
+    // Horizontal direction to the right of the heading
+    let right = Vector3::new(cos_h, 0.0, sin_h);
+    acceleration.dot(&right).atan2(gravity)
+}
+
+/// Radius of a level coordinated turn at `speed` and `bank`. Infinite with
+/// wings level.
+pub fn turn_radius(speed: f32, bank: f32, gravity: f32) -> f32 {
+    speed * speed / (gravity * bank.tan().abs())
+}
+
+/// Rate of heading change in radians per second of a level coordinated turn,
+/// positive to the right.
+pub fn turn_rate(speed: f32, bank: f32, gravity: f32) -> f32 {
+    gravity * bank.tan() / speed
+}
+
+/// Bank needed to fly a level coordinated turn of `radius` at `speed`.
+pub fn bank_for_radius(speed: f32, radius: f32, gravity: f32) -> f32 {
+    (speed * speed / (gravity * radius)).atan()
+}
+
+/// Load factor (the g felt by the pilot) in a level coordinated turn.
+pub fn load_factor(bank: f32) -> f32 {
+    bank.cos().recip()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn test_heading_pitch() {
+        let north = Vector3::new(0.0, 0.0, -5.0);
+        assert_eq!(heading_pitch(north), (0.0, 0.0));
+        assert_eq!(heading_pitch(Vector3::new(2.0, 0.0, 0.0)).0, FRAC_PI_2);
+        assert_eq!(heading_pitch(Vector3::new(0.0, 0.0, 1.0)).0, PI);
+        let (h, p) = heading_pitch(Vector3::new(-1.0, 1.0, -1.0));
+        assert_eq!(h, -FRAC_PI_4);
+        assert!((p - (1.0 / 2f32.sqrt()).atan()).abs() < 1e-6);
+        assert_eq!(heading_pitch(Vector3::new(0.0, 3.0, 0.0)), (0.0, FRAC_PI_2));
+
+        let v = from_heading_pitch(h, p, 3f32.sqrt());
+        assert!((v - Vector3::new(-1.0, 1.0, -1.0)).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn test_coordinated_turn() {
+        let g = 9.81;
+        let bank = bank_for_radius(100.0, 500.0, g);
+        assert!((turn_radius(100.0, bank, g) - 500.0).abs() < 1e-2);
+        assert!((turn_rate(100.0, bank, g) - 0.2).abs() < 1e-6);
+        assert!((load_factor(PI / 3.0) - 2.0).abs() < 1e-6);
+        assert_eq!(turn_radius(100.0, 0.0, g), f32::INFINITY);
+
+        // Flying north and pulling toward +x: a right turn
+        let v = Vector3::new(0.0, 0.0, -100.0);
+        let a = Vector3::new(g, 0.0, 0.0);
+        assert!((bank_angle(v, a, g) - FRAC_PI_4).abs() < 1e-6);
+        assert!((bank_angle(v, a * -1.0, g) + FRAC_PI_4).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,153 @@
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Floating point component types (`f32` and `f64`) usable by the float-only
+/// vector and matrix operations.
+pub trait Float:
+    Copy
+    + Default
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn recip(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn tan(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn to_degrees(self) -> Self;
+    fn to_radians(self) -> Self;
+    fn is_finite(self) -> bool;
+    fn total_cmp(&self, other: &Self) -> std::cmp::Ordering;
+}
+
+macro_rules! impl_float {
+    ($t:ty) => {
+        impl Float for $t {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn sqrt(self) -> Self {
+                <$t>::sqrt(self)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                <$t>::hypot(self, other)
+            }
+
+            fn recip(self) -> Self {
+                <$t>::recip(self)
+            }
+
+            fn floor(self) -> Self {
+                <$t>::floor(self)
+            }
+
+            fn ceil(self) -> Self {
+                <$t>::ceil(self)
+            }
+
+            fn round(self) -> Self {
+                <$t>::round(self)
+            }
+
+            fn trunc(self) -> Self {
+                <$t>::trunc(self)
+            }
+
+            fn min(self, other: Self) -> Self {
+                <$t>::min(self, other)
+            }
+
+            fn max(self, other: Self) -> Self {
+                <$t>::max(self, other)
+            }
+
+            fn tan(self) -> Self {
+                <$t>::tan(self)
+            }
+
+            fn exp(self) -> Self {
+                <$t>::exp(self)
+            }
+
+            fn ln(self) -> Self {
+                <$t>::ln(self)
+            }
+
+            fn powf(self, n: Self) -> Self {
+                <$t>::powf(self, n)
+            }
+
+            fn sin(self) -> Self {
+                <$t>::sin(self)
+            }
+
+            fn cos(self) -> Self {
+                <$t>::cos(self)
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                <$t>::atan2(self, other)
+            }
+
+            fn to_degrees(self) -> Self {
+                <$t>::to_degrees(self)
+            }
+
+            fn to_radians(self) -> Self {
+                <$t>::to_radians(self)
+            }
+
+            fn is_finite(self) -> bool {
+                <$t>::is_finite(self)
+            }
+
+            fn total_cmp(&self, other: &Self) -> std::cmp::Ordering {
+                <$t>::total_cmp(self, other)
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
+
+// Reciprocal square root estimate refined by one Newton-Raphson step.
+// Relative error is around 1e-5 after refinement.
+#[cfg(feature = "fast-rsqrt")]
+pub(crate) fn rsqrt_fast(x: f32) -> f32 {
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse"))]
+    let estimate = unsafe {
+        use std::arch::x86_64::{_mm_cvtss_f32, _mm_rsqrt_ss, _mm_set_ss};
+        _mm_cvtss_f32(_mm_rsqrt_ss(_mm_set_ss(x)))
+    };
+    #[cfg(not(all(target_arch = "x86_64", target_feature = "sse")))]
+    let estimate = f32::from_bits(0x5f37_5a86 - (x.to_bits() >> 1));
+
+    estimate * (1.5 - 0.5 * x * estimate * estimate)
+}
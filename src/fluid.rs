@@ -0,0 +1,105 @@
+//! Forces on bodies floating in or moving through a fluid, such as boats,
+//! floating debris and simple aircraft.
+//!
+//! Velocities are relative to the fluid, so subtract any current or wind
+//! first. The water surface is the horizontal plane `y = water_height`.
+
+use std::f32::consts::PI;
+
+use crate::culling::{Aabb, Sphere};
+use crate::Vector3;
+
+/// Archimedes' force on a body displacing `submerged_volume` of a fluid, which
+/// points against `gravity`.
+pub fn buoyancy_force(
+    submerged_volume: f32,
+    fluid_density: f32,
+    gravity: Vector3<f32>,
+) -> Vector3<f32> {
+    gravity * (-fluid_density * submerged_volume)
+}
+
+/// Quadratic drag `0.5 * rho * cd * area * |v|^2`, opposing `velocity`.
+pub fn drag_force(
+    velocity: Vector3<f32>,
+    fluid_density: f32,
+    drag_coefficient: f32,
+    area: f32,
+) -> Vector3<f32> {
+    velocity * (-0.5 * fluid_density * drag_coefficient * area * velocity.magnitude())
+}
+
+/// Lift `0.5 * rho * cl * area * |v|^2` on a wing moving with `velocity`,
+/// perpendicular to both the velocity and the wing's `span` axis. With the
+/// span pointing to the right of the direction of travel, positive lift
+/// points up. Zero when moving along the span.
+pub fn lift_force(
+    velocity: Vector3<f32>,
+    span: Vector3<f32>,
+    fluid_density: f32,
+    lift_coefficient: f32,
+    area: f32,
+) -> Vector3<f32> {
+    let direction = span.cross(&velocity);
+    let len = direction.magnitude();
+    if len == 0.0 {
+        return Vector3::default();
+    }
+    let speed_sq = velocity.dot(&velocity);
+    direction * (0.5 * fluid_density * lift_coefficient * area * speed_sq / len)
+}
+
+/// Volume of `aabb` below the water surface.
+pub fn submerged_volume_aabb(aabb: &Aabb, water_height: f32) -> f32 {
+    let depth = (water_height.min(aabb.max.y) - aabb.min.y).max(0.0);
+    (aabb.max.x - aabb.min.x) * depth * (aabb.max.z - aabb.min.z)
+}
+
+/// Volume of `sphere` below the water surface, from the spherical cap formula.
+pub fn submerged_volume_sphere(sphere: &Sphere, water_height: f32) -> f32 {
+    let r = sphere.radius;
+    let h = (water_height - sphere.center.y + r).clamp(0.0, 2.0 * r);
+    PI * h * h * (3.0 * r - h) / 3.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buoyancy() {
+        let aabb = Aabb::new(Vector3::new(0.0, -1.0, 0.0), Vector3::new(2.0, 1.0, 3.0));
+        assert_eq!(submerged_volume_aabb(&aabb, 0.0), 6.0);
+        assert_eq!(submerged_volume_aabb(&aabb, 5.0), 12.0);
+        assert_eq!(submerged_volume_aabb(&aabb, -2.0), 0.0);
+
+        let sphere = Sphere::new(Vector3::new(0.0, 1.0, 0.0), 1.0);
+        let full = 4.0 / 3.0 * PI;
+        assert!((submerged_volume_sphere(&sphere, 1.0) - full / 2.0).abs() < 1e-6);
+        assert!((submerged_volume_sphere(&sphere, 3.0) - full).abs() < 1e-6);
+        assert_eq!(submerged_volume_sphere(&sphere, -1.0), 0.0);
+
+        let force = buoyancy_force(2.0, 1000.0, Vector3::new(0.0, -9.81, 0.0));
+        assert!((force - Vector3::new(0.0, 19620.0, 0.0)).magnitude() < 1e-2);
+    }
+
+    #[test]
+    fn test_drag_and_lift() {
+        let v = Vector3::new(0.0, 0.0, -10.0);
+        assert_eq!(drag_force(v, 1.2, 0.5, 2.0), Vector3::new(0.0, 0.0, 60.0));
+
+        // Flying down -z with the span along +x lifts along +y
+        let lift = lift_force(v, Vector3::new(3.0, 0.0, 0.0), 1.2, 1.0, 2.0);
+        assert!((lift - Vector3::new(0.0, 120.0, 0.0)).magnitude() < 1e-4);
+        assert_eq!(
+            lift_force(
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                1.2,
+                1.0,
+                2.0
+            ),
+            Vector3::default()
+        );
+    }
+}
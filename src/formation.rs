@@ -0,0 +1,171 @@
+//! Formation slot layouts and agent-to-slot assignment for squad movement.
+//!
+//! Slot offsets are given in the leader's local frame, with +y pointing
+//! forward and +x to the leader's right.
+
+use crate::components::{delta, dot};
+use crate::{Components, Vector2, Vector3};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Formation {
+    /// Side by side with the leader, alternating right and left.
+    Line { spacing: f32 },
+    /// A V shape opening backwards, alternating right and left.
+    Wedge { spacing: f32 },
+    /// Evenly spread on a circle around the leader.
+    Circle { radius: f32 },
+    /// Rows of `columns` slots behind the leader.
+    Grid { columns: usize, spacing: f32 },
+}
+
+impl Formation {
+    /// Local offsets of `count` follower slots relative to the leader.
+    pub fn offsets(&self, count: usize) -> Vec<Vector2<f32>> {
+        (0..count)
+            .map(|i| {
+                // Rank away from the leader and side (+1 right, -1 left)
+                let rank = (i / 2 + 1) as f32;
+                let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+                match *self {
+                    Formation::Line { spacing } => Vector2::new(side * rank * spacing, 0.0),
+                    Formation::Wedge { spacing } => {
+                        Vector2::new(side * rank * spacing, -rank * spacing)
+                    }
+                    Formation::Circle { radius } => {
+                        let angle = std::f32::consts::TAU * i as f32 / count as f32;
+                        Vector2::new(angle.sin(), -angle.cos()) * radius
+                    }
+                    Formation::Grid { columns, spacing } => {
+                        let columns = columns.max(1);
+                        let row = (i / columns + 1) as f32;
+                        let column = (i % columns) as f32 - (columns - 1) as f32 / 2.0;
+                        Vector2::new(column * spacing, -row * spacing)
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// Places local slot offsets around a leader at `position` whose forward
+/// direction is rotated `heading` radians counter-clockwise from +y.
+pub fn world_slots(
+    offsets: &[Vector2<f32>],
+    position: Vector2<f32>,
+    heading: f32,
+) -> Vec<Vector2<f32>> {
+    let (sin, cos) = heading.sin_cos();
+    offsets
+        .iter()
+        .map(|o| position + Vector2::new(o.x * cos - o.y * sin, o.x * sin + o.y * cos))
+        .collect()
+}
+
+/// Places local slot offsets on the ground plane (y up) around a leader at
+/// `position`. With `heading` zero the leader faces -z and its right is +x.
+pub fn world_slots_xz(
+    offsets: &[Vector2<f32>],
+    position: Vector3<f32>,
+    heading: f32,
+) -> Vec<Vector3<f32>> {
+    let (sin, cos) = heading.sin_cos();
+    offsets
+        .iter()
+        .map(|o| {
+            let x = o.x * cos - o.y * sin;
+            let forward = o.x * sin + o.y * cos;
+            position + Vector3::new(x, 0.0, -forward)
+        })
+        .collect()
+}
+
+/// Assigns agents to slots greedily, repeatedly matching the closest free
+/// agent and slot pair. Returns the slot index for every agent, or `None` for
+/// agents left over when there are fewer slots than agents.
+pub fn assign_slots<V: Components>(agents: &[V], slots: &[V]) -> Vec<Option<usize>> {
+    let mut pairs = Vec::with_capacity(agents.len() * slots.len());
+    for (a, agent) in agents.iter().enumerate() {
+        for (s, slot) in slots.iter().enumerate() {
+            let d = delta(agent, slot);
+            let d = dot(&d, &d);
+            pairs.push((d, a, s));
+        }
+    }
+    pairs.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let mut assignment = vec![None; agents.len()];
+    let mut taken = vec![false; slots.len()];
+    for (_, a, s) in pairs {
+        if assignment[a].is_none() && !taken[s] {
+            assignment[a] = Some(s);
+            taken[s] = true;
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets() {
+        let line = Formation::Line { spacing: 2.0 }.offsets(3);
+        assert_eq!(
+            line,
+            vec![
+                Vector2::new(2.0, 0.0),
+                Vector2::new(-2.0, 0.0),
+                Vector2::new(4.0, 0.0)
+            ]
+        );
+        let wedge = Formation::Wedge { spacing: 1.0 }.offsets(2);
+        assert_eq!(
+            wedge,
+            vec![Vector2::new(1.0, -1.0), Vector2::new(-1.0, -1.0)]
+        );
+        let grid = Formation::Grid {
+            columns: 3,
+            spacing: 1.0,
+        }
+        .offsets(4);
+        assert_eq!(
+            grid,
+            vec![
+                Vector2::new(-1.0, -1.0),
+                Vector2::new(0.0, -1.0),
+                Vector2::new(1.0, -1.0),
+                Vector2::new(-1.0, -2.0),
+            ]
+        );
+        for o in (Formation::Circle { radius: 3.0 }).offsets(7) {
+            assert!((o.x.hypot(o.y) - 3.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_world_slots() {
+        let offsets = [Vector2::new(1.0, -1.0)];
+        let slots = world_slots(
+            &offsets,
+            Vector2::new(10.0, 0.0),
+            std::f32::consts::FRAC_PI_2,
+        );
+        assert!((slots[0] - Vector2::new(11.0, 1.0)).x.abs() < 1e-6);
+        assert!((slots[0] - Vector2::new(11.0, 1.0)).y.abs() < 1e-6);
+
+        let slots = world_slots_xz(&offsets, Vector3::new(0.0, 5.0, 0.0), 0.0);
+        assert_eq!(slots, vec![Vector3::new(1.0, 5.0, 1.0)]);
+    }
+
+    #[test]
+    fn test_assign_slots() {
+        let agents = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(5.0, 5.0),
+        ];
+        let slots = [Vector2::new(9.0, 0.0), Vector2::new(1.0, 0.0)];
+        assert_eq!(assign_slots(&agents, &slots), vec![Some(1), Some(0), None]);
+    }
+}
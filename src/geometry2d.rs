@@ -0,0 +1,208 @@
+//! Intersection routines for 2D shapes, for top-down collision detection.
+
+use crate::Vector2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Circle {
+    pub center: Vector2<f32>,
+    pub radius: f32,
+}
+
+/// Line segment between two points. Also used to describe the infinite line
+/// through them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Segment2 {
+    pub a: Vector2<f32>,
+    pub b: Vector2<f32>,
+}
+
+/// Overlap between two shapes: the deepest point, the unit normal pointing
+/// from the first shape toward the second, and the penetration depth along it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contact {
+    pub point: Vector2<f32>,
+    pub normal: Vector2<f32>,
+    pub depth: f32,
+}
+
+impl Circle {
+    pub const fn new(center: Vector2<f32>, radius: f32) -> Circle {
+        Circle { center, radius }
+    }
+}
+
+impl Segment2 {
+    pub const fn new(a: Vector2<f32>, b: Vector2<f32>) -> Segment2 {
+        Segment2 { a, b }
+    }
+
+    /// Point of the segment closest to `p`.
+    pub fn closest_point(&self, p: Vector2<f32>) -> Vector2<f32> {
+        let d = self.b - self.a;
+        let len_sq = d.dot(&d);
+        if len_sq == 0.0 {
+            return self.a;
+        }
+        let t = ((p - self.a).dot(&d) / len_sq).clamp(0.0, 1.0);
+        self.a + d * t
+    }
+}
+
+/// Contact between two overlapping circles, with the normal pointing from `a`
+/// to `b`. Concentric circles are separated along +x.
+pub fn circle_circle(a: &Circle, b: &Circle) -> Option<Contact> {
+    let d = b.center - a.center;
+    let dist = d.x.hypot(d.y);
+    let depth = a.radius + b.radius - dist;
+    if depth < 0.0 {
+        return None;
+    }
+    let normal = if dist > 0.0 {
+        d * dist.recip()
+    } else {
+        Vector2::new(1.0, 0.0)
+    };
+    Some(Contact {
+        point: a.center + normal * (a.radius - depth * 0.5),
+        normal,
+        depth,
+    })
+}
+
+/// The points where the outlines of two circles cross. Returns `None` if they
+/// are separate, nested or coincident; touching circles yield the same point
+/// twice.
+pub fn circle_circle_points(a: &Circle, b: &Circle) -> Option<(Vector2<f32>, Vector2<f32>)> {
+    let d = b.center - a.center;
+    let dist_sq = d.dot(&d);
+    let dist = dist_sq.sqrt();
+    if dist == 0.0 || dist > a.radius + b.radius || dist < (a.radius - b.radius).abs() {
+        return None;
+    }
+    // Distance from a's center to the chord, and half the chord length
+    let along = (a.radius * a.radius - b.radius * b.radius + dist_sq) / (2.0 * dist);
+    let half = (a.radius * a.radius - along * along).max(0.0).sqrt();
+    let dir = d * dist.recip();
+    let mid = a.center + dir * along;
+    let perp = Vector2::new(-dir.y, dir.x) * half;
+    Some((mid + perp, mid - perp))
+}
+
+/// Contact between a circle and a segment, with the normal pointing from the
+/// segment toward the circle center.
+pub fn circle_segment(circle: &Circle, segment: &Segment2) -> Option<Contact> {
+    let closest = segment.closest_point(circle.center);
+    let d = circle.center - closest;
+    let dist = d.x.hypot(d.y);
+    if dist > circle.radius {
+        return None;
+    }
+    let normal = if dist > 0.0 {
+        d * dist.recip()
+    } else {
+        // Center on the segment, use the segment's left-hand normal
+        let s = segment.b - segment.a;
+        let len = s.x.hypot(s.y);
+        if len > 0.0 {
+            Vector2::new(-s.y, s.x) * len.recip()
+        } else {
+            Vector2::new(1.0, 0.0)
+        }
+    };
+    Some(Contact {
+        point: closest,
+        normal,
+        depth: circle.radius - dist,
+    })
+}
+
+/// Intersection of the infinite lines through two segments, or `None` if they
+/// are parallel.
+pub fn line_line(a: &Segment2, b: &Segment2) -> Option<Vector2<f32>> {
+    let r = a.b - a.a;
+    let s = b.b - b.a;
+    let denom = r.perp_dot(&s);
+    if denom == 0.0 {
+        return None;
+    }
+    let t = (b.a - a.a).perp_dot(&s) / denom;
+    Some(a.a + r * t)
+}
+
+/// Intersection point of two segments, or `None` if they do not cross or are
+/// parallel.
+pub fn segment_segment(a: &Segment2, b: &Segment2) -> Option<Vector2<f32>> {
+    let r = a.b - a.a;
+    let s = b.b - b.a;
+    let denom = r.perp_dot(&s);
+    if denom == 0.0 {
+        return None;
+    }
+    let q = b.a - a.a;
+    let t = q.perp_dot(&s) / denom;
+    let u = q.perp_dot(&r) / denom;
+    if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+        Some(a.a + r * t)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circle_circle() {
+        let a = Circle::new(Vector2::new(0.0, 0.0), 2.0);
+        let b = Circle::new(Vector2::new(3.0, 0.0), 2.0);
+        let contact = circle_circle(&a, &b).unwrap();
+        assert_eq!(contact.normal, Vector2::new(1.0, 0.0));
+        assert_eq!(contact.depth, 1.0);
+        assert_eq!(contact.point, Vector2::new(1.5, 0.0));
+        assert_eq!(
+            circle_circle(&a, &Circle::new(Vector2::new(5.0, 0.0), 2.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_circle_circle_points() {
+        let a = Circle::new(Vector2::new(0.0, 0.0), 5.0);
+        let b = Circle::new(Vector2::new(8.0, 0.0), 5.0);
+        let (p, q) = circle_circle_points(&a, &b).unwrap();
+        assert_eq!(p, Vector2::new(4.0, 3.0));
+        assert_eq!(q, Vector2::new(4.0, -3.0));
+        let inner = Circle::new(Vector2::new(1.0, 0.0), 1.0);
+        assert_eq!(circle_circle_points(&a, &inner), None);
+    }
+
+    #[test]
+    fn test_circle_segment() {
+        let segment = Segment2::new(Vector2::new(-5.0, 0.0), Vector2::new(5.0, 0.0));
+        let circle = Circle::new(Vector2::new(1.0, 0.5), 1.0);
+        let contact = circle_segment(&circle, &segment).unwrap();
+        assert_eq!(contact.point, Vector2::new(1.0, 0.0));
+        assert_eq!(contact.normal, Vector2::new(0.0, 1.0));
+        assert_eq!(contact.depth, 0.5);
+
+        // Past the end of the segment
+        let circle = Circle::new(Vector2::new(5.5, 0.5), 1.0);
+        let contact = circle_segment(&circle, &segment).unwrap();
+        assert_eq!(contact.point, Vector2::new(5.0, 0.0));
+        assert!(circle_segment(&Circle::new(Vector2::new(7.0, 0.0), 1.0), &segment).is_none());
+    }
+
+    #[test]
+    fn test_line_and_segment_intersection() {
+        let a = Segment2::new(Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0));
+        let b = Segment2::new(Vector2::new(4.0, 0.0), Vector2::new(3.0, 1.0));
+        assert_eq!(line_line(&a, &b), Some(Vector2::new(2.0, 2.0)));
+        assert_eq!(segment_segment(&a, &b), None);
+
+        let c = Segment2::new(Vector2::new(0.0, 1.0), Vector2::new(1.0, 0.0));
+        assert_eq!(segment_segment(&a, &c), Some(Vector2::new(0.5, 0.5)));
+        let parallel = Segment2::new(Vector2::new(0.0, 1.0), Vector2::new(1.0, 2.0));
+        assert_eq!(line_line(&a, &parallel), None);
+    }
+}
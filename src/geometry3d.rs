@@ -0,0 +1,173 @@
+//! Closest point queries between 3D lines and segments, the core of capsule
+//! collision, and the result type of raycasts.
+
+use crate::Vector3;
+
+/// Line segment between two points. Also used to describe the infinite line
+/// through them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Segment3 {
+    pub a: Vector3<f32>,
+    pub b: Vector3<f32>,
+}
+
+impl Segment3 {
+    pub const fn new(a: Vector3<f32>, b: Vector3<f32>) -> Segment3 {
+        Segment3 { a, b }
+    }
+
+    /// Point at parameter `t`, with 0 at `a` and 1 at `b`.
+    pub fn point_at(&self, t: f32) -> Vector3<f32> {
+        self.a + (self.b - self.a) * t
+    }
+}
+
+/// Where a ray hit a surface, as reported by the caller's physics engine.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    pub point: Vector3<f32>,
+    /// Unit surface normal at the hit point, facing the ray origin.
+    pub normal: Vector3<f32>,
+    /// Distance along the ray from its origin to the hit point.
+    pub distance: f32,
+}
+
+impl RayHit {
+    pub const fn new(point: Vector3<f32>, normal: Vector3<f32>, distance: f32) -> RayHit {
+        RayHit {
+            point,
+            normal,
+            distance,
+        }
+    }
+}
+
+/// Parameters `(s, t)` of the closest points of two lines. Parallel lines have
+/// no unique answer; `s` is then 0.
+fn line_parameters(p: &Segment3, q: &Segment3) -> (f32, f32) {
+    let d1 = p.b - p.a;
+    let d2 = q.b - q.a;
+    let r = p.a - q.a;
+    let (a, b, c) = (d1.dot(&d1), d1.dot(&d2), d1.dot(&r));
+    let (e, f) = (d2.dot(&d2), d2.dot(&r));
+    if a == 0.0 && e == 0.0 {
+        return (0.0, 0.0);
+    }
+    if a == 0.0 {
+        return (0.0, f / e);
+    }
+    if e == 0.0 {
+        return (-c / a, 0.0);
+    }
+    let denom = a * e - b * b;
+    let s = if denom > 1e-6 * a * e {
+        (b * f - c * e) / denom
+    } else {
+        0.0
+    };
+    (s, (b * s + f) / e)
+}
+
+/// Closest points between the infinite lines through two segments. For
+/// parallel lines the point on `p` is `p.a`.
+pub fn closest_points_on_lines(p: &Segment3, q: &Segment3) -> (Vector3<f32>, Vector3<f32>) {
+    let (s, t) = line_parameters(p, q);
+    (p.point_at(s), q.point_at(t))
+}
+
+/// Closest points between two segments, one on each. Segments that collapse to
+/// a point and parallel segments are handled; for overlapping parallel
+/// segments one of the closest pairs is returned.
+pub fn closest_points(p: &Segment3, q: &Segment3) -> (Vector3<f32>, Vector3<f32>) {
+    let d1 = p.b - p.a;
+    let d2 = q.b - q.a;
+    let r = p.a - q.a;
+    let (a, b, c) = (d1.dot(&d1), d1.dot(&d2), d1.dot(&r));
+    let e = d2.dot(&d2);
+
+    // Clamp the line solution to the first segment, find the closest point of
+    // the second one to it, and if that had to be clamped too, go back and
+    // recompute the point on the first segment
+    let on_p = |s: f32| if a > 0.0 { s.clamp(0.0, 1.0) } else { 0.0 };
+    let s = line_parameters(p, q).0.clamp(0.0, 1.0);
+    let t = if e > 0.0 {
+        (b * s + d2.dot(&r)) / e
+    } else {
+        0.0
+    };
+    let (s, t) = if t < 0.0 {
+        (on_p(-c / a), 0.0)
+    } else if t > 1.0 {
+        (on_p((b - c) / a), 1.0)
+    } else {
+        (s, t)
+    };
+    (p.point_at(s), q.point_at(t))
+}
+
+/// Shortest distance between two segments.
+pub fn segment_distance(p: &Segment3, q: &Segment3) -> f32 {
+    let (a, b) = closest_points(p, q);
+    (b - a).magnitude()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(a: [f32; 3], b: [f32; 3]) -> Segment3 {
+        Segment3::new(Vector3::from(a), Vector3::from(b))
+    }
+
+    #[test]
+    fn test_closest_points_crossing() {
+        let p = seg([-1.0, 0.0, 0.0], [1.0, 0.0, 0.0]);
+        let q = seg([0.5, -1.0, 2.0], [0.5, 1.0, 2.0]);
+        let (a, b) = closest_points(&p, &q);
+        assert_eq!(a, Vector3::new(0.5, 0.0, 0.0));
+        assert_eq!(b, Vector3::new(0.5, 0.0, 2.0));
+        assert_eq!(segment_distance(&p, &q), 2.0);
+    }
+
+    #[test]
+    fn test_closest_points_clamped() {
+        // The lines meet at the origin, outside both segments
+        let p = seg([1.0, 0.0, 0.0], [3.0, 0.0, 0.0]);
+        let q = seg([0.0, 2.0, 0.0], [0.0, 4.0, 0.0]);
+        assert_eq!(
+            closest_points(&p, &q),
+            (Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 2.0, 0.0))
+        );
+        assert_eq!(
+            closest_points_on_lines(&p, &q),
+            (Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_closest_points_degenerate() {
+        // Parallel, not overlapping
+        let p = seg([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]);
+        let q = seg([3.0, 1.0, 0.0], [2.0, 1.0, 0.0]);
+        assert_eq!(
+            closest_points(&p, &q),
+            (Vector3::new(1.0, 0.0, 0.0), Vector3::new(2.0, 1.0, 0.0))
+        );
+
+        // Parallel and overlapping
+        let q = seg([0.5, 1.0, 0.0], [4.0, 1.0, 0.0]);
+        assert_eq!(segment_distance(&p, &q), 1.0);
+
+        // Points
+        let point = seg([0.5, 2.0, 0.0], [0.5, 2.0, 0.0]);
+        assert_eq!(
+            closest_points(&point, &p),
+            (Vector3::new(0.5, 2.0, 0.0), Vector3::new(0.5, 0.0, 0.0))
+        );
+        assert_eq!(
+            closest_points(&p, &point),
+            (Vector3::new(0.5, 0.0, 0.0), Vector3::new(0.5, 2.0, 0.0))
+        );
+        assert_eq!(segment_distance(&point, &point), 0.0);
+    }
+}
@@ -0,0 +1,289 @@
+//! Newtonian gravity between many bodies, directly or with the Barnes-Hut
+//! approximation.
+//!
+//! Accelerations use Plummer softening: a body of mass `m` at offset `d` pulls
+//! with `g * m * d / (|d|^2 + softening^2)^(3/2)`, which stays finite when two
+//! bodies pass through each other.
+
+use crate::Vector3;
+
+/// Leaves hold up to this many bodies, and cells stop splitting below
+/// `MAX_DEPTH` so coincident bodies cannot recurse forever.
+const LEAF_SIZE: usize = 4;
+const MAX_DEPTH: u32 = 32;
+
+fn pull(offset: Vector3<f32>, mass: f32, softening_sq: f32) -> Vector3<f32> {
+    let dist_sq = offset.dot(&offset) + softening_sq;
+    if dist_sq == 0.0 {
+        return Vector3::default();
+    }
+    offset * (mass / (dist_sq * dist_sq.sqrt()))
+}
+
+/// Gravitational acceleration of every body due to all others, by summing
+/// over all pairs. Exact, but O(n^2).
+pub fn direct_accelerations(
+    positions: &[Vector3<f32>],
+    masses: &[f32],
+    g: f32,
+    softening: f32,
+) -> Vec<Vector3<f32>> {
+    assert_eq!(positions.len(), masses.len());
+    let softening_sq = softening * softening;
+    positions
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let mut acc = Vector3::default();
+            for (j, (q, m)) in positions.iter().zip(masses).enumerate() {
+                if i != j {
+                    acc += pull(*q - *p, *m, softening_sq);
+                }
+            }
+            acc * g
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+struct Cell {
+    center: Vector3<f32>,
+    /// Edge length of the cubic cell.
+    size: f32,
+    mass: f32,
+    center_of_mass: Vector3<f32>,
+    /// Child cells, empty for leaves.
+    children: Vec<usize>,
+    /// Bodies of a leaf, as a range into `BarnesHut::order`.
+    bodies: std::ops::Range<usize>,
+}
+
+/// Octree over point masses, summarizing each cell by its total mass and
+/// center of mass so distant groups of bodies can be treated as one.
+#[derive(Clone, Debug)]
+pub struct BarnesHut {
+    cells: Vec<Cell>,
+    order: Vec<usize>,
+    positions: Vec<Vector3<f32>>,
+    masses: Vec<f32>,
+}
+
+impl BarnesHut {
+    /// Builds the octree over the bodies. Panics if the slices differ in length.
+    pub fn new(positions: &[Vector3<f32>], masses: &[f32]) -> BarnesHut {
+        assert_eq!(positions.len(), masses.len());
+        let mut tree = BarnesHut {
+            cells: Vec::new(),
+            order: (0..positions.len()).collect(),
+            positions: positions.to_vec(),
+            masses: masses.to_vec(),
+        };
+        if positions.is_empty() {
+            return tree;
+        }
+
+        let mut min = positions[0];
+        let mut max = positions[0];
+        for p in positions {
+            min = Vector3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+            max = Vector3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+        }
+        let size = (max.x - min.x).max(max.y - min.y).max(max.z - min.z);
+        let center = (min + max) * 0.5;
+        tree.build(0..positions.len(), center, size, 0);
+        tree
+    }
+
+    /// Builds the cell for `order[range]` and returns its index.
+    fn build(
+        &mut self,
+        range: std::ops::Range<usize>,
+        center: Vector3<f32>,
+        size: f32,
+        depth: u32,
+    ) -> usize {
+        let mut mass = 0.0;
+        let mut weighted = Vector3::default();
+        for &i in &self.order[range.clone()] {
+            mass += self.masses[i];
+            weighted += self.positions[i] * self.masses[i];
+        }
+        let center_of_mass = if mass > 0.0 {
+            weighted * mass.recip()
+        } else {
+            center
+        };
+
+        let index = self.cells.len();
+        self.cells.push(Cell {
+            center,
+            size,
+            mass,
+            center_of_mass,
+            children: Vec::new(),
+            bodies: range.clone(),
+        });
+        if range.len() <= LEAF_SIZE || depth >= MAX_DEPTH {
+            return index;
+        }
+
+        // Sort the bodies by octant and recurse into each non-empty one
+        let octant = |p: Vector3<f32>| {
+            (p.x > center.x) as usize
+                | ((p.y > center.y) as usize) << 1
+                | ((p.z > center.z) as usize) << 2
+        };
+        let positions = &self.positions;
+        self.order[range.clone()].sort_by_key(|&i| octant(positions[i]));
+
+        let quarter = size * 0.25;
+        let mut start = range.start;
+        let mut children = Vec::new();
+        for oct in 0..8 {
+            let end = start
+                + self.order[start..range.end]
+                    .iter()
+                    .take_while(|&&i| octant(self.positions[i]) == oct)
+                    .count();
+            if end > start {
+                let offset = Vector3::new(
+                    if oct & 1 != 0 { quarter } else { -quarter },
+                    if oct & 2 != 0 { quarter } else { -quarter },
+                    if oct & 4 != 0 { quarter } else { -quarter },
+                );
+                children.push(self.build(start..end, center + offset, size * 0.5, depth + 1));
+            }
+            start = end;
+        }
+        self.cells[index].children = children;
+        index
+    }
+
+    /// Acceleration at `point` due to all bodies except `skip`, opening cells
+    /// whose size seen from `point` exceeds the angle `theta`.
+    fn acceleration(
+        &self,
+        point: Vector3<f32>,
+        skip: Option<usize>,
+        theta: f32,
+        softening_sq: f32,
+    ) -> Vector3<f32> {
+        let mut acc = Vector3::default();
+        if self.cells.is_empty() {
+            return acc;
+        }
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let cell = &self.cells[index];
+            let offset = cell.center_of_mass - point;
+            let dist_sq = offset.dot(&offset);
+            // Never approximate a cell containing the point, which would make a
+            // body attract itself for large `theta`
+            let d = point - cell.center;
+            let half = cell.size * 0.5;
+            let inside = d.x.abs() <= half && d.y.abs() <= half && d.z.abs() <= half;
+            if !inside && cell.size * cell.size < theta * theta * dist_sq {
+                acc += pull(offset, cell.mass, softening_sq);
+            } else if cell.children.is_empty() {
+                for &i in &self.order[cell.bodies.clone()] {
+                    if Some(i) != skip {
+                        acc += pull(self.positions[i] - point, self.masses[i], softening_sq);
+                    }
+                }
+            } else {
+                stack.extend_from_slice(&cell.children);
+            }
+        }
+        acc
+    }
+
+    /// Gravitational acceleration at an arbitrary point, such as a probe that
+    /// is not itself one of the bodies.
+    pub fn acceleration_at(
+        &self,
+        point: Vector3<f32>,
+        g: f32,
+        theta: f32,
+        softening: f32,
+    ) -> Vector3<f32> {
+        self.acceleration(point, None, theta, softening * softening) * g
+    }
+
+    /// Acceleration of every body due to all others. `theta` trades accuracy
+    /// for speed: 0 opens every cell and matches `direct_accelerations`, 0.5
+    /// is a common choice.
+    pub fn accelerations(&self, g: f32, theta: f32, softening: f32) -> Vec<Vector3<f32>> {
+        let softening_sq = softening * softening;
+        self.positions
+            .iter()
+            .enumerate()
+            .map(|(i, p)| self.acceleration(*p, Some(i), theta, softening_sq) * g)
+            .collect()
+    }
+}
+
+/// Barnes-Hut approximation of `direct_accelerations` in O(n log n), see
+/// `BarnesHut::accelerations`.
+pub fn accelerations(
+    positions: &[Vector3<f32>],
+    masses: &[f32],
+    g: f32,
+    theta: f32,
+    softening: f32,
+) -> Vec<Vector3<f32>> {
+    BarnesHut::new(positions, masses).accelerations(g, theta, softening)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hash33;
+
+    fn bodies(n: i32) -> (Vec<Vector3<f32>>, Vec<f32>) {
+        let positions: Vec<_> = (0..n)
+            .map(|i| hash33(Vector3::new(i, 0, 0), 7) * 100.0)
+            .collect();
+        let masses = (0..n).map(|i| 1.0 + (i % 5) as f32).collect();
+        (positions, masses)
+    }
+
+    fn max_relative_error(a: &[Vector3<f32>], b: &[Vector3<f32>]) -> f32 {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (*x - *y).magnitude() / y.magnitude())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_two_bodies() {
+        let positions = [Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0)];
+        let acc = accelerations(&positions, &[1.0, 3.0], 1.0, 0.5, 0.0);
+        assert_eq!(
+            acc,
+            vec![Vector3::new(0.75, 0.0, 0.0), Vector3::new(-0.25, 0.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_barnes_hut_matches_direct() {
+        let (positions, masses) = bodies(300);
+        let direct = direct_accelerations(&positions, &masses, 2.0, 0.1);
+
+        let exact = accelerations(&positions, &masses, 2.0, 0.0, 0.1);
+        assert!(max_relative_error(&exact, &direct) < 1e-4);
+
+        let approx = accelerations(&positions, &masses, 2.0, 0.5, 0.1);
+        assert!(max_relative_error(&approx, &direct) < 0.05);
+    }
+
+    #[test]
+    fn test_coincident_bodies() {
+        let positions = vec![Vector3::new(1.0, 1.0, 1.0); 10];
+        let acc = accelerations(&positions, &[1.0; 10], 1.0, 0.5, 0.0);
+        assert!(acc.iter().all(|a| *a == Vector3::default()));
+
+        let tree = BarnesHut::new(&positions, &[1.0; 10]);
+        let probe = tree.acceleration_at(Vector3::new(1.0, 3.0, 1.0), 1.0, 0.5, 0.0);
+        assert!((probe - Vector3::new(0.0, -2.5, 0.0)).magnitude() < 1e-6);
+    }
+}
@@ -0,0 +1,335 @@
+//! Traversal of uniform grids of square or cubic cells, such as tilemaps and
+//! voxel worlds.
+
+use crate::hash::hash_u32;
+use crate::{Vector2, Vector3};
+
+/// Digital differential analyzer state shared by the 2D and 3D raycasts
+/// (Amanatides and Woo, "A Fast Voxel Traversal Algorithm for Ray Tracing").
+#[derive(Clone, Debug)]
+struct Dda<const N: usize> {
+    cell: [i32; N],
+    step: [i32; N],
+    /// Ray parameter at which the next boundary on each axis is crossed.
+    t_max: [f32; N],
+    /// Ray parameter between two boundaries on each axis.
+    t_delta: [f32; N],
+    done: bool,
+}
+
+impl<const N: usize> Dda<N> {
+    fn new(origin: [f32; N], dir: [f32; N], cell_size: f32) -> Dda<N> {
+        let mut dda = Dda {
+            cell: [0; N],
+            step: [0; N],
+            t_max: [f32::INFINITY; N],
+            t_delta: [f32::INFINITY; N],
+            done: false,
+        };
+        for i in 0..N {
+            let p = origin[i] / cell_size;
+            dda.cell[i] = p.floor() as i32;
+            if dir[i] > 0.0 {
+                dda.step[i] = 1;
+                dda.t_delta[i] = cell_size / dir[i];
+                dda.t_max[i] = (p.floor() + 1.0 - p) * dda.t_delta[i];
+            } else if dir[i] < 0.0 {
+                dda.step[i] = -1;
+                dda.t_delta[i] = -cell_size / dir[i];
+                dda.t_max[i] = (p - p.floor()) * dda.t_delta[i];
+            }
+        }
+        dda
+    }
+
+    fn next(&mut self) -> Option<[i32; N]> {
+        if self.done {
+            return None;
+        }
+        let current = self.cell;
+        let mut axis = 0;
+        for i in 1..N {
+            if self.t_max[i] < self.t_max[axis] {
+                axis = i;
+            }
+        }
+        if self.t_max[axis].is_infinite() {
+            // Zero direction: only the starting cell is visited
+            self.done = true;
+        } else {
+            self.cell[axis] += self.step[axis];
+            self.t_max[axis] += self.t_delta[axis];
+        }
+        Some(current)
+    }
+}
+
+// Whether the segment from `a` to `b`, in unit cells, only crosses cells that
+// are not `blocked`. Where `grid_raycast` steps past a corner through one side
+// cell, this also requires the other side cell to be free, so the segment
+// can't slip diagonally between two blocked cells.
+pub(crate) fn segment_clear<F>(a: Vector2<f32>, b: Vector2<f32>, blocked: &F) -> bool
+where
+    F: Fn(Vector2<i32>) -> bool,
+{
+    let end = [b.x.floor() as i32, b.y.floor() as i32];
+    let mut dda = Dda::new([a.x, a.y], [b.x - a.x, b.y - a.y], 1.0);
+    loop {
+        if blocked(Vector2::from(dda.cell)) {
+            return false;
+        }
+        // The segment ends at ray parameter 1
+        if dda.cell == end || dda.t_max[0].min(dda.t_max[1]) > 1.0 {
+            return true;
+        }
+        // On a tie the x side cell comes next, so check the y side cell here
+        if (dda.t_max[0] - dda.t_max[1]).abs() < 1e-6
+            && blocked(Vector2::new(dda.cell[0], dda.cell[1] + dda.step[1]))
+        {
+            return false;
+        }
+        dda.next();
+    }
+}
+
+/// Iterator over the cells of a 2D grid pierced by a ray, see `grid_raycast`.
+#[derive(Clone, Debug)]
+pub struct GridRaycast2(Dda<2>);
+
+/// Iterator over the cells of a 3D grid pierced by a ray, see
+/// `grid_raycast3`.
+#[derive(Clone, Debug)]
+pub struct GridRaycast3(Dda<3>);
+
+impl Iterator for GridRaycast2 {
+    type Item = Vector2<i32>;
+
+    fn next(&mut self) -> Option<Vector2<i32>> {
+        self.0.next().map(Vector2::from)
+    }
+}
+
+impl Iterator for GridRaycast3 {
+    type Item = Vector3<i32>;
+
+    fn next(&mut self) -> Option<Vector3<i32>> {
+        self.0.next().map(Vector3::from)
+    }
+}
+
+/// Cells of a grid with square cells of `cell_size` that the ray from
+/// `origin` along `dir` passes through, in order, starting with the cell
+/// containing `origin`. Cell `(i, j)` covers `[i, i + 1) * cell_size` on x and
+/// likewise on y.
+///
+/// The iterator is endless unless `dir` is zero, so bound it with `take` or
+/// `take_while`. When the ray passes exactly through a corner, one of the two
+/// side cells is visited before the diagonal one.
+pub fn grid_raycast(origin: Vector2<f32>, dir: Vector2<f32>, cell_size: f32) -> GridRaycast2 {
+    GridRaycast2(Dda::new([origin.x, origin.y], [dir.x, dir.y], cell_size))
+}
+
+/// Voxels of a grid with cubic cells of `cell_size` that the ray from `origin`
+/// along `dir` passes through, see `grid_raycast`.
+pub fn grid_raycast3(origin: Vector3<f32>, dir: Vector3<f32>, cell_size: f32) -> GridRaycast3 {
+    GridRaycast3(Dda::new(
+        [origin.x, origin.y, origin.z],
+        [dir.x, dir.y, dir.z],
+        cell_size,
+    ))
+}
+
+/// Cells of the rasterized line from `a` to `b` by Bresenham's algorithm,
+/// including both endpoints. Consecutive cells touch by an edge or a corner.
+pub fn line_between(a: Vector2<i32>, b: Vector2<i32>) -> impl Iterator<Item = Vector2<i32>> {
+    let dx = (b.x - a.x).abs();
+    let dy = -(b.y - a.y).abs();
+    let step = Vector2::new((b.x - a.x).signum(), (b.y - a.y).signum());
+    let mut current = Some(a);
+    let mut error = dx + dy;
+    std::iter::from_fn(move || {
+        let cell = current?;
+        current = if cell == b {
+            None
+        } else {
+            let mut next = cell;
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                next.x += step.x;
+            }
+            if e2 <= dx {
+                error += dx;
+                next.y += step.y;
+            }
+            Some(next)
+        };
+        Some(cell)
+    })
+}
+
+/// Position of `index` in a seeded pseudo-random permutation of `0..count`.
+/// Every index maps to a distinct position, so the permutation never needs
+/// to be stored. Panics if `index` is not below `count`.
+///
+/// A four round Feistel network over the smallest even number of bits that
+/// covers `count` is a bijection on that power of two range; values landing
+/// outside `0..count` are fed through again until they come back in range.
+pub fn permute_index(index: u32, count: u32, seed: u32) -> u32 {
+    assert!(index < count, "index out of range");
+    let bits = (32 - (count - 1).leading_zeros()).max(2).div_ceil(2);
+    let mask = (1u64 << bits) as u32 - 1;
+    let mut value = index;
+    loop {
+        let (mut left, mut right) = (value >> bits, value & mask);
+        for round in 0..4 {
+            let f = hash_u32(right as i32, round, 0, seed) & mask;
+            (left, right) = (right, left ^ f);
+        }
+        value = (left << bits) | right;
+        if value < count {
+            return value;
+        }
+    }
+}
+
+/// All cells of the `size.x` by `size.y` grid starting at the origin, each
+/// exactly once, in a seeded pseudo-random order. Nothing is allocated, so
+/// huge grids can be visited progressively, e.g. for dithered updates.
+/// Panics if the grid has more than `u32::MAX` cells.
+pub fn shuffled_cells(size: Vector2<i32>, seed: u32) -> impl Iterator<Item = Vector2<i32>> {
+    let width = size.x.max(0) as u32;
+    let count = width
+        .checked_mul(size.y.max(0) as u32)
+        .expect("grid has more than u32::MAX cells");
+    (0..count).map(move |i| {
+        let cell = permute_index(i, count, seed);
+        Vector2::new((cell % width) as i32, (cell / width) as i32)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_raycast() {
+        let cells: Vec<_> = grid_raycast(Vector2::new(0.5, 0.2), Vector2::new(1.0, 1.0), 1.0)
+            .take(4)
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                Vector2::new(0, 0),
+                Vector2::new(1, 0),
+                Vector2::new(1, 1),
+                Vector2::new(2, 1),
+            ]
+        );
+
+        // Negative direction and larger cells
+        let cells: Vec<_> = grid_raycast(Vector2::new(1.0, 3.0), Vector2::new(-1.0, 0.0), 2.0)
+            .take(3)
+            .collect();
+        assert_eq!(
+            cells,
+            vec![Vector2::new(0, 1), Vector2::new(-1, 1), Vector2::new(-2, 1)]
+        );
+
+        let cells: Vec<_> =
+            grid_raycast(Vector2::new(-0.5, 0.5), Vector2::new(0.0, 0.0), 1.0).collect();
+        assert_eq!(cells, vec![Vector2::new(-1, 0)]);
+    }
+
+    #[test]
+    fn test_grid_raycast3() {
+        let cells: Vec<_> = grid_raycast3(
+            Vector3::new(0.5, 0.5, 0.5),
+            Vector3::new(0.0, -1.0, 2.5),
+            1.0,
+        )
+        .take(4)
+        .collect();
+        assert_eq!(
+            cells,
+            vec![
+                Vector3::new(0, 0, 0),
+                Vector3::new(0, 0, 1),
+                Vector3::new(0, -1, 1),
+                Vector3::new(0, -1, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_between() {
+        let cells: Vec<_> = line_between(Vector2::new(0, 0), Vector2::new(5, 2)).collect();
+        assert_eq!(
+            cells,
+            vec![
+                Vector2::new(0, 0),
+                Vector2::new(1, 0),
+                Vector2::new(2, 1),
+                Vector2::new(3, 1),
+                Vector2::new(4, 2),
+                Vector2::new(5, 2),
+            ]
+        );
+
+        let steep: Vec<_> = line_between(Vector2::new(1, 3), Vector2::new(0, -1)).collect();
+        assert_eq!(steep.len(), 5);
+        assert_eq!(steep.first(), Some(&Vector2::new(1, 3)));
+        assert_eq!(steep.last(), Some(&Vector2::new(0, -1)));
+
+        let point: Vec<_> = line_between(Vector2::new(2, 2), Vector2::new(2, 2)).collect();
+        assert_eq!(point, vec![Vector2::new(2, 2)]);
+    }
+
+    #[test]
+    fn test_permute_index() {
+        for count in [1, 2, 5, 64, 1000] {
+            let mut seen = vec![false; count as usize];
+            for i in 0..count {
+                let p = permute_index(i, count, 9);
+                assert!(!seen[p as usize]);
+                seen[p as usize] = true;
+            }
+        }
+        let a: Vec<_> = (0..16).map(|i| permute_index(i, 16, 1)).collect();
+        let b: Vec<_> = (0..16).map(|i| permute_index(i, 16, 2)).collect();
+        assert_ne!(a, b);
+        assert_ne!(a, (0..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_segment_clear() {
+        // Two blocked cells touching at the corner (1, 1)
+        let blocked = |c: Vector2<i32>| c == Vector2::new(1, 0) || c == Vector2::new(0, 1);
+        let center = |x: f32, y: f32| Vector2::new(x + 0.5, y + 0.5);
+        assert!(!segment_clear(center(0.0, 0.0), center(1.0, 1.0), &blocked));
+        assert!(!segment_clear(center(1.0, 1.0), center(0.0, 0.0), &blocked));
+        assert!(segment_clear(center(0.0, 0.0), center(0.0, -3.0), &blocked));
+        assert!(segment_clear(center(2.0, 2.0), center(5.0, 4.0), &blocked));
+    }
+
+    #[test]
+    fn test_shuffled_cells() {
+        let mut cells: Vec<_> = shuffled_cells(Vector2::new(7, 3), 4).collect();
+        assert_eq!(cells.len(), 21);
+        assert_eq!(
+            cells,
+            shuffled_cells(Vector2::new(7, 3), 4).collect::<Vec<_>>()
+        );
+        cells.sort_by_key(|c| (c.y, c.x));
+        for (i, c) in cells.iter().enumerate() {
+            assert_eq!(*c, Vector2::new(i as i32 % 7, i as i32 / 7));
+        }
+        assert_eq!(shuffled_cells(Vector2::new(0, 5), 4).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "more than u32::MAX cells")]
+    fn test_shuffled_cells_too_many() {
+        let _ = shuffled_cells(Vector2::new(70000, 70000), 0);
+    }
+}
@@ -0,0 +1,135 @@
+//! Guidance laws for missiles and homing projectiles.
+//!
+//! Both laws return the commanded acceleration, perpendicular to the line of
+//! sight or the missile's velocity, limited to `max_acceleration`. For a limit
+//! given in g, pass `max_g * 9.81`.
+
+use crate::Vector3;
+
+/// Tuning of a homing projectile.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Guidance {
+    /// Proportional navigation gain `N`, typically between 3 and 5. Also the
+    /// turn gain of pure pursuit.
+    pub navigation_constant: f32,
+    pub max_acceleration: f32,
+}
+
+impl Default for Guidance {
+    fn default() -> Self {
+        Guidance {
+            navigation_constant: 4.0,
+            max_acceleration: f32::INFINITY,
+        }
+    }
+}
+
+impl Guidance {
+    pub const fn new(navigation_constant: f32, max_acceleration: f32) -> Guidance {
+        Guidance {
+            navigation_constant,
+            max_acceleration,
+        }
+    }
+
+    fn clamp(&self, a: Vector3<f32>) -> Vector3<f32> {
+        let mag = a.magnitude();
+        if mag > self.max_acceleration {
+            a * (self.max_acceleration / mag)
+        } else {
+            a
+        }
+    }
+
+    /// Proportional navigation: turns at `N` times the rotation rate of the
+    /// line of sight, `a = N * Vc * (omega x r_hat)` with closing speed `Vc`.
+    /// Leads a moving target onto a collision course without predicting its
+    /// path. Zero once the projectile is on the target or moving away from it.
+    pub fn proportional_navigation(
+        &self,
+        position: Vector3<f32>,
+        velocity: Vector3<f32>,
+        target: Vector3<f32>,
+        target_velocity: Vector3<f32>,
+    ) -> Vector3<f32> {
+        let r = target - position;
+        let r_sq = r.dot(&r);
+        if r_sq == 0.0 {
+            return Vector3::default();
+        }
+        let v = target_velocity - velocity;
+        let los = r * r_sq.sqrt().recip();
+        let closing = -v.dot(&los);
+        if closing <= 0.0 {
+            return Vector3::default();
+        }
+        let omega = r.cross(&v) * r_sq.recip();
+        self.clamp(omega.cross(&los) * (self.navigation_constant * closing))
+    }
+
+    /// Pure pursuit: turns the velocity straight toward the target's current
+    /// position, at a rate of `N` times the sine of the heading error. Simple,
+    /// but trails moving targets in a tail chase.
+    pub fn pure_pursuit(
+        &self,
+        position: Vector3<f32>,
+        velocity: Vector3<f32>,
+        target: Vector3<f32>,
+    ) -> Vector3<f32> {
+        let r = target - position;
+        let speed = velocity.magnitude();
+        if speed == 0.0 || r.dot(&r) == 0.0 {
+            return Vector3::default();
+        }
+        let heading = velocity * speed.recip();
+        let los = r.normalize();
+        // Part of the line of sight perpendicular to the heading
+        let lateral = los - heading * los.dot(&heading);
+        self.clamp(lateral * (self.navigation_constant * speed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proportional_navigation() {
+        let g = Guidance::new(3.0, f32::INFINITY);
+        let position = Vector3::new(0.0, 0.0, 0.0);
+        let velocity = Vector3::new(1.0, 0.0, 0.0);
+        let target = Vector3::new(10.0, 0.0, 0.0);
+        // Target crossing to +y: lead it
+        let a = g.proportional_navigation(position, velocity, target, Vector3::new(0.0, 1.0, 0.0));
+        assert!((a - Vector3::new(0.0, 0.3, 0.0)).magnitude() < 1e-6);
+        // Already on a collision course: no correction
+        let still = Vector3::new(0.0, 0.0, 0.0);
+        assert_eq!(
+            g.proportional_navigation(position, velocity, target, still),
+            Vector3::new(0.0, 0.0, 0.0)
+        );
+        // Opening range: no command
+        let fleeing = Vector3::new(2.0, 1.0, 0.0);
+        assert_eq!(
+            g.proportional_navigation(position, velocity, target, fleeing),
+            Vector3::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_pure_pursuit() {
+        let g = Guidance::new(2.0, 3.0);
+        let position = Vector3::new(0.0, 0.0, 0.0);
+        let velocity = Vector3::new(1.0, 0.0, 0.0);
+        let a = g.pure_pursuit(position, velocity, Vector3::new(0.0, 0.0, 5.0));
+        assert_eq!(a, Vector3::new(0.0, 0.0, 2.0));
+        // Clamped to the acceleration limit
+        let fast = Vector3::new(4.0, 0.0, 0.0);
+        let a = g.pure_pursuit(position, fast, Vector3::new(0.0, 0.0, 5.0));
+        assert_eq!(a, Vector3::new(0.0, 0.0, 3.0));
+        assert_eq!(
+            g.pure_pursuit(position, velocity, Vector3::new(5.0, 0.0, 0.0)),
+            Vector3::new(0.0, 0.0, 0.0)
+        );
+    }
+}
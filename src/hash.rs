@@ -0,0 +1,123 @@
+//! Stateless, deterministic pseudo-random values keyed by integer grid
+//! coordinates. Only integer arithmetic, exact conversions and `sqrt` are used,
+//! so results are identical on every platform.
+
+use crate::{Vector2, Vector3};
+
+/// Hashes three integer coordinates and a seed into a well mixed `u32`.
+pub fn hash_u32(x: i32, y: i32, z: i32, seed: u32) -> u32 {
+    let mut h = (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ (z as u32).wrapping_mul(0xcb1a_b31f)
+        ^ seed.wrapping_mul(0x1656_67b1);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb_352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846c_a68b);
+    h ^= h >> 16;
+    h
+}
+
+// Top 24 bits as a float in [0, 1), exactly representable in f32
+fn to_unit(h: u32) -> f32 {
+    (h >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// Float in [0, 1) from a 2D coordinate.
+pub fn hash21(p: Vector2<i32>, seed: u32) -> f32 {
+    to_unit(hash_u32(p.x, p.y, 0, seed))
+}
+
+/// Float in [0, 1) from a 3D coordinate.
+pub fn hash31(p: Vector3<i32>, seed: u32) -> f32 {
+    to_unit(hash_u32(p.x, p.y, p.z, seed))
+}
+
+/// Vector with components in [0, 1) from a 2D coordinate.
+pub fn hash22(p: Vector2<i32>, seed: u32) -> Vector2<f32> {
+    Vector2::new(
+        to_unit(hash_u32(p.x, p.y, 0, seed)),
+        to_unit(hash_u32(p.x, p.y, 1, seed)),
+    )
+}
+
+/// Vector with components in [0, 1) from a 3D coordinate.
+pub fn hash33(p: Vector3<i32>, seed: u32) -> Vector3<f32> {
+    let seed = seed.wrapping_mul(3);
+    Vector3::new(
+        to_unit(hash_u32(p.x, p.y, p.z, seed)),
+        to_unit(hash_u32(p.x, p.y, p.z, seed.wrapping_add(1))),
+        to_unit(hash_u32(p.x, p.y, p.z, seed.wrapping_add(2))),
+    )
+}
+
+/// Uniformly distributed unit vector from a 2D coordinate.
+pub fn unit_vector2(p: Vector2<i32>, seed: u32) -> Vector2<f32> {
+    // Rejection sampling in the unit disk avoids platform dependent sin/cos
+    let mut round = seed.wrapping_mul(0x9e37_79b9);
+    loop {
+        let v = hash22(p, round) * 2.0 - Vector2::new(1.0, 1.0);
+        let len_sq = v.x * v.x + v.y * v.y;
+        if len_sq > 1e-6 && len_sq <= 1.0 {
+            return v * len_sq.sqrt().recip();
+        }
+        round = round.wrapping_add(1);
+    }
+}
+
+/// Uniformly distributed unit vector from a 3D coordinate.
+pub fn unit_vector3(p: Vector3<i32>, seed: u32) -> Vector3<f32> {
+    let mut round = seed.wrapping_mul(0x9e37_79b9);
+    loop {
+        let v = hash33(p, round) * 2.0 - Vector3::new(1.0, 1.0, 1.0);
+        let len_sq = v.dot(&v);
+        if len_sq > 1e-6 && len_sq <= 1.0 {
+            return v * len_sq.sqrt().recip();
+        }
+        round = round.wrapping_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_is_deterministic() {
+        let p = Vector3::new(12, -7, 300);
+        assert_eq!(hash31(p, 5), hash31(p, 5));
+        assert_eq!(hash33(p, 5), hash33(p, 5));
+        assert_ne!(hash31(p, 5), hash31(p, 6));
+        // Pinned value so accidental changes to the mixing function are caught
+        assert_eq!(hash_u32(1, 2, 3, 4), 0xafa6fa8a);
+        assert_ne!(hash_u32(1, 2, 3, 4), hash_u32(2, 1, 3, 4));
+    }
+
+    #[test]
+    fn test_hash_ranges() {
+        for i in -50..50 {
+            let p = Vector2::new(i, i * 7);
+            let h = hash21(p, 0);
+            assert!((0.0..1.0).contains(&h));
+            let v = hash22(p, 1);
+            assert!((0.0..1.0).contains(&v.x) && (0.0..1.0).contains(&v.y));
+        }
+    }
+
+    #[test]
+    fn test_hash_mean() {
+        let n = 10_000;
+        let sum: f32 = (0..n).map(|i| hash21(Vector2::new(i, 0), 9)).sum();
+        assert!((sum / n as f32 - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_unit_vectors() {
+        for i in 0..100 {
+            let v = unit_vector2(Vector2::new(i, -i), 3);
+            assert!(((v.x * v.x + v.y * v.y) - 1.0).abs() < 1e-5);
+            let v = unit_vector3(Vector3::new(i, 2 * i, -i), 3);
+            assert!((v.dot(&v) - 1.0).abs() < 1e-5);
+        }
+    }
+}
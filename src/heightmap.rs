@@ -0,0 +1,143 @@
+//! Terrain heights stored as a regular grid, sampled at world positions for
+//! ground collision and placement.
+//!
+//! Positions on the ground plane are `Vector2`s of world x and z, and y is
+//! up. Grid point `(i, j)` sits at `(i, j) * cell_size` and its height is
+//! `heights[j * width + i] * height_scale`.
+
+use crate::field::gradient2;
+use crate::{Vector2, Vector3};
+
+/// Read-only view of a row-major height grid with world-space scaling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Heightmap<'a> {
+    heights: &'a [f32],
+    width: usize,
+    pub cell_size: f32,
+    pub height_scale: f32,
+}
+
+impl<'a> Heightmap<'a> {
+    /// Grid of `width` columns with unit cell size and height scale.
+    ///
+    /// Panics if `heights` is empty or not a whole number of rows.
+    pub fn new(heights: &'a [f32], width: usize) -> Heightmap<'a> {
+        assert!(width > 0 && !heights.is_empty(), "empty heightmap");
+        assert!(heights.len().is_multiple_of(width), "partial row");
+        Heightmap {
+            heights,
+            width,
+            cell_size: 1.0,
+            height_scale: 1.0,
+        }
+    }
+
+    /// Same grid with grid points `cell_size` apart in world units and heights
+    /// multiplied by `height_scale`.
+    pub fn with_scale(self, cell_size: f32, height_scale: f32) -> Heightmap<'a> {
+        Heightmap {
+            cell_size,
+            height_scale,
+            ..self
+        }
+    }
+
+    /// Number of grid points along x and z.
+    pub fn size(&self) -> Vector2<usize> {
+        Vector2 {
+            x: self.width,
+            y: self.heights.len() / self.width,
+        }
+    }
+
+    /// World height of the grid point `cell`, with x the column and y the
+    /// row, clamped to the grid.
+    pub fn height(&self, cell: Vector2<i32>) -> f32 {
+        let size = self.size();
+        let i = (cell.x.max(0) as usize).min(size.x - 1);
+        let j = (cell.y.max(0) as usize).min(size.y - 1);
+        self.heights[j * self.width + i] * self.height_scale
+    }
+
+    /// Bilinearly interpolated world height below `position`. Positions off
+    /// the grid take the height of the nearest edge.
+    pub fn sample(&self, position: Vector2<f32>) -> f32 {
+        let x = position.x / self.cell_size;
+        let z = position.y / self.cell_size;
+        let (i, j) = (x.floor(), z.floor());
+        let size = self.size();
+        // Clamp the fraction too, so the edges extend flat
+        let fx = if x < 0.0 || x >= (size.x - 1) as f32 {
+            0.0
+        } else {
+            x - i
+        };
+        let fz = if z < 0.0 || z >= (size.y - 1) as f32 {
+            0.0
+        } else {
+            z - j
+        };
+        // Far off the grid the cast saturates, and so does the neighbor
+        let cell = Vector2::new(i as i32, j as i32);
+        let at = |di: i32, dj: i32| {
+            self.height(Vector2::new(
+                cell.x.saturating_add(di),
+                cell.y.saturating_add(dj),
+            ))
+        };
+        let near = at(0, 0) + (at(1, 0) - at(0, 0)) * fx;
+        let far = at(0, 1) + (at(1, 1) - at(0, 1)) * fx;
+        near + (far - near) * fz
+    }
+
+    /// World position on the surface above or below `position`.
+    pub fn surface_point(&self, position: Vector2<f32>) -> Vector3<f32> {
+        Vector3::new(position.x, self.sample(position), position.y)
+    }
+
+    /// Unit surface normal at `position`, from the slope over one cell.
+    pub fn normal_at(&self, position: Vector2<f32>) -> Vector3<f32> {
+        let slope = gradient2(|p| self.sample(p), position, 0.5 * self.cell_size);
+        Vector3::new(-slope.x, 1.0, -slope.y).normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3x2 grid rising by 1 per cell along x
+    const RAMP: [f32; 6] = [0.0, 1.0, 2.0, 0.0, 1.0, 2.0];
+
+    #[test]
+    fn test_sample() {
+        let map = Heightmap::new(&RAMP, 3).with_scale(2.0, 10.0);
+        assert_eq!(map.size(), Vector2 { x: 3, y: 2 });
+        assert_eq!(map.height(Vector2::new(2, 0)), 20.0);
+        assert_eq!(map.height(Vector2::new(-5, i32::MAX)), 0.0);
+        assert_eq!(map.sample(Vector2::new(1.0, 1.0)), 5.0);
+        assert_eq!(map.sample(Vector2::new(3.0, 0.5)), 15.0);
+        // Off the grid
+        assert_eq!(map.sample(Vector2::new(-5.0, 0.0)), 0.0);
+        assert_eq!(map.sample(Vector2::new(9.0, 9.0)), 20.0);
+        assert_eq!(map.sample(Vector2::new(1e12, -1e12)), 20.0);
+        assert_eq!(
+            map.surface_point(Vector2::new(2.0, 0.0)),
+            Vector3::new(2.0, 10.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_normal_at() {
+        let map = Heightmap::new(&RAMP, 3);
+        let n = map.normal_at(Vector2::new(1.0, 0.5));
+        let expected = Vector3::new(-1.0f32, 1.0, 0.0).normalize();
+        assert!((n - expected).magnitude() < 1e-5);
+
+        let flat = Heightmap::new(&[3.0; 4], 2);
+        assert_eq!(
+            flat.normal_at(Vector2::new(0.5, 0.5)),
+            Vector3::new(0.0, 1.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Influence maps: sparse scalar fields over a 2D grid of square cells, used
+//! by game AI to track threat, scent, noise or territory.
+//!
+//! Cells are addressed by integer coordinates, with cell `(i, j)` covering
+//! `[i, i + 1) x [j, j + 1)` times the cell size, matching the cells visited by
+//! [`crate::grid::grid_raycast`]. Cells without a value read as zero.
+
+use std::collections::HashMap;
+
+use crate::Vector2;
+
+const NEIGHBORS: [Vector2<i32>; 4] = [
+    Vector2 { x: 1, y: 0 },
+    Vector2 { x: -1, y: 0 },
+    Vector2 { x: 0, y: 1 },
+    Vector2 { x: 0, y: -1 },
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InfluenceMap {
+    pub cell_size: f32,
+    cells: HashMap<Vector2<i32>, f32>,
+}
+
+impl InfluenceMap {
+    pub fn new(cell_size: f32) -> InfluenceMap {
+        InfluenceMap {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Cell containing the world `position`.
+    pub fn cell_of(&self, position: Vector2<f32>) -> Vector2<i32> {
+        Vector2::new(
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    pub fn get(&self, cell: Vector2<i32>) -> f32 {
+        self.cells.get(&cell).copied().unwrap_or(0.0)
+    }
+
+    pub fn set(&mut self, cell: Vector2<i32>, value: f32) {
+        self.cells.insert(cell, value);
+    }
+
+    /// Adds `amount` to the cell containing the world `position`.
+    pub fn deposit(&mut self, position: Vector2<f32>, amount: f32) {
+        *self.cells.entry(self.cell_of(position)).or_insert(0.0) += amount;
+    }
+
+    /// Cells holding a value, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item = (Vector2<i32>, f32)> + '_ {
+        self.cells.iter().map(|(&cell, &value)| (cell, value))
+    }
+
+    /// Exponential decay of every cell by `rate` per second over `dt`
+    /// seconds, independent of the frame rate.
+    pub fn decay(&mut self, rate: f32, dt: f32) {
+        let factor = (-rate * dt).exp();
+        for value in self.cells.values_mut() {
+            *value *= factor;
+        }
+    }
+
+    /// One diffusion step: every cell moves a fraction `rate` in [0, 1] of the
+    /// way toward the mean of its four edge neighbors. The total influence is
+    /// conserved while it spreads into neighboring cells.
+    pub fn diffuse(&mut self, rate: f32) {
+        let mut next = HashMap::with_capacity(self.cells.len() * 2);
+        for &cell in self.cells.keys() {
+            for offset in NEIGHBORS.iter().chain(&[Vector2::new(0, 0)]) {
+                let c = cell + *offset;
+                next.entry(c).or_insert_with(|| {
+                    let mean = NEIGHBORS.iter().map(|&n| self.get(c + n)).sum::<f32>() * 0.25;
+                    let value = self.get(c);
+                    value + (mean - value) * rate
+                });
+            }
+        }
+        self.cells = next;
+    }
+
+    /// Removes cells whose magnitude fell below `threshold`, keeping decayed
+    /// and diffused maps sparse.
+    pub fn prune(&mut self, threshold: f32) {
+        self.cells.retain(|_, value| value.abs() >= threshold);
+    }
+
+    /// Value at the world `position`, bilinearly interpolated between the
+    /// centers of the four nearest cells.
+    pub fn sample(&self, position: Vector2<f32>) -> f32 {
+        let x = position.x / self.cell_size - 0.5;
+        let y = position.y / self.cell_size - 0.5;
+        let (i, j) = (x.floor() as i32, y.floor() as i32);
+        let (fx, fy) = (x - x.floor(), y - y.floor());
+        let v = |di, dj| self.get(Vector2::new(i + di, j + dj));
+        let bottom = v(0, 0) + (v(1, 0) - v(0, 0)) * fx;
+        let top = v(0, 1) + (v(1, 1) - v(0, 1)) * fx;
+        bottom + (top - bottom) * fy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deposit_and_decay() {
+        let mut map = InfluenceMap::new(2.0);
+        map.deposit(Vector2::new(3.0, -0.5), 4.0);
+        map.deposit(Vector2::new(2.5, -1.5), 2.0);
+        assert_eq!(map.get(Vector2::new(1, -1)), 6.0);
+        map.decay(2f32.ln(), 1.0);
+        assert!((map.get(Vector2::new(1, -1)) - 3.0).abs() < 1e-6);
+        map.prune(5.0);
+        assert_eq!(map.cells().count(), 0);
+    }
+
+    #[test]
+    fn test_diffuse() {
+        let mut map = InfluenceMap::new(1.0);
+        map.set(Vector2::new(0, 0), 8.0);
+        map.diffuse(0.5);
+        assert_eq!(map.get(Vector2::new(0, 0)), 4.0);
+        assert_eq!(map.get(Vector2::new(-1, 0)), 1.0);
+        assert_eq!(map.get(Vector2::new(0, 1)), 1.0);
+        map.diffuse(0.5);
+        let total: f32 = map.cells().map(|(_, v)| v).sum();
+        assert!((total - 8.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut map = InfluenceMap::new(2.0);
+        map.set(Vector2::new(0, 0), 4.0);
+        map.set(Vector2::new(1, 0), 8.0);
+        assert_eq!(map.sample(Vector2::new(1.0, 1.0)), 4.0);
+        assert_eq!(map.sample(Vector2::new(2.0, 1.0)), 6.0);
+        assert_eq!(map.sample(Vector2::new(2.0, 2.0)), 3.0);
+    }
+}
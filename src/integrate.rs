@@ -0,0 +1,127 @@
+//! Numerical integrators for simulations.
+//!
+//! Like the interpolation helpers these accept any state `V` that can be added,
+//! subtracted and scaled by the scalar type `T`, so they work with scalars and
+//! every vector type.
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::Float;
+
+/// Semi-implicit (symplectic) Euler step: updates the velocity first and moves
+/// with the new velocity. Returns the new `(position, velocity)`. Stable for
+/// springs and orbits where explicit Euler gains energy.
+pub fn semi_implicit_euler<V, T>(position: V, velocity: V, acceleration: V, dt: T) -> (V, V)
+where
+    V: Copy + Add<Output = V> + Mul<T, Output = V>,
+    T: Float,
+{
+    let velocity = velocity + acceleration * dt;
+    (position + velocity * dt, velocity)
+}
+
+/// Position Verlet step from the current and previous positions. Returns the
+/// next position; the velocity is implicit in the position history.
+pub fn verlet<V, T>(position: V, previous: V, acceleration: V, dt: T) -> V
+where
+    V: Copy + Add<Output = V> + Sub<Output = V> + Mul<T, Output = V>,
+    T: Float,
+{
+    position + (position - previous) + acceleration * (dt * dt)
+}
+
+/// Classic fourth order Runge-Kutta step of `dy/dt = derivative(t, y)`.
+pub fn rk4<V, T, F>(state: V, t: T, dt: T, derivative: F) -> V
+where
+    V: Copy + Add<Output = V> + Mul<T, Output = V>,
+    T: Float,
+    F: Fn(T, V) -> V,
+{
+    let two = T::ONE + T::ONE;
+    let half = dt / two;
+    let k1 = derivative(t, state);
+    let k2 = derivative(t + half, state + k1 * half);
+    let k3 = derivative(t + half, state + k2 * half);
+    let k4 = derivative(t + dt, state + k3 * dt);
+    state + (k1 + k2 * two + k3 * two + k4) * (dt / (two + two + two))
+}
+
+/// Fourth order Runge-Kutta step of a second order system, given the
+/// acceleration as a function of time, position and velocity. Returns the new
+/// `(position, velocity)`.
+pub fn rk4_motion<V, T, F>(position: V, velocity: V, t: T, dt: T, acceleration: F) -> (V, V)
+where
+    V: Copy + Add<Output = V> + Mul<T, Output = V>,
+    T: Float,
+    F: Fn(T, V, V) -> V,
+{
+    let two = T::ONE + T::ONE;
+    let half = dt / two;
+    let (p1, v1) = (position, velocity);
+    let a1 = acceleration(t, p1, v1);
+    let (p2, v2) = (position + v1 * half, velocity + a1 * half);
+    let a2 = acceleration(t + half, p2, v2);
+    let (p3, v3) = (position + v2 * half, velocity + a2 * half);
+    let a3 = acceleration(t + half, p3, v3);
+    let (p4, v4) = (position + v3 * dt, velocity + a3 * dt);
+    let a4 = acceleration(t + dt, p4, v4);
+
+    let sixth = dt / (two + two + two);
+    (
+        position + (v1 + v2 * two + v3 * two + v4) * sixth,
+        velocity + (a1 + a2 * two + a3 * two + a4) * sixth,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector2;
+
+    #[test]
+    fn test_semi_implicit_euler() {
+        let (p, v) = semi_implicit_euler(
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, -10.0),
+            0.5,
+        );
+        assert_eq!(v, Vector2::new(1.0, -5.0));
+        assert_eq!(p, Vector2::new(0.5, -2.5));
+    }
+
+    #[test]
+    fn test_verlet_constant_acceleration() {
+        // Exact for constant acceleration: x(t) = t^2 with a = 2
+        let dt = 0.1;
+        let (mut prev, mut pos) = (0.0, dt * dt);
+        for _ in 0..9 {
+            let next = verlet(pos, prev, 2.0, dt);
+            prev = pos;
+            pos = next;
+        }
+        assert!((pos - 1.0f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rk4_exponential() {
+        let mut y = 1.0f64;
+        let mut t = 0.0;
+        for _ in 0..10 {
+            y = rk4(y, t, 0.1, |_, y| y);
+            t += 0.1;
+        }
+        assert!((y - std::f64::consts::E).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_rk4_motion_harmonic_oscillator() {
+        let (mut p, mut v) = (Vector2::new(1.0f64, 0.0), Vector2::new(0.0, 1.0));
+        let dt = std::f64::consts::TAU / 1000.0;
+        for i in 0..1000 {
+            (p, v) = rk4_motion(p, v, i as f64 * dt, dt, |_, p, _| p * -1.0);
+        }
+        assert!((p.x - 1.0).abs() < 1e-9 && p.y.abs() < 1e-9);
+        assert!(v.x.abs() < 1e-9 && (v.y - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,58 @@
+//! Interpolation of scalars and vectors alike.
+//!
+//! Every function works with any `V` that can be added, subtracted and scaled
+//! by `T`, which includes `f32`/`f64` themselves and all vector types.
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::Float;
+
+/// Linear interpolation from `a` (t = 0) to `b` (t = 1).
+pub fn lerp<V, T>(a: V, b: V, t: T) -> V
+where
+    V: Copy + Add<Output = V> + Sub<Output = V> + Mul<T, Output = V>,
+{
+    a + (b - a) * t
+}
+
+/// Frame-rate independent exponential smoothing of `current` toward `target`.
+///
+/// `lambda` is the decay rate: after one second (in units of `dt`) the
+/// remaining distance has shrunk by a factor of `e^-lambda`. Unlike
+/// `lerp(current, target, k)` applied each frame, splitting a step into two
+/// half steps yields the same result.
+pub fn damp<V, T>(current: V, target: V, lambda: T, dt: T) -> V
+where
+    V: Copy + Add<Output = V> + Sub<Output = V> + Mul<T, Output = V>,
+    T: Float,
+{
+    lerp(current, target, T::ONE - (-lambda * dt).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector2, Vector3};
+
+    #[test]
+    fn test_lerp() {
+        assert_eq!(lerp(1.0, 3.0, 0.5), 2.0);
+        let v = lerp(Vector2::new(0.0, 0.0), Vector2::new(2.0, -4.0), 0.25);
+        assert_eq!(v, Vector2::new(0.5, -1.0));
+    }
+
+    #[test]
+    fn test_damp_limits() {
+        assert_eq!(damp(1.0, 5.0, 10.0, 0.0), 1.0);
+        assert!((damp(1.0f64, 5.0, 10.0, 100.0) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_damp_is_frame_rate_independent() {
+        let (a, b) = (Vector3::new(0.0, 0.0, 0.0), Vector3::new(10.0, -2.0, 4.0));
+        let once = damp(a, b, 3.0f64, 0.1);
+        let twice = damp(damp(a, b, 3.0, 0.05), b, 3.0, 0.05);
+        let d = once - twice;
+        assert!(d.dot(&d) < 1e-20);
+    }
+}
@@ -1,7 +1,80 @@
+mod angle;
+pub mod animation;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+pub mod avoidance;
+pub mod ballistics;
+pub mod blue_noise;
+pub mod boids;
+mod cast;
+pub mod character;
+mod components;
+pub mod culling;
+pub mod distance;
+pub mod easing;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod field;
+pub mod flight;
+mod float;
+pub mod fluid;
+pub mod formation;
+pub mod geometry2d;
+pub mod geometry3d;
+pub mod gravity;
+pub mod grid;
+pub mod guidance;
+pub mod hash;
+pub mod heightmap;
+pub mod influence;
+pub mod integrate;
+pub mod interpolation;
+mod matrix4;
+pub mod mesh;
+pub mod noise;
+#[cfg(feature = "num-traits")]
+pub mod num;
+mod ops;
+pub mod orbit;
+mod ordered;
+pub mod packing;
+pub mod perception;
+pub mod polygon;
+pub mod polyline;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod regions;
+pub mod rope;
+pub mod rotation;
+pub mod sdf;
+pub mod sh;
+pub mod skyline;
+pub mod softbody;
+pub mod spatial_hash;
+pub mod sph;
+pub mod stats;
+pub mod steering;
+pub mod targeting;
+pub mod trajectory;
+pub mod trigger;
 mod vector2;
 mod vector3;
 mod vector4;
+pub mod vector_ops;
+pub mod vehicle;
+pub mod visibility;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+pub use angle::{Deg, Rad};
+pub use cast::{AsPrimitive, Rounding};
+pub use components::Components;
+pub use error::{VectorError, WrongLength};
+pub use float::Float;
+pub use matrix4::Matrix4;
+pub use ops::{InnerSpace, MetricSpace, VectorOps, VectorSpace};
+pub use ordered::OrderedVector;
 pub use vector2::Vector2;
 pub use vector3::Vector3;
-pub use vector4::Vector4;
\ No newline at end of file
+pub use vector4::Vector4;
@@ -0,0 +1,272 @@
+//! 4x4 matrices for projection and affine transforms.
+//!
+//! Matrices are column-major, as in OpenGL and GLSL: the fields are
+//! columns, their memory layout is the 16 column-major floats graphics APIs
+//! expect, and vectors are column vectors multiplied on the right, so
+//! `a * b * v` applies `b` first. Projections use the right-handed OpenGL
+//! convention with clip space depth in [-1, 1].
+
+use std::ops::Mul;
+
+use crate::distance::Plane;
+use crate::{Float, Vector3, Vector4};
+
+/// A column-major 4x4 matrix. Each field is one column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[repr(C)]
+pub struct Matrix4<T> {
+    pub x: Vector4<T>,
+    pub y: Vector4<T>,
+    pub z: Vector4<T>,
+    pub w: Vector4<T>,
+}
+
+impl<T> Matrix4<T> {
+    pub const fn from_cols(
+        x: Vector4<T>,
+        y: Vector4<T>,
+        z: Vector4<T>,
+        w: Vector4<T>,
+    ) -> Matrix4<T> {
+        Matrix4 { x, y, z, w }
+    }
+}
+
+impl<T: Float> Matrix4<T> {
+    pub fn identity() -> Matrix4<T> {
+        let (o, l) = (T::ZERO, T::ONE);
+        Matrix4::from_cols(
+            Vector4::new(l, o, o, o),
+            Vector4::new(o, l, o, o),
+            Vector4::new(o, o, l, o),
+            Vector4::new(o, o, o, l),
+        )
+    }
+
+    // Right-handed perspective projection mapping depth to [-1, 1] (OpenGL convention)
+    pub fn perspective(fovy: T, aspect: T, near: T, far: T) -> Matrix4<T> {
+        let o = T::ZERO;
+        let two = T::ONE + T::ONE;
+        let f = (fovy / two).tan().recip();
+        let depth = near - far;
+        Matrix4::from_cols(
+            Vector4::new(f / aspect, o, o, o),
+            Vector4::new(o, f, o, o),
+            Vector4::new(o, o, (far + near) / depth, -T::ONE),
+            Vector4::new(o, o, two * far * near / depth, o),
+        )
+    }
+
+    pub fn row(&self, index: usize) -> Vector4<T> {
+        let pick = |c: &Vector4<T>| match index {
+            0 => c.x,
+            1 => c.y,
+            2 => c.z,
+            3 => c.w,
+            _ => panic!("row index out of bounds: {}", index),
+        };
+        Vector4::new(pick(&self.x), pick(&self.y), pick(&self.z), pick(&self.w))
+    }
+
+    /// Modifies a perspective projection so that its near plane coincides with
+    /// `plane`, given in camera space as `(a, b, c, d)` with `ax + by + cz + d = 0`.
+    /// Points on the side the normal points to are kept, which is what mirrors
+    /// and portals need to clip geometry behind the reflecting surface.
+    ///
+    /// This is Eric Lengyel's oblique near-plane clipping technique. The far
+    /// plane becomes skewed, but depth precision is otherwise preserved.
+    pub fn oblique_near_plane(proj: Matrix4<T>, plane: Vector4<T>) -> Matrix4<T> {
+        let sgn = |v: T| {
+            if v > T::ZERO {
+                T::ONE
+            } else if v < T::ZERO {
+                -T::ONE
+            } else {
+                T::ZERO
+            }
+        };
+
+        // Corner of the view frustum opposite to the clip plane, in camera space
+        let q = Vector4::new(
+            (sgn(plane.x) + proj.z.x) / proj.x.x,
+            (sgn(plane.y) + proj.z.y) / proj.y.y,
+            -T::ONE,
+            (T::ONE + proj.z.z) / proj.w.z,
+        );
+
+        let two = T::ONE + T::ONE;
+        let c = plane * (two / plane.dot(&q));
+
+        // Replace the third row with the scaled plane minus the fourth row
+        let mut result = proj;
+        result.x.z = c.x - proj.x.w;
+        result.y.z = c.y - proj.y.w;
+        result.z.z = c.z - proj.z.w;
+        result.w.z = c.w - proj.w.w;
+        result
+    }
+
+    /// Transforms every point of `points` as `(x, y, z, 1)` into `out`, for
+    /// vertex buffers and skinning. The matrix is treated as affine: its
+    /// bottom row is ignored and no perspective divide happens.
+    ///
+    /// Panics if the slices differ in length.
+    pub fn transform_points(&self, points: &[Vector3<T>], out: &mut [Vector3<T>]) {
+        assert_eq!(points.len(), out.len(), "slices differ in length");
+        // Keep the used matrix entries in locals for the whole loop
+        let (x, y, z, w) = (self.x, self.y, self.z, self.w);
+        for (p, out) in points.iter().zip(out) {
+            *out = Vector3::new(
+                x.x * p.x + y.x * p.y + z.x * p.z + w.x,
+                x.y * p.x + y.y * p.y + z.y * p.z + w.y,
+                x.z * p.x + y.z * p.y + z.z * p.z + w.z,
+            );
+        }
+    }
+
+    /// Multiplies every vector of `vectors` by the matrix into `out`.
+    ///
+    /// Panics if the slices differ in length.
+    pub fn transform_vectors4(&self, vectors: &[Vector4<T>], out: &mut [Vector4<T>]) {
+        assert_eq!(vectors.len(), out.len(), "slices differ in length");
+        let m = *self;
+        for (v, out) in vectors.iter().zip(out) {
+            *out = m * *v;
+        }
+    }
+}
+
+impl Matrix4<f32> {
+    /// Affine transform mirroring points across `plane`, e.g. to render the
+    /// reflected scene of a mirror or water surface. It flips handedness, so
+    /// triangle winding must be reversed when drawing with it.
+    pub fn from_reflection(plane: &Plane) -> Matrix4<f32> {
+        let n = plane.normal;
+        // Householder matrix I - 2nn^T, then a shift of -2d along the normal
+        let col = |axis: Vector3<f32>| {
+            let c = axis - n * (2.0 * n.dot(&axis));
+            Vector4::new(c.x, c.y, c.z, 0.0)
+        };
+        let t = n * (-2.0 * plane.d);
+        Matrix4::from_cols(
+            col(Vector3::new(1.0, 0.0, 0.0)),
+            col(Vector3::new(0.0, 1.0, 0.0)),
+            col(Vector3::new(0.0, 0.0, 1.0)),
+            Vector4::new(t.x, t.y, t.z, 1.0),
+        )
+    }
+}
+
+impl<T: Float> Mul<Vector4<T>> for Matrix4<T> {
+    type Output = Vector4<T>;
+
+    fn mul(self, rhs: Vector4<T>) -> Self::Output {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+    }
+}
+
+impl<T: Float> Mul<Matrix4<T>> for Matrix4<T> {
+    type Output = Matrix4<T>;
+
+    fn mul(self, rhs: Matrix4<T>) -> Self::Output {
+        Matrix4::from_cols(self * rhs.x, self * rhs.y, self * rhs.z, self * rhs.w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Matrix4<f64>, b: Matrix4<f64>) {
+        for i in 0..4 {
+            let d = a.row(i) - b.row(i);
+            assert!(d.dot(&d) < 1e-18, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn assert_close_f32(a: Matrix4<f32>, b: Matrix4<f32>) {
+        for i in 0..4 {
+            let d = a.row(i) - b.row(i);
+            assert!(d.dot(&d) < 1e-10, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_matrix4_identity_mul() {
+        let m = Matrix4::<f64>::identity();
+        let v = Vector4::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(m * v, v);
+        assert_eq!(m * m, m);
+    }
+
+    #[test]
+    fn test_matrix4_row() {
+        let m = Matrix4::<f64>::perspective(1.0, 1.5, 0.1, 100.0);
+        assert_eq!(m.row(3), Vector4::new(0.0, 0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn test_oblique_near_plane_matches_regular_near_plane() {
+        let (near, far) = (0.5, 50.0);
+        let proj = Matrix4::<f64>::perspective(1.2, 1.0, near, far);
+        let plane = Vector4::new(0.0, 0.0, -1.0, -near);
+        assert_close(Matrix4::oblique_near_plane(proj, plane), proj);
+    }
+
+    #[test]
+    fn test_oblique_near_plane_clips_on_plane() {
+        let proj = Matrix4::<f64>::perspective(1.2, 1.0, 0.5, 50.0);
+        // Tilted plane through (0, 0, -5) facing away from the camera
+        let plane = Vector4::new(0.0, 0.6, -0.8, -4.0);
+        let oblique = Matrix4::oblique_near_plane(proj, plane);
+
+        // Points on the plane land exactly on the near clip plane (z_ndc = -1)
+        let p = oblique * Vector4::new(0.0, 0.0, -5.0, 1.0);
+        assert!((p.z / p.w + 1.0).abs() < 1e-9);
+        let p = oblique * Vector4::new(1.0, 0.8, -4.4, 1.0);
+        assert!((p.z / p.w + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_from_reflection() {
+        let plane =
+            Plane::from_point_normal(Vector3::new(1.0, 2.0, 0.5), Vector3::new(1.0, -2.0, 2.0));
+        let m = Matrix4::from_reflection(&plane);
+        let points = [Vector3::new(0.5, -3.0, 2.0), Vector3::new(4.0, 0.0, -1.0)];
+        let mut out = [Vector3::default(); 2];
+        m.transform_points(&points, &mut out);
+        for (p, r) in points.iter().zip(out) {
+            let d = r - p.reflect_across(&plane);
+            assert!(d.dot(&d) < 1e-10);
+        }
+        assert_close_f32(m * m, Matrix4::identity());
+    }
+
+    #[test]
+    fn test_transform_points() {
+        let mut m = Matrix4::<f32>::identity();
+        m.x.x = 2.0;
+        m.w = Vector4::new(1.0, -1.0, 3.0, 1.0);
+        let points = [Vector3::new(1.0, 2.0, 3.0), Vector3::new(-1.0, 0.0, 0.0)];
+        let mut out = [Vector3::default(); 2];
+        m.transform_points(&points, &mut out);
+        assert_eq!(
+            out,
+            [Vector3::new(3.0, 1.0, 6.0), Vector3::new(-1.0, -1.0, 3.0)]
+        );
+
+        let vectors = [
+            Vector4::new(1.0, 2.0, 3.0, 1.0),
+            Vector4::new(1.0, 0.0, 0.0, 0.0),
+        ];
+        let mut out = [Vector4::default(); 2];
+        m.transform_vectors4(&vectors, &mut out);
+        assert_eq!(
+            out,
+            [
+                Vector4::new(3.0, 1.0, 6.0, 1.0),
+                Vector4::new(2.0, 0.0, 0.0, 0.0)
+            ]
+        );
+    }
+}
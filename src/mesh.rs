@@ -0,0 +1,237 @@
+//! Helpers for processing triangle meshes.
+
+use std::collections::HashMap;
+
+use crate::spatial_hash::SpatialHash;
+use crate::{OrderedVector, Vector2, Vector3};
+
+/// Orthonormal tangent space basis, the columns of the TBN matrix used for
+/// normal mapping.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TangentFrame {
+    pub tangent: Vector3<f32>,
+    pub bitangent: Vector3<f32>,
+    pub normal: Vector3<f32>,
+}
+
+/// Unnormalized tangent and bitangent of a triangle, the directions in which
+/// the texture coordinates u and v increase. Returns `None` if the UVs are
+/// degenerate: an edge of zero length or two edges parallel to within float
+/// precision, however small the triangle is in texture space.
+pub fn triangle_tangents(
+    positions: [Vector3<f32>; 3],
+    uvs: [Vector2<f32>; 3],
+) -> Option<(Vector3<f32>, Vector3<f32>)> {
+    let e1 = positions[1] - positions[0];
+    let e2 = positions[2] - positions[0];
+    let d1 = uvs[1] - uvs[0];
+    let d2 = uvs[2] - uvs[0];
+
+    // |d1| |d2| sin(angle), so compare against the edge lengths
+    let det = d1.x * d2.y - d2.x * d1.y;
+    if det.abs() <= f32::EPSILON * d1.magnitude() * d2.magnitude() {
+        return None;
+    }
+    let r = det.recip();
+    let tangent = (e1 * d2.y - e2 * d1.y) * r;
+    let bitangent = (e2 * d1.x - e1 * d2.x) * r;
+    Some((tangent, bitangent))
+}
+
+/// Tangent frame of a triangle for the given (unit) vertex or face normal.
+/// The tangent is made perpendicular to the normal with Gram-Schmidt and the
+/// bitangent is rebuilt from the cross product, flipped if the UV mapping is
+/// mirrored.
+pub fn tangent_frame(
+    positions: [Vector3<f32>; 3],
+    uvs: [Vector2<f32>; 3],
+    normal: Vector3<f32>,
+) -> Option<TangentFrame> {
+    let (tangent, bitangent) = triangle_tangents(positions, uvs)?;
+    let tangent = (tangent - normal * normal.dot(&tangent)).normalize();
+    let handedness = if normal.cross(&tangent).dot(&bitangent) < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    Some(TangentFrame {
+        tangent,
+        bitangent: normal.cross(&tangent) * handedness,
+        normal,
+    })
+}
+
+/// Smooth vertex normals of a triangle mesh, with each face contributing to
+/// its corners in proportion to its area. Triangles are counter-clockwise
+/// seen from the front. Vertices not used by any non-degenerate triangle get
+/// the zero vector.
+pub fn accumulate_normals(
+    positions: &[Vector3<f32>],
+    triangles: &[[usize; 3]],
+) -> Vec<Vector3<f32>> {
+    let mut normals = vec![Vector3::default(); positions.len()];
+    for &[a, b, c] in triangles {
+        // Twice the face area in length
+        let n = (positions[b] - positions[a]).cross(&(positions[c] - positions[a]));
+        for i in [a, b, c] {
+            normals[i] += n;
+        }
+    }
+    for n in &mut normals {
+        *n = n.try_normalize().unwrap_or_default();
+    }
+    normals
+}
+
+/// Welds points closer than `epsilon` together, e.g. the duplicated corners
+/// of an imported mesh. Returns the kept points and, for every input point,
+/// the index of the kept point it was merged into. Points are bucketed in a
+/// `SpatialHash` with cells of size `epsilon` and each one is merged into the
+/// earliest kept point within range, so chains of close points are not merged
+/// transitively. An `epsilon` of zero merges only identical points. Points
+/// with non-finite coordinates are never merged, except with `epsilon` zero.
+pub fn dedup_points(points: &[Vector3<f32>], epsilon: f32) -> (Vec<Vector3<f32>>, Vec<u32>) {
+    let mut unique = Vec::new();
+    let mut remap = Vec::with_capacity(points.len());
+    if epsilon <= 0.0 {
+        let mut seen = HashMap::new();
+        for p in points {
+            let index = *seen.entry(OrderedVector(*p)).or_insert_with(|| {
+                unique.push(*p);
+                unique.len() as u32 - 1
+            });
+            remap.push(index);
+        }
+        return (unique, remap);
+    }
+
+    let eps_sq = epsilon * epsilon;
+    let mut grid: SpatialHash<u32> = SpatialHash::new(epsilon);
+    for p in points {
+        let found = grid
+            .around(*p)
+            .copied()
+            .filter(|&i| unique[i as usize].distance_squared(p) <= eps_sq)
+            .min();
+        let index = found.unwrap_or_else(|| {
+            unique.push(*p);
+            let index = unique.len() as u32 - 1;
+            grid.insert(*p, index);
+            index
+        });
+        remap.push(index);
+    }
+    (unique, remap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSITIONS: [Vector3<f32>; 3] = [
+        Vector3::new(0.0, 0.0, 0.0),
+        Vector3::new(2.0, 0.0, 0.0),
+        Vector3::new(0.0, 2.0, 0.0),
+    ];
+
+    #[test]
+    fn test_triangle_tangents() {
+        let uvs = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, 1.0),
+        ];
+        let (t, b) = triangle_tangents(POSITIONS, uvs).unwrap();
+        assert_eq!(t, Vector3::new(2.0, 0.0, 0.0));
+        assert_eq!(b, Vector3::new(0.0, 2.0, 0.0));
+
+        let degenerate = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(2.0, 2.0),
+        ];
+        assert_eq!(triangle_tangents(POSITIONS, degenerate), None);
+
+        // A texel sized triangle in a large atlas is still valid
+        let tiny = uvs.map(|uv| uv * 1e-4);
+        let (t, _) = triangle_tangents(POSITIONS, tiny).unwrap();
+        assert!((t - Vector3::new(2e4, 0.0, 0.0)).magnitude() < 1.0);
+        let tiny_degenerate = degenerate.map(|uv| uv * 1e-4);
+        assert_eq!(triangle_tangents(POSITIONS, tiny_degenerate), None);
+    }
+
+    #[test]
+    fn test_tangent_frame() {
+        let uvs = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(0.0, 1.0),
+            Vector2::new(1.0, 0.0),
+        ];
+        let normal = Vector3::new(0.0, 0.0, 1.0);
+        let frame = tangent_frame(POSITIONS, uvs, normal).unwrap();
+        assert_eq!(frame.tangent, Vector3::new(0.0, 1.0, 0.0));
+        // Mirrored UVs flip the bitangent to keep it pointing along +v
+        assert_eq!(frame.bitangent, Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(frame.normal, normal);
+    }
+
+    #[test]
+    fn test_accumulate_normals() {
+        // Two perpendicular faces sharing an edge, the larger one dominating
+        let positions = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.0),
+            Vector3::new(0.0, 2.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(5.0, 5.0, 5.0),
+        ];
+        let normals = accumulate_normals(&positions, &[[0, 1, 2], [0, 3, 1]]);
+        assert_eq!(normals[2], Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(normals[3], Vector3::new(0.0, 1.0, 0.0));
+        let expected = Vector3::new(0.0f32, 1.0, 2.0).normalize();
+        assert!((normals[0] - expected).magnitude() < 1e-6);
+        assert_eq!(normals[4], Vector3::default());
+    }
+
+    #[test]
+    fn test_dedup_points() {
+        let points = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0005, -0.0005, 0.0),
+            Vector3::new(1.0, 0.0, 0.0009),
+            Vector3::new(0.0, 1.0, 0.0),
+        ];
+        let (unique, remap) = dedup_points(&points, 1e-3);
+        assert_eq!(unique, [points[0], points[1], points[4]]);
+        assert_eq!(remap, [0, 1, 0, 1, 2]);
+
+        let (unique, remap) = dedup_points(&points, 0.0);
+        assert_eq!(unique.len(), 5);
+        assert_eq!(remap, [0, 1, 2, 3, 4]);
+        let (unique, remap) = dedup_points(&[points[1], points[1]], 0.0);
+        assert_eq!((unique.len(), remap), (1, vec![0, 0]));
+    }
+
+    #[test]
+    fn test_dedup_points_earliest_match() {
+        // Both kept points are in range of the last one, in different cells
+        let points = [
+            Vector3::new(0.0015, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0008, 0.0, 0.0),
+        ];
+        let (unique, remap) = dedup_points(&points, 1e-3);
+        assert_eq!(unique, [points[0], points[1]]);
+        assert_eq!(remap, [0, 1, 0]);
+    }
+
+    #[test]
+    fn test_dedup_points_out_of_range_cells() {
+        let far = Vector3::new(1e4, -1e4, 0.0);
+        let inf = Vector3::new(f32::INFINITY, f32::NEG_INFINITY, 0.0);
+        let (unique, remap) = dedup_points(&[far, far, inf, inf], 1e-6);
+        assert_eq!(unique, [far, inf, inf]);
+        assert_eq!(remap, [0, 0, 1, 2]);
+    }
+}
@@ -0,0 +1,736 @@
+//! Procedural noise: lattice value noise, Perlin and simplex gradient noise,
+//! Worley cellular noise and curl noise, plus `Fractal` octave sums and
+//! domain warping built on any of them.
+//!
+//! Every function is a pure function of its input point, hashed from the
+//! integer lattice with `hash::hash_u32`, so results repeat across runs and
+//! platforms. There is no seed parameter: offset the input point to get an
+//! independent pattern. Scalar noise lies in [-1, 1] with a feature size of
+//! one unit; scale the point to change the frequency. Worley noise returns
+//! distances, and curl noise a velocity, instead.
+
+use std::ops::Mul;
+
+use crate::field::{directional_derivative, gradient2};
+use crate::hash::hash_u32;
+use crate::{Vector2, Vector3};
+
+// Integer lattice hash mapped to [0, 1)
+fn unit(x: i32, y: i32, z: i32, seed: u32) -> f32 {
+    (hash_u32(x, y, z, seed) >> 8) as f32 / (1u32 << 24) as f32
+}
+
+// Integer lattice hash mapped to [-1, 1]
+fn lattice(x: i32, y: i32, z: i32) -> f32 {
+    (hash_u32(x, y, z, 0) as f32 / u32::MAX as f32) * 2.0 - 1.0
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Smoothly interpolated lattice value noise in [-1, 1].
+pub fn value2(p: Vector2<f32>) -> f32 {
+    let (x0, y0) = (p.x.floor(), p.y.floor());
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    // Far out coordinates saturate, so the lattice wraps instead of overflowing
+    let (jx, jy) = (ix.wrapping_add(1), iy.wrapping_add(1));
+    let (tx, ty) = (fade(p.x - x0), fade(p.y - y0));
+
+    let a = lerp(lattice(ix, iy, 0), lattice(jx, iy, 0), tx);
+    let b = lerp(lattice(ix, jy, 0), lattice(jx, jy, 0), tx);
+    lerp(a, b, ty)
+}
+
+/// Smoothly interpolated lattice value noise in [-1, 1].
+pub fn value3(p: Vector3<f32>) -> f32 {
+    let (x0, y0, z0) = (p.x.floor(), p.y.floor(), p.z.floor());
+    let (ix, iy, iz) = (x0 as i32, y0 as i32, z0 as i32);
+    let (jx, jy) = (ix.wrapping_add(1), iy.wrapping_add(1));
+    let (tx, ty, tz) = (fade(p.x - x0), fade(p.y - y0), fade(p.z - z0));
+
+    let plane = |iz: i32| {
+        let a = lerp(lattice(ix, iy, iz), lattice(jx, iy, iz), tx);
+        let b = lerp(lattice(ix, jy, iz), lattice(jx, jy, iz), tx);
+        lerp(a, b, ty)
+    };
+    lerp(plane(iz), plane(iz.wrapping_add(1)), tz)
+}
+
+// Perlin's quintic interpolant, with zero first and second derivatives at
+// the lattice points
+fn quintic(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+// Eight evenly spaced unit gradients for 2D gradient noise
+const GRADIENTS2: [Vector2<f32>; 8] = [
+    Vector2::new(1.0, 0.0),
+    Vector2::new(-1.0, 0.0),
+    Vector2::new(0.0, 1.0),
+    Vector2::new(0.0, -1.0),
+    Vector2::new(
+        std::f32::consts::FRAC_1_SQRT_2,
+        std::f32::consts::FRAC_1_SQRT_2,
+    ),
+    Vector2::new(
+        -std::f32::consts::FRAC_1_SQRT_2,
+        std::f32::consts::FRAC_1_SQRT_2,
+    ),
+    Vector2::new(
+        std::f32::consts::FRAC_1_SQRT_2,
+        -std::f32::consts::FRAC_1_SQRT_2,
+    ),
+    Vector2::new(
+        -std::f32::consts::FRAC_1_SQRT_2,
+        -std::f32::consts::FRAC_1_SQRT_2,
+    ),
+];
+
+// Dot product of the lattice gradient at (ix, iy) with the offset (dx, dy)
+fn grad2(ix: i32, iy: i32, dx: f32, dy: f32) -> f32 {
+    let g = GRADIENTS2[(hash_u32(ix, iy, 0, 4) & 7) as usize];
+    g.x * dx + g.y * dy
+}
+
+// Dot product with one of the 12 cube edge gradients of improved Perlin noise
+fn grad3(ix: i32, iy: i32, iz: i32, dx: f32, dy: f32, dz: f32) -> f32 {
+    match hash_u32(ix, iy, iz, 4) % 12 {
+        0 => dx + dy,
+        1 => -dx + dy,
+        2 => dx - dy,
+        3 => -dx - dy,
+        4 => dx + dz,
+        5 => -dx + dz,
+        6 => dx - dz,
+        7 => -dx - dz,
+        8 => dy + dz,
+        9 => -dy + dz,
+        10 => dy - dz,
+        _ => -dy - dz,
+    }
+}
+
+/// Perlin gradient noise in [-1, 1], zero at every lattice point. Smoother
+/// and less blocky than value noise.
+pub fn perlin2(p: Vector2<f32>) -> f32 {
+    let (x0, y0) = (p.x.floor(), p.y.floor());
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let (jx, jy) = (ix.wrapping_add(1), iy.wrapping_add(1));
+    let (fx, fy) = (p.x - x0, p.y - y0);
+    let (tx, ty) = (quintic(fx), quintic(fy));
+
+    let a = lerp(grad2(ix, iy, fx, fy), grad2(jx, iy, fx - 1.0, fy), tx);
+    let b = lerp(
+        grad2(ix, jy, fx, fy - 1.0),
+        grad2(jx, jy, fx - 1.0, fy - 1.0),
+        tx,
+    );
+    // Unit gradients reach at most sqrt(1/2)
+    (lerp(a, b, ty) * std::f32::consts::SQRT_2).clamp(-1.0, 1.0)
+}
+
+/// Perlin gradient noise in [-1, 1], zero at every lattice point.
+pub fn perlin3(p: Vector3<f32>) -> f32 {
+    let (x0, y0, z0) = (p.x.floor(), p.y.floor(), p.z.floor());
+    let (ix, iy, iz) = (x0 as i32, y0 as i32, z0 as i32);
+    let (jx, jy) = (ix.wrapping_add(1), iy.wrapping_add(1));
+    let (fx, fy, fz) = (p.x - x0, p.y - y0, p.z - z0);
+    let (tx, ty, tz) = (quintic(fx), quintic(fy), quintic(fz));
+
+    let plane = |k: i32| {
+        let (iz, fz) = (iz.wrapping_add(k), fz - k as f32);
+        let a = lerp(
+            grad3(ix, iy, iz, fx, fy, fz),
+            grad3(jx, iy, iz, fx - 1.0, fy, fz),
+            tx,
+        );
+        let b = lerp(
+            grad3(ix, jy, iz, fx, fy - 1.0, fz),
+            grad3(jx, jy, iz, fx - 1.0, fy - 1.0, fz),
+            tx,
+        );
+        lerp(a, b, ty)
+    };
+    lerp(plane(0), plane(1), tz).clamp(-1.0, 1.0)
+}
+
+/// Simplex noise in [-1, 1] (Perlin 2001, after Gustavson's reference
+/// implementation). Sums three corner contributions per sample instead of
+/// four and has no axis-aligned artifacts.
+pub fn simplex2(p: Vector2<f32>) -> f32 {
+    const F2: f32 = 0.366_025_42; // (sqrt(3) - 1) / 2
+    const G2: f32 = 0.211_324_87; // (3 - sqrt(3)) / 6
+
+    // Skew to the lattice of squares split into two triangles each
+    let s = (p.x + p.y) * F2;
+    let (i, j) = ((p.x + s).floor(), (p.y + s).floor());
+    let t = (i + j) * G2;
+    let (x0, y0) = (p.x - (i - t), p.y - (j - t));
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+    let (ii, jj) = (i as i32, j as i32);
+
+    let corner = |di: i32, dj: i32, x: f32, y: f32| {
+        let falloff = 0.5 - x * x - y * y;
+        if falloff <= 0.0 {
+            return 0.0;
+        }
+        let f2 = falloff * falloff;
+        f2 * f2 * grad2(ii.wrapping_add(di), jj.wrapping_add(dj), x, y)
+    };
+    let n = corner(0, 0, x0, y0)
+        + corner(i1, j1, x0 - i1 as f32 + G2, y0 - j1 as f32 + G2)
+        + corner(1, 1, x0 - 1.0 + 2.0 * G2, y0 - 1.0 + 2.0 * G2);
+    (n * 99.2).clamp(-1.0, 1.0)
+}
+
+/// Simplex noise in [-1, 1] over a lattice of tetrahedra, summing four
+/// corner contributions per sample where `perlin3` interpolates eight.
+pub fn simplex3(p: Vector3<f32>) -> f32 {
+    const F3: f32 = 1.0 / 3.0;
+    const G3: f32 = 1.0 / 6.0;
+
+    // Skew to the lattice of cubes split into six tetrahedra each
+    let s = (p.x + p.y + p.z) * F3;
+    let (i, j, k) = ((p.x + s).floor(), (p.y + s).floor(), (p.z + s).floor());
+    let t = (i + j + k) * G3;
+    let (x0, y0, z0) = (p.x - (i - t), p.y - (j - t), p.z - (k - t));
+    // Second and third corners of the tetrahedron holding the sample
+    let (c1, c2) = if x0 >= y0 {
+        if y0 >= z0 {
+            ((1, 0, 0), (1, 1, 0))
+        } else if x0 >= z0 {
+            ((1, 0, 0), (1, 0, 1))
+        } else {
+            ((0, 0, 1), (1, 0, 1))
+        }
+    } else if y0 < z0 {
+        ((0, 0, 1), (0, 1, 1))
+    } else if x0 < z0 {
+        ((0, 1, 0), (0, 1, 1))
+    } else {
+        ((0, 1, 0), (1, 1, 0))
+    };
+    let (ii, jj, kk) = (i as i32, j as i32, k as i32);
+
+    let corner = |(di, dj, dk): (i32, i32, i32), g: f32| {
+        let (x, y, z) = (x0 - di as f32 + g, y0 - dj as f32 + g, z0 - dk as f32 + g);
+        let falloff = 0.6 - x * x - y * y - z * z;
+        if falloff <= 0.0 {
+            return 0.0;
+        }
+        let (ci, cj, ck) = (
+            ii.wrapping_add(di),
+            jj.wrapping_add(dj),
+            kk.wrapping_add(dk),
+        );
+        let f2 = falloff * falloff;
+        f2 * f2 * grad3(ci, cj, ck, x, y, z)
+    };
+    let n = corner((0, 0, 0), 0.0)
+        + corner(c1, G3)
+        + corner(c2, 2.0 * G3)
+        + corner((1, 1, 1), 3.0 * G3);
+    (n * 32.0).clamp(-1.0, 1.0)
+}
+
+/// Octave settings for summing several layers of a base noise function.
+///
+/// Each octave samples the noise at `lacunarity` times the previous frequency
+/// and weights it by `gain` times the previous amplitude.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fractal {
+    pub octaves: u32,
+    pub lacunarity: f32,
+    pub gain: f32,
+}
+
+impl Default for Fractal {
+    fn default() -> Self {
+        Fractal {
+            octaves: 6,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+impl Fractal {
+    pub const fn new(octaves: u32, lacunarity: f32, gain: f32) -> Fractal {
+        Fractal {
+            octaves,
+            lacunarity,
+            gain,
+        }
+    }
+
+    // Sums `layer(octave_value, weight_of_previous)` over all octaves and
+    // normalizes by the total amplitude
+    fn accumulate<P, F, L>(&self, p: P, noise: F, mut layer: L) -> f32
+    where
+        P: Mul<f32, Output = P> + Copy,
+        F: Fn(P) -> f32,
+        L: FnMut(f32) -> f32,
+    {
+        let mut sum = 0.0;
+        let mut norm = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        for _ in 0..self.octaves {
+            sum += layer(noise(p * frequency)) * amplitude;
+            norm += amplitude;
+            amplitude *= self.gain;
+            frequency *= self.lacunarity;
+        }
+        if norm > 0.0 {
+            sum / norm
+        } else {
+            0.0
+        }
+    }
+
+    /// Fractional Brownian motion. Keeps the range of `noise`, typically [-1, 1].
+    pub fn fbm<P, F>(&self, p: P, noise: F) -> f32
+    where
+        P: Mul<f32, Output = P> + Copy,
+        F: Fn(P) -> f32,
+    {
+        self.accumulate(p, noise, |n| n)
+    }
+
+    /// Sum of absolute noise values, in [0, 1] for noise in [-1, 1].
+    pub fn turbulence<P, F>(&self, p: P, noise: F) -> f32
+    where
+        P: Mul<f32, Output = P> + Copy,
+        F: Fn(P) -> f32,
+    {
+        self.accumulate(p, noise, |n| n.abs())
+    }
+
+    /// Ridged multifractal in [0, 1] for noise in [-1, 1]. Sharp crests form
+    /// where the noise crosses zero and each octave is weighted by the one
+    /// before it, so detail concentrates on the ridges.
+    pub fn ridged<P, F>(&self, p: P, noise: F) -> f32
+    where
+        P: Mul<f32, Output = P> + Copy,
+        F: Fn(P) -> f32,
+    {
+        let mut weight = 1.0;
+        self.accumulate(p, noise, |n| {
+            let signal = (1.0 - n.abs()).powi(2) * weight;
+            weight = (signal * 2.0).clamp(0.0, 1.0);
+            signal
+        })
+    }
+}
+
+// Offsets decorrelating the noise samples used for each warp component
+const WARP_OFFSETS: [Vector3<f32>; 3] = [
+    Vector3::new(0.0, 0.0, 0.0),
+    Vector3::new(5.2, 1.3, 7.7),
+    Vector3::new(1.7, 9.2, 3.4),
+];
+
+/// Displaces `p` by a vector built from samples of `noise`, scaled by `strength`.
+/// Sampling a noise function at the returned point instead of `p` produces
+/// swirling, organic distortions of its pattern.
+pub fn warp2<F>(p: Vector2<f32>, noise: F, strength: f32) -> Vector2<f32>
+where
+    F: Fn(Vector2<f32>) -> f32,
+{
+    let offset = |i: usize| Vector2::new(WARP_OFFSETS[i].x, WARP_OFFSETS[i].y);
+    let q = Vector2::new(noise(p + offset(0)), noise(p + offset(1)));
+    p + q * strength
+}
+
+/// Displaces `p` by a vector built from samples of `noise`, scaled by `strength`.
+pub fn warp3<F>(p: Vector3<f32>, noise: F, strength: f32) -> Vector3<f32>
+where
+    F: Fn(Vector3<f32>) -> f32,
+{
+    let q = Vector3::new(
+        noise(p + WARP_OFFSETS[0]),
+        noise(p + WARP_OFFSETS[1]),
+        noise(p + WARP_OFFSETS[2]),
+    );
+    p + q * strength
+}
+
+/// Two levels of domain warping, the classic preset for terrain and marbled
+/// textures. Usually fed with an fBm stack. Computes `noise(r)` with
+/// `q = p + s * w(p)` and `r = q + s * w(q)`, where `s` is `strength` and
+/// `w(x)` is `(noise(x), noise(x + (5.2, 1.3)))` as in `warp2`.
+pub fn double_warp2<F>(p: Vector2<f32>, noise: F, strength: f32) -> f32
+where
+    F: Fn(Vector2<f32>) -> f32,
+{
+    let q = warp2(p, &noise, strength);
+    let r = warp2(q, &noise, strength);
+    noise(r)
+}
+
+/// Two levels of domain warping, `noise(r)` with `q = p + s * w(p)` and
+/// `r = q + s * w(q)`, where `s` is `strength` and `w` is the displacement
+/// of `warp3`, one `noise` sample per axis at decorrelating offsets.
+pub fn double_warp3<F>(p: Vector3<f32>, noise: F, strength: f32) -> f32
+where
+    F: Fn(Vector3<f32>) -> f32,
+{
+    let q = warp3(p, &noise, strength);
+    let r = warp3(q, &noise, strength);
+    noise(r)
+}
+
+// Step for the central differences of curl noise, small against the
+// lattice spacing but above f32 resolution for coordinates up to about 1e3
+const CURL_STEP: f32 = 1e-3;
+
+/// Divergence-free velocity field, the curl of a vector potential of three
+/// decorrelated `perlin3` samples (Bridson et al. 2007). Particles advected
+/// through it swirl like smoke without bunching up or thinning out.
+///
+/// The six partial derivatives take two samples each. The finite difference
+/// step is fixed, so the field degrades into noise of its own as coordinates
+/// approach 1e4; keep positions within a few thousand units of the origin.
+pub fn curl_noise(p: Vector3<f32>) -> Vector3<f32> {
+    let diff = |axis: usize, dir: Vector3<f32>| {
+        directional_derivative(|q| perlin3(q + WARP_OFFSETS[axis]), p, dir, CURL_STEP)
+    };
+    let (dx, dy, dz) = (
+        Vector3::new(1.0, 0.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0),
+        Vector3::new(0.0, 0.0, 1.0),
+    );
+    Vector3::new(
+        diff(2, dy) - diff(1, dz),
+        diff(0, dz) - diff(2, dx),
+        diff(1, dx) - diff(0, dy),
+    )
+}
+
+/// Divergence-free velocity field in the plane, the `perlin2` gradient
+/// rotated by 90 degrees, so particles follow its contour lines. Like
+/// `curl_noise`, it is accurate within a few thousand units of the origin.
+pub fn curl_noise2(p: Vector2<f32>) -> Vector2<f32> {
+    let g = gradient2(perlin2, p, CURL_STEP);
+    Vector2::new(g.y, -g.x)
+}
+
+/// Result of a cellular noise query: the distances to the closest (`f1`) and
+/// second closest (`f2`) feature points, and the closest feature point itself.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cellular<P> {
+    pub f1: f32,
+    pub f2: f32,
+    pub point: P,
+}
+
+impl<P> Cellular<P> {
+    fn insert(&mut self, dist: f32, point: P) {
+        if dist < self.f1 {
+            self.f2 = self.f1;
+            self.f1 = dist;
+            self.point = point;
+        } else if dist < self.f2 {
+            self.f2 = dist;
+        }
+    }
+}
+
+// Cell offsets per axis, nearest first so that the closest feature points
+// are usually found early and farther cells can be skipped
+const WORLEY_OFFSETS: [i32; 5] = [0, -1, 1, -2, 2];
+
+// Distance along one axis from `frac` in [0, 1) to the cell `offset` away
+fn cell_gap(frac: f32, offset: i32) -> f32 {
+    match offset.signum() {
+        1 => offset as f32 - frac,
+        -1 => frac - (offset + 1) as f32,
+        _ => 0.0,
+    }
+}
+
+/// Worley (cellular) noise with one jittered feature point per unit cell.
+/// `f2 - f1` is zero along cell borders, which gives crack and cell patterns.
+///
+/// `f1` and `f2` are exact: both closest feature points always lie within
+/// two cells of the one holding `p`, and all of those are searched.
+pub fn worley2(p: Vector2<f32>) -> Cellular<Vector2<f32>> {
+    let (cx, cy) = (p.x.floor() as i32, p.y.floor() as i32);
+    let (fx, fy) = (p.x - p.x.floor(), p.y - p.y.floor());
+    let mut result = Cellular {
+        f1: f32::INFINITY,
+        f2: f32::INFINITY,
+        point: p,
+    };
+    for dj in WORLEY_OFFSETS {
+        let gy = cell_gap(fy, dj);
+        for di in WORLEY_OFFSETS {
+            let gx = cell_gap(fx, di);
+            if gx * gx + gy * gy >= result.f2 * result.f2 {
+                continue;
+            }
+            let (i, j) = (cx.wrapping_add(di), cy.wrapping_add(dj));
+            let feature = Vector2::new(i as f32 + unit(i, j, 0, 1), j as f32 + unit(i, j, 0, 2));
+            let d = feature - p;
+            result.insert((d.x * d.x + d.y * d.y).sqrt(), feature);
+        }
+    }
+    result
+}
+
+/// Worley (cellular) noise with one jittered feature point per unit cell.
+/// Like `worley2`, `f1` and `f2` are exact.
+pub fn worley3(p: Vector3<f32>) -> Cellular<Vector3<f32>> {
+    let (cx, cy, cz) = (p.x.floor() as i32, p.y.floor() as i32, p.z.floor() as i32);
+    let (fx, fy, fz) = (p.x - p.x.floor(), p.y - p.y.floor(), p.z - p.z.floor());
+    let mut result = Cellular {
+        f1: f32::INFINITY,
+        f2: f32::INFINITY,
+        point: p,
+    };
+    for dk in WORLEY_OFFSETS {
+        let gz = cell_gap(fz, dk);
+        for dj in WORLEY_OFFSETS {
+            let gy = cell_gap(fy, dj);
+            for di in WORLEY_OFFSETS {
+                let gx = cell_gap(fx, di);
+                if gx * gx + gy * gy + gz * gz >= result.f2 * result.f2 {
+                    continue;
+                }
+                let (i, j, k) = (
+                    cx.wrapping_add(di),
+                    cy.wrapping_add(dj),
+                    cz.wrapping_add(dk),
+                );
+                let feature = Vector3::new(
+                    i as f32 + unit(i, j, k, 1),
+                    j as f32 + unit(i, j, k, 2),
+                    k as f32 + unit(i, j, k, 3),
+                );
+                let d = feature - p;
+                result.insert(d.dot(&d).sqrt(), feature);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_noise_range_and_determinism() {
+        for i in 0..200 {
+            let t = i as f32 * 0.37;
+            let p2 = Vector2::new(t, -t * 0.5);
+            let p3 = Vector3::new(t, t * 0.25, -t);
+            assert!((-1.0..=1.0).contains(&value2(p2)));
+            assert!((-1.0..=1.0).contains(&value3(p3)));
+            assert_eq!(value2(p2), value2(p2));
+            assert_eq!(value3(p3), value3(p3));
+        }
+    }
+
+    #[test]
+    fn test_value_noise_lattice_points() {
+        assert_eq!(value2(Vector2::new(3.0, -2.0)), lattice(3, -2, 0));
+        assert_eq!(value3(Vector3::new(1.0, 2.0, 3.0)), lattice(1, 2, 3));
+    }
+
+    #[test]
+    fn test_gradient_noise() {
+        assert_eq!(perlin2(Vector2::new(3.0, -2.0)), 0.0);
+        assert_eq!(perlin3(Vector3::new(1.0, 2.0, -3.0)), 0.0);
+        let (mut lo, mut hi) = (0.0f32, 0.0f32);
+        for i in 0..2000 {
+            let t = i as f32 * 0.173;
+            let p2 = Vector2::new(t, t * 0.61 - 40.0);
+            let p3 = Vector3::new(t, -t * 0.37, t * 0.11);
+            for n in [perlin2(p2), perlin3(p3), simplex2(p2), simplex3(p3)] {
+                assert!((-1.0..=1.0).contains(&n));
+                lo = lo.min(n);
+                hi = hi.max(n);
+            }
+            assert_eq!(simplex2(p2), simplex2(p2));
+            assert_eq!(simplex3(p3), simplex3(p3));
+        }
+        // Spans most of the range
+        assert!(lo < -0.5 && hi > 0.5);
+        let fractal = Fractal::default();
+        assert!((-1.0..=1.0).contains(&fractal.fbm(Vector2::new(0.3, 0.4), perlin2)));
+    }
+
+    #[test]
+    fn test_noise_far_from_origin() {
+        for x in [3e9, -3e9, f32::MAX, f32::INFINITY] {
+            let p2 = Vector2::new(x, 0.5);
+            let p3 = Vector3::new(x, 0.5, -x);
+            for n in [value2(p2), value3(p3), perlin2(p2), perlin3(p3)] {
+                assert!((-1.0..=1.0).contains(&n) || !x.is_finite());
+            }
+            simplex2(p2);
+            simplex3(p3);
+            worley2(p2);
+            worley3(p3);
+        }
+    }
+
+    #[test]
+    fn test_curl_noise_is_divergence_free() {
+        let h = 0.01;
+        let (ex, ey, ez) = (
+            Vector3::new(h, 0.0, 0.0),
+            Vector3::new(0.0, h, 0.0),
+            Vector3::new(0.0, 0.0, h),
+        );
+        for i in 0..20 {
+            let t = i as f32 * 0.37 + 0.1;
+            let p = Vector3::new(t, 1.3 - t * 0.5, t * 0.2);
+            let div = (curl_noise(p + ex).x - curl_noise(p - ex).x + curl_noise(p + ey).y
+                - curl_noise(p - ey).y
+                + curl_noise(p + ez).z
+                - curl_noise(p - ez).z)
+                / (2.0 * h);
+            assert!(div.abs() < 0.1, "divergence {div} at {p:?}");
+
+            let q = Vector2::new(p.x, p.y);
+            let (qx, qy) = (Vector2::new(h, 0.0), Vector2::new(0.0, h));
+            let div = (curl_noise2(q + qx).x - curl_noise2(q - qx).x + curl_noise2(q + qy).y
+                - curl_noise2(q - qy).y)
+                / (2.0 * h);
+            assert!(div.abs() < 0.1, "divergence {div} at {q:?}");
+        }
+        assert!(curl_noise(Vector3::new(0.4, 0.2, 0.7)).magnitude() > 0.0);
+    }
+
+    #[test]
+    fn test_fbm_single_octave_is_base_noise() {
+        let p = Vector2::new(1.3, 4.7);
+        let fractal = Fractal::new(1, 2.0, 0.5);
+        assert_eq!(fractal.fbm(p, value2), value2(p));
+    }
+
+    #[test]
+    fn test_fractal_ranges() {
+        let fractal = Fractal::default();
+        for i in 0..100 {
+            let p = Vector3::new(i as f32 * 0.13, i as f32 * 0.71, 0.5);
+            assert!((-1.0..=1.0).contains(&fractal.fbm(p, value3)));
+            assert!((0.0..=1.0).contains(&fractal.turbulence(p, value3)));
+            assert!((0.0..=1.0).contains(&fractal.ridged(p, value3)));
+        }
+    }
+
+    #[test]
+    fn test_zero_octaves() {
+        let fractal = Fractal::new(0, 2.0, 0.5);
+        assert_eq!(fractal.fbm(Vector2::new(0.5, 0.5), value2), 0.0);
+    }
+
+    #[test]
+    fn test_warp_zero_strength_is_identity() {
+        let p2 = Vector2::new(0.3, 0.7);
+        let p3 = Vector3::new(0.3, 0.7, 1.1);
+        assert_eq!(warp2(p2, value2, 0.0), p2);
+        assert_eq!(warp3(p3, value3, 0.0), p3);
+        assert_eq!(double_warp2(p2, value2, 0.0), value2(p2));
+        assert_eq!(double_warp3(p3, value3, 0.0), value3(p3));
+    }
+
+    #[test]
+    fn test_warp_displacement_is_bounded() {
+        let fractal = Fractal::default();
+        let noise = |q: Vector2<f32>| fractal.fbm(q, value2);
+        for i in 0..50 {
+            let p = Vector2::new(i as f32 * 0.31, i as f32 * -0.17);
+            let d = warp2(p, noise, 2.0) - p;
+            assert!(d.x.abs() <= 2.0 && d.y.abs() <= 2.0);
+            assert!((-1.0..=1.0).contains(&double_warp2(p, noise, 2.0)));
+        }
+    }
+
+    #[test]
+    fn test_worley2() {
+        for i in 0..100 {
+            let p = Vector2::new(i as f32 * 0.37, i as f32 * 0.53);
+            let cell = worley2(p);
+            assert!(cell.f1 <= cell.f2);
+            let d = cell.point - p;
+            assert!(((d.x * d.x + d.y * d.y).sqrt() - cell.f1).abs() < 1e-6);
+            // Feature points are stable, so querying one returns itself
+            let at_point = worley2(cell.point);
+            assert_eq!(at_point.f1, 0.0);
+            assert_eq!(at_point.point, cell.point);
+        }
+    }
+
+    #[test]
+    fn test_worley3() {
+        for i in 0..50 {
+            let p = Vector3::new(i as f32 * 0.37, i as f32 * -0.53, 0.25);
+            let cell = worley3(p);
+            assert!(cell.f1 <= cell.f2);
+            let d = cell.point - p;
+            assert!((d.dot(&d).sqrt() - cell.f1).abs() < 1e-6);
+            assert_eq!(worley3(cell.point).f1, 0.0);
+        }
+    }
+
+    // Distances to every feature point within `reach` cells, sorted
+    fn brute_force(p: Vector3<f32>, reach: i32, dims: usize) -> Vec<f32> {
+        let (cx, cy, cz) = (p.x.floor() as i32, p.y.floor() as i32, p.z.floor() as i32);
+        let span = |c: i32, on: bool| if on { c - reach..=c + reach } else { c..=c };
+        let mut dists = Vec::new();
+        for k in span(cz, dims == 3) {
+            for j in span(cy, true) {
+                for i in span(cx, true) {
+                    let feature = if dims == 3 {
+                        Vector3::new(
+                            i as f32 + unit(i, j, k, 1),
+                            j as f32 + unit(i, j, k, 2),
+                            k as f32 + unit(i, j, k, 3),
+                        )
+                    } else {
+                        Vector3::new(
+                            i as f32 + unit(i, j, 0, 1),
+                            j as f32 + unit(i, j, 0, 2),
+                            0.0,
+                        )
+                    };
+                    let d = feature - p;
+                    dists.push(d.dot(&d).sqrt());
+                }
+            }
+        }
+        dists.sort_by(f32::total_cmp);
+        dists
+    }
+
+    #[test]
+    fn test_worley_matches_brute_force() {
+        // The second closest point of the first two lies two cells away
+        let far2 = [Vector2::new(3.65, 3.85), Vector2::new(3.65, 3.95)];
+        let sweep2 = (0..40 * 40).map(|i| Vector2::new((i % 40) as f32, (i / 40) as f32) * 0.25);
+        for p in far2.into_iter().chain(sweep2) {
+            let expected = brute_force(Vector3::new(p.x, p.y, 0.0), 3, 2);
+            let cell = worley2(p);
+            assert_eq!((cell.f1, cell.f2), (expected[0], expected[1]), "at {p:?}");
+        }
+        let far3 = [Vector3::new(0.0, 0.2, 0.7), Vector3::new(1.4, 3.0, 1.7)];
+        let sweep3 = (0..300).map(|i| {
+            let t = i as f32;
+            Vector3::new(t * 0.137 - 20.0, t * -0.291, t * 0.077 + 3.0)
+        });
+        for p in far3.into_iter().chain(sweep3) {
+            let expected = brute_force(p, 3, 3);
+            let cell = worley3(p);
+            assert_eq!((cell.f1, cell.f2), (expected[0], expected[1]), "at {p:?}");
+        }
+    }
+}
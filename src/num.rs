@@ -0,0 +1,61 @@
+//! `num_traits::Zero` and `One` for the vector types, for generic numeric
+//! code such as accumulators and polynomial evaluation. Requires the
+//! `num-traits` feature.
+//!
+//! `One` is the vector of ones, the identity of the component-wise product.
+
+use num_traits::{One, Zero};
+
+use crate::{Vector2, Vector3, Vector4};
+
+macro_rules! impl_zero_one {
+    ($($v:ident => [$($c:ident),*]);*) => {
+        $(
+            impl<T: Zero> Zero for $v<T> {
+                fn zero() -> Self {
+                    $v { $($c: T::zero()),* }
+                }
+
+                fn is_zero(&self) -> bool {
+                    true $(&& self.$c.is_zero())*
+                }
+            }
+
+            impl<T: One> One for $v<T> {
+                fn one() -> Self {
+                    $v { $($c: T::one()),* }
+                }
+            }
+        )*
+    };
+}
+
+impl_zero_one!(
+    Vector2 => [x, y];
+    Vector3 => [x, y, z];
+    Vector4 => [x, y, z, w]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Horner's scheme, generic over anything with Zero and a product
+    fn eval_poly<V: Zero + One + Copy + std::ops::Mul<Output = V>>(coeffs: &[V], x: V) -> V {
+        coeffs.iter().rev().fold(V::zero(), |acc, &c| acc * x + c)
+    }
+
+    #[test]
+    fn test_zero_one() {
+        assert!(Vector3::<f32>::zero().is_zero());
+        assert!(!Vector2::new(0, 1).is_zero());
+        assert_eq!(Vector4::<i32>::one(), Vector4::new(1, 1, 1, 1));
+
+        // 1 + 2x + x^2 per component
+        let coeffs = [Vector2::one(), Vector2::new(2.0, 2.0), Vector2::one()];
+        assert_eq!(
+            eval_poly(&coeffs, Vector2::new(1.0, -1.0)),
+            Vector2::new(4.0, 0.0)
+        );
+    }
+}
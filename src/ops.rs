@@ -0,0 +1,180 @@
+//! Traits describing what vectors can do, for code that is generic over the
+//! vector type and its scalar.
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::{Components, Float, Vector2, Vector3, Vector4};
+
+/// Vectors that can be added and scaled, with interpolation between them.
+pub trait VectorSpace:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Self::Scalar, Output = Self>
+{
+    type Scalar: Float;
+
+    fn zero() -> Self;
+
+    /// Linear interpolation from self (t = 0) to `other` (t = 1).
+    fn lerp(&self, other: &Self, t: Self::Scalar) -> Self {
+        *self + (*other - *self) * t
+    }
+}
+
+/// Vector spaces with a dot product, and with it lengths and directions.
+pub trait InnerSpace: VectorSpace {
+    fn dot(&self, other: &Self) -> Self::Scalar;
+
+    fn magnitude_squared(&self) -> Self::Scalar {
+        self.dot(self)
+    }
+
+    fn magnitude(&self) -> Self::Scalar {
+        self.magnitude_squared().sqrt()
+    }
+
+    /// Unit vector in the same direction. The zero vector gives NaN
+    /// components.
+    fn normalize(&self) -> Self {
+        *self * self.magnitude().recip()
+    }
+}
+
+/// Types with a distance between values, such as points.
+pub trait MetricSpace {
+    type Metric: Float;
+
+    fn distance_squared(&self, other: &Self) -> Self::Metric;
+
+    fn distance(&self, other: &Self) -> Self::Metric {
+        self.distance_squared(other).sqrt()
+    }
+}
+
+/// All of the above plus per-axis access through `Components`, for
+/// algorithms such as splines and integrators written once for every
+/// dimension.
+pub trait VectorOps:
+    InnerSpace + MetricSpace<Metric = Self::Scalar> + Components<Self::Scalar>
+{
+}
+
+// The provided methods forward to the inherent ones, which share their names
+macro_rules! impl_vector_ops {
+    ($($v:ident => [$($c:ident),*]);*) => {
+        $(
+            impl<T: Float> VectorSpace for $v<T> {
+                type Scalar = T;
+
+                fn zero() -> Self {
+                    $v { $($c: T::ZERO),* }
+                }
+            }
+
+            impl<T: Float> InnerSpace for $v<T> {
+                fn dot(&self, other: &Self) -> T {
+                    T::ZERO $(+ self.$c * other.$c)*
+                }
+
+                fn magnitude_squared(&self) -> T {
+                    $v::magnitude_squared(self)
+                }
+            }
+
+            impl<T: Float> MetricSpace for $v<T> {
+                type Metric = T;
+
+                fn distance_squared(&self, other: &Self) -> T {
+                    $v::distance_squared(self, other)
+                }
+
+                fn distance(&self, other: &Self) -> T {
+                    $v::distance(self, other)
+                }
+            }
+
+            impl<T: Float> VectorOps for $v<T> {}
+        )*
+    };
+}
+
+impl_vector_ops!(
+    Vector2 => [x, y];
+    Vector3 => [x, y, z];
+    Vector4 => [x, y, z, w]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Written once, used for every dimension
+    fn path_length<V: VectorOps>(points: &[V]) -> V::Scalar {
+        points
+            .windows(2)
+            .fold(V::Scalar::ZERO, |sum, w| sum + (w[1] - w[0]).magnitude())
+    }
+
+    fn midpoint<V: VectorSpace>(a: &V, b: &V) -> V {
+        let half = V::Scalar::ONE / (V::Scalar::ONE + V::Scalar::ONE);
+        a.lerp(b, half)
+    }
+
+    fn direction<V: InnerSpace>(v: &V) -> (V::Scalar, V) {
+        (v.dot(v), v.normalize())
+    }
+
+    fn reversed<V: VectorOps>(v: &V) -> V {
+        V::from_components(|i| v.component(V::DIM - 1 - i))
+    }
+
+    #[test]
+    fn test_vector_ops() {
+        let path = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(3.0, 4.0),
+            Vector2::new(3.0, 5.0),
+        ];
+        assert_eq!(path_length(&path), 6.0);
+
+        let v = Vector4::new(0.0f64, 1.0, 2.0, 3.0);
+        assert_eq!(reversed(&v), Vector4::new(3.0, 2.0, 1.0, 0.0));
+        assert_eq!(
+            midpoint(&Vector3::new(0.0, 2.0, 4.0), &Vector3::new(2.0, 2.0, 0.0)),
+            Vector3::new(1.0, 2.0, 2.0)
+        );
+        assert_eq!(
+            direction(&Vector4::new(0.0, 0.0, 2.0, 0.0)),
+            (4.0, Vector4::new(0.0, 0.0, 1.0, 0.0))
+        );
+        assert_eq!(<Vector3<f32> as Components>::DIM, 3);
+    }
+
+    // Bounded only on what it uses
+    fn nearest<P: MetricSpace + Copy>(points: &[P], to: &P) -> Option<P> {
+        points
+            .iter()
+            .copied()
+            .min_by(|a, b| a.distance_squared(to).total_cmp(&b.distance_squared(to)))
+    }
+
+    fn farthest_distance<P: MetricSpace>(points: &[P], to: &P) -> P::Metric {
+        points
+            .iter()
+            .map(|p| p.distance(to))
+            .fold(P::Metric::ZERO, |a, b| a.max(b))
+    }
+
+    #[test]
+    fn test_space_traits() {
+        let points = [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 3.0, 0.0)];
+        let p = Vector3::new(0.0, 2.0, 0.0);
+        assert_eq!(nearest(&points, &p), Some(points[1]));
+        assert_eq!(farthest_distance(&points, &points[0]), 10.0f64.sqrt());
+        // Far apart points keep a finite distance, as with the inherent method
+        let far = [Vector2::new(1e20f32, 0.0), Vector2::new(-1e20, 0.0)];
+        assert_eq!(farthest_distance(&far, &far[0]), 2e20);
+        assert_eq!(
+            <Vector2<f32> as VectorSpace>::zero(),
+            Vector2::new(0.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! Two-body orbital mechanics on `Vector3<f64>` state vectors.
+//!
+//! Everything is in `f64` since orbital states span many orders of magnitude.
+//! `mu` is the standard gravitational parameter `G * M` of the central body, in
+//! units consistent with the positions and velocities. Only bound (elliptic)
+//! orbits are supported.
+
+use std::f64::consts::{PI, TAU};
+
+use crate::Vector3;
+
+const EPSILON: f64 = 1e-10;
+
+/// Classical Keplerian elements of an elliptic orbit. Angles are in radians.
+///
+/// For circular orbits the argument of periapsis is zero and the mean anomaly
+/// is measured from the ascending node; for equatorial orbits the longitude of
+/// the ascending node is zero and angles are measured from +x.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitalElements {
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub longitude_of_ascending_node: f64,
+    pub argument_of_periapsis: f64,
+    pub mean_anomaly: f64,
+}
+
+/// Signed angle from `from` to `to` around the axis `normal` (unit length).
+fn angle_around(from: &Vector3<f64>, to: &Vector3<f64>, normal: &Vector3<f64>) -> f64 {
+    normal.dot(&from.cross(to)).atan2(from.dot(to))
+}
+
+/// Solves Kepler's equation `M = E - e sin E` for the eccentric anomaly `E`
+/// with Newton's method.
+pub fn solve_kepler(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    // Wrap into [-pi, pi] so the starting guess is close
+    let m = (mean_anomaly + PI).rem_euclid(TAU) - PI;
+    let mut e_anomaly = if eccentricity < 0.8 {
+        m
+    } else {
+        PI.copysign(m)
+    };
+    for _ in 0..50 {
+        let f = e_anomaly - eccentricity * e_anomaly.sin() - m;
+        let step = f / (1.0 - eccentricity * e_anomaly.cos());
+        e_anomaly -= step;
+        if step.abs() < 1e-14 {
+            break;
+        }
+    }
+    e_anomaly + (mean_anomaly - m)
+}
+
+impl OrbitalElements {
+    /// Elements of the orbit through `position` with `velocity`. Returns `None`
+    /// for unbound (parabolic or hyperbolic) and degenerate radial trajectories.
+    pub fn from_state(
+        position: &Vector3<f64>,
+        velocity: &Vector3<f64>,
+        mu: f64,
+    ) -> Option<OrbitalElements> {
+        let r = position.magnitude();
+        let v_sq = velocity.dot(velocity);
+        let energy = 0.5 * v_sq - mu / r;
+        let h = position.cross(velocity);
+        let h_len = h.magnitude();
+        if r == 0.0 || energy >= 0.0 || h_len <= EPSILON * r * v_sq.sqrt() {
+            return None;
+        }
+        let h_hat = h * h_len.recip();
+
+        let semi_major_axis = -mu / (2.0 * energy);
+        let e_vec = (*position * (v_sq - mu / r) - *velocity * position.dot(velocity)) * mu.recip();
+        let eccentricity = e_vec.magnitude();
+        let inclination = (h_hat.z).clamp(-1.0, 1.0).acos();
+
+        // Node vector, falling back to +x for equatorial orbits
+        let node = Vector3::new(-h.y, h.x, 0.0);
+        let node_len = node.magnitude();
+        let (reference, longitude_of_ascending_node) = if node_len > EPSILON * h_len {
+            (
+                node * node_len.recip(),
+                node.y.atan2(node.x).rem_euclid(TAU),
+            )
+        } else {
+            (Vector3::new(1.0, 0.0, 0.0), 0.0)
+        };
+
+        let (argument_of_periapsis, true_anomaly) = if eccentricity > EPSILON {
+            (
+                angle_around(&reference, &e_vec, &h_hat).rem_euclid(TAU),
+                angle_around(&e_vec, position, &h_hat),
+            )
+        } else {
+            (0.0, angle_around(&reference, position, &h_hat))
+        };
+
+        let e_anomaly = ((1.0 - eccentricity * eccentricity).sqrt() * true_anomaly.sin())
+            .atan2(eccentricity + true_anomaly.cos());
+        let mean_anomaly = (e_anomaly - eccentricity * e_anomaly.sin()).rem_euclid(TAU);
+
+        Some(OrbitalElements {
+            semi_major_axis,
+            eccentricity,
+            inclination,
+            longitude_of_ascending_node,
+            argument_of_periapsis,
+            mean_anomaly,
+        })
+    }
+
+    /// Position and velocity on the orbit at the current mean anomaly.
+    pub fn to_state(&self, mu: f64) -> (Vector3<f64>, Vector3<f64>) {
+        let (a, e) = (self.semi_major_axis, self.eccentricity);
+        let e_anomaly = solve_kepler(self.mean_anomaly, e);
+        let (sin_e, cos_e) = e_anomaly.sin_cos();
+        let b_ratio = (1.0 - e * e).sqrt();
+        let r = a * (1.0 - e * cos_e);
+        let speed = (mu * a).sqrt() / r;
+
+        // Perifocal basis: p toward periapsis, q 90 degrees ahead in the plane
+        let (sin_o, cos_o) = self.longitude_of_ascending_node.sin_cos();
+        let (sin_w, cos_w) = self.argument_of_periapsis.sin_cos();
+        let (sin_i, cos_i) = self.inclination.sin_cos();
+        let p = Vector3::new(
+            cos_o * cos_w - sin_o * sin_w * cos_i,
+            sin_o * cos_w + cos_o * sin_w * cos_i,
+            sin_w * sin_i,
+        );
+        let q = Vector3::new(
+            -cos_o * sin_w - sin_o * cos_w * cos_i,
+            -sin_o * sin_w + cos_o * cos_w * cos_i,
+            cos_w * sin_i,
+        );
+
+        let position = p * (a * (cos_e - e)) + q * (a * b_ratio * sin_e);
+        let velocity = p * (-speed * sin_e) + q * (speed * b_ratio * cos_e);
+        (position, velocity)
+    }
+
+    /// Orbital period.
+    pub fn period(&self, mu: f64) -> f64 {
+        TAU * (self.semi_major_axis.powi(3) / mu).sqrt()
+    }
+
+    /// The same orbit `dt` later, advancing only the mean anomaly.
+    pub fn propagate(&self, dt: f64, mu: f64) -> OrbitalElements {
+        let mean_motion = (mu / self.semi_major_axis.powi(3)).sqrt();
+        OrbitalElements {
+            mean_anomaly: (self.mean_anomaly + mean_motion * dt).rem_euclid(TAU),
+            ..*self
+        }
+    }
+}
+
+/// Position and velocity `dt` after the given state, following the exact
+/// two-body orbit. Returns `None` for unbound or radial trajectories.
+pub fn propagate(
+    position: &Vector3<f64>,
+    velocity: &Vector3<f64>,
+    mu: f64,
+    dt: f64,
+) -> Option<(Vector3<f64>, Vector3<f64>)> {
+    OrbitalElements::from_state(position, velocity, mu).map(|el| el.propagate(dt, mu).to_state(mu))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Vector3<f64>, b: Vector3<f64>) {
+        let d = a - b;
+        assert!(
+            d.magnitude() < 1e-8 * b.magnitude().max(1.0),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn test_solve_kepler() {
+        for &(m, e) in &[(0.5, 0.1), (3.0, 0.9), (-2.0, 0.5), (10.0, 0.99)] {
+            let big_e = solve_kepler(m, e);
+            assert!((big_e - e * big_e.sin() - m).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_circular_orbit() {
+        // Unit circular orbit in the xy plane
+        let el = OrbitalElements::from_state(
+            &Vector3::new(1.0, 0.0, 0.0),
+            &Vector3::new(0.0, 1.0, 0.0),
+            1.0,
+        )
+        .unwrap();
+        assert!((el.semi_major_axis - 1.0).abs() < 1e-12);
+        assert!(el.eccentricity < 1e-12);
+        assert!((el.period(1.0) - TAU).abs() < 1e-12);
+
+        let (pos, vel) = el.propagate(PI / 2.0, 1.0).to_state(1.0);
+        assert_close(pos, Vector3::new(0.0, 1.0, 0.0));
+        assert_close(vel, Vector3::new(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mu = 398600.4418;
+        let states = [
+            (
+                Vector3::new(7000.0, -1200.0, 300.0),
+                Vector3::new(1.0, 7.2, 3.1),
+            ),
+            (
+                Vector3::new(-4000.0, 5000.0, 0.0),
+                Vector3::new(-5.0, -4.0, 0.0),
+            ),
+            (
+                Vector3::new(0.0, 9000.0, -2000.0),
+                Vector3::new(-6.5, 0.0, 0.5),
+            ),
+        ];
+        for (pos, vel) in states {
+            let el = OrbitalElements::from_state(&pos, &vel, mu).unwrap();
+            let (p, v) = el.to_state(mu);
+            assert_close(p, pos);
+            assert_close(v, vel);
+
+            let period = el.period(mu);
+            let (p, v) = propagate(&pos, &vel, mu, period).unwrap();
+            assert_close(p, pos);
+            assert_close(v, vel);
+        }
+    }
+
+    #[test]
+    fn test_unbound_orbit() {
+        let pos = Vector3::new(1.0, 0.0, 0.0);
+        assert!(OrbitalElements::from_state(&pos, &Vector3::new(0.0, 2.0, 0.0), 1.0).is_none());
+        assert!(OrbitalElements::from_state(&pos, &Vector3::new(0.5, 0.0, 0.0), 1.0).is_none());
+    }
+}
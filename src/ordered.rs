@@ -0,0 +1,112 @@
+//! Total ordering, equality and hashing for float vectors, so they can key
+//! hash maps and sets or be sorted and deduplicated.
+//!
+//! Comparison canonicalizes each component first: `-0.0` becomes `0.0` and
+//! every NaN becomes the one quiet NaN, which sorts after positive infinity.
+//! Equality and hashing then agree, so values that compare equal always land
+//! in the same bucket.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use crate::Components;
+
+/// A float vector that is `Eq`, `Ord` and `Hash`, for use as a key in hash
+/// sets and maps, e.g. to deduplicate vertices.
+///
+/// Components compare by value, except that `-0.0` equals `0.0` and all NaNs
+/// are equal to each other and ordered after positive infinity. Vectors that
+/// are equal hash the same.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrderedVector<V>(pub V);
+
+// Collapses the values that compare equal to a single bit pattern
+fn canonical(x: f32) -> f32 {
+    if x == 0.0 {
+        0.0
+    } else if x.is_nan() {
+        f32::NAN
+    } else {
+        x
+    }
+}
+
+impl<V: Components> OrderedVector<V> {
+    fn key(&self, axis: usize) -> f32 {
+        canonical(self.0.component(axis))
+    }
+}
+
+impl<V> From<V> for OrderedVector<V> {
+    fn from(v: V) -> Self {
+        OrderedVector(v)
+    }
+}
+
+impl<V: Components> PartialEq for OrderedVector<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<V: Components> Eq for OrderedVector<V> {}
+
+impl<V: Components> PartialOrd for OrderedVector<V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Lexicographic, like the ordering of integer vectors.
+impl<V: Components> Ord for OrderedVector<V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (0..V::DIM)
+            .map(|i| self.key(i).total_cmp(&other.key(i)))
+            .find(|&o| o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<V: Components> Hash for OrderedVector<V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for i in 0..V::DIM {
+            self.key(i).to_bits().hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector2, Vector3};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_ordered_vector_dedup() {
+        let points = [
+            Vector3::new(1.0, 0.0, 2.0),
+            Vector3::new(1.0, -0.0, 2.0),
+            Vector3::new(f32::NAN, 0.0, 0.0),
+            Vector3::new(-f32::NAN, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 2.5),
+        ];
+        let unique: HashSet<_> = points.iter().copied().map(OrderedVector).collect();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[test]
+    fn test_ordered_vector_ord() {
+        let mut v: Vec<_> = [
+            Vector2::new(f32::NAN, 0.0),
+            Vector2::new(1.0, 5.0),
+            Vector2::new(1.0, -1.0),
+            Vector2::new(f32::NEG_INFINITY, 9.0),
+        ]
+        .map(OrderedVector)
+        .to_vec();
+        v.sort();
+        assert_eq!(v[0].0, Vector2::new(f32::NEG_INFINITY, 9.0));
+        assert_eq!(v[1].0, Vector2::new(1.0, -1.0));
+        assert!(v[3].0.x.is_nan());
+    }
+}
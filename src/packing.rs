@@ -0,0 +1,180 @@
+//! Rectangle packing for texture atlases and UI layout, with the skyline
+//! bottom-left heuristic (Jylänki, "A Thousand Ways to Pack the Bin").
+//!
+//! Sizes and offsets are `Vector2<u32>` with x the width and y the height.
+//! Offsets are the minimum corner of each placed rectangle.
+
+use crate::Vector2;
+
+/// Horizontal segment of the skyline: the top of the used area over
+/// `[x, x + width)` is at height `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Segment {
+    x: u32,
+    y: u32,
+    width: u32,
+}
+
+/// Online packer placing rectangles one at a time into a fixed-size atlas.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtlasPacker {
+    size: Vector2<u32>,
+    skyline: Vec<Segment>,
+}
+
+impl AtlasPacker {
+    pub fn new(size: Vector2<u32>) -> AtlasPacker {
+        AtlasPacker {
+            size,
+            skyline: vec![Segment {
+                x: 0,
+                y: 0,
+                width: size.x,
+            }],
+        }
+    }
+
+    pub fn size(&self) -> Vector2<u32> {
+        self.size
+    }
+
+    /// Height of the used part of the atlas, for trimming it after packing.
+    pub fn used_height(&self) -> u32 {
+        self.skyline.iter().map(|s| s.y).max().unwrap_or(0)
+    }
+
+    /// Height the rectangle would rest at if its left edge sat at the start
+    /// of segment `i`, or `None` if it does not fit there.
+    fn fit(&self, i: usize, size: Vector2<u32>) -> Option<u32> {
+        // Skyline segments never leave the atlas, so these can't underflow
+        let x = self.skyline[i].x;
+        if size.x > self.size.x - x {
+            return None;
+        }
+        let mut y = 0;
+        let mut covered = 0;
+        for s in &self.skyline[i..] {
+            if covered >= size.x {
+                break;
+            }
+            y = y.max(s.y);
+            covered += s.width;
+        }
+        (size.y <= self.size.y - y).then_some(y)
+    }
+
+    /// Places a rectangle as low as possible, then as far left as possible,
+    /// and returns its offset, or `None` if it does not fit. Empty rectangles
+    /// take no space and are placed at the origin.
+    pub fn insert(&mut self, size: Vector2<u32>) -> Option<Vector2<u32>> {
+        if size.x == 0 || size.y == 0 {
+            return Some(Vector2::new(0, 0));
+        }
+        let (i, y) = (0..self.skyline.len())
+            .filter_map(|i| Some((i, self.fit(i, size)?)))
+            .min_by_key(|&(i, y)| (y + size.y, self.skyline[i].x))?;
+        let x = self.skyline[i].x;
+
+        // Raise the skyline under the new rectangle
+        let placed = Segment {
+            x,
+            y: y + size.y,
+            width: size.x,
+        };
+        self.skyline.insert(i, placed);
+        let end = x + size.x;
+        while i + 1 < self.skyline.len() && self.skyline[i + 1].x < end {
+            let next = &mut self.skyline[i + 1];
+            let next_end = next.x + next.width;
+            if next_end <= end {
+                self.skyline.remove(i + 1);
+            } else {
+                next.width = next_end - end;
+                next.x = end;
+            }
+        }
+        // Merge neighbors at equal heights
+        self.skyline.dedup_by(|b, a| {
+            if a.y == b.y {
+                a.width += b.width;
+                true
+            } else {
+                false
+            }
+        });
+        Some(Vector2::new(x, y))
+    }
+}
+
+/// Packs all `sizes` into an atlas of `atlas_size`, placing taller rectangles
+/// first for a tighter layout. Offsets are returned in the order of `sizes`,
+/// `None` for rectangles that did not fit.
+pub fn pack(sizes: &[Vector2<u32>], atlas_size: Vector2<u32>) -> Vec<Option<Vector2<u32>>> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| (std::cmp::Reverse(sizes[i].y), std::cmp::Reverse(sizes[i].x)));
+    let mut packer = AtlasPacker::new(atlas_size);
+    let mut offsets = vec![None; sizes.len()];
+    for i in order {
+        offsets[i] = packer.insert(sizes[i]);
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overlap(a: (Vector2<u32>, Vector2<u32>), b: (Vector2<u32>, Vector2<u32>)) -> bool {
+        a.0.x < b.0.x + b.1.x
+            && b.0.x < a.0.x + a.1.x
+            && a.0.y < b.0.y + b.1.y
+            && b.0.y < a.0.y + a.1.y
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut packer = AtlasPacker::new(Vector2::new(10, 10));
+        assert_eq!(packer.insert(Vector2::new(6, 4)), Some(Vector2::new(0, 0)));
+        assert_eq!(packer.insert(Vector2::new(4, 2)), Some(Vector2::new(6, 0)));
+        assert_eq!(packer.insert(Vector2::new(4, 3)), Some(Vector2::new(6, 2)));
+        // Rests on the lower, left part of the skyline
+        assert_eq!(packer.insert(Vector2::new(6, 6)), Some(Vector2::new(0, 4)));
+        assert_eq!(packer.used_height(), 10);
+        assert_eq!(packer.insert(Vector2::new(5, 5)), None);
+        assert_eq!(packer.insert(Vector2::new(4, 5)), Some(Vector2::new(6, 5)));
+    }
+
+    #[test]
+    fn test_insert_oversized() {
+        let mut packer = AtlasPacker::new(Vector2::new(10, 10));
+        assert_eq!(packer.insert(Vector2::new(3, 3)), Some(Vector2::new(0, 0)));
+        assert_eq!(packer.insert(Vector2::new(u32::MAX, 1)), None);
+        assert_eq!(packer.insert(Vector2::new(1, u32::MAX)), None);
+        assert_eq!(packer.insert(Vector2::new(7, 7)), Some(Vector2::new(3, 0)));
+    }
+
+    #[test]
+    fn test_pack() {
+        let sizes = [
+            Vector2::new(3, 2),
+            Vector2::new(5, 5),
+            Vector2::new(2, 7),
+            Vector2::new(4, 1),
+            Vector2::new(9, 9),
+        ];
+        let offsets = pack(&sizes, Vector2::new(8, 8));
+        assert_eq!(offsets[4], None);
+        let placed: Vec<_> = offsets
+            .iter()
+            .zip(sizes)
+            .filter_map(|(o, s)| Some(((*o)?, s)))
+            .collect();
+        assert_eq!(placed.len(), 4);
+        for (i, a) in placed.iter().enumerate() {
+            assert!(a.0.x + a.1.x <= 8 && a.0.y + a.1.y <= 8);
+            for b in &placed[i + 1..] {
+                assert!(!overlap(*a, *b));
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Field-of-view checks for AI perception, in 2D or 3D.
+
+use crate::components::{delta, dot};
+use crate::Components;
+
+/// Perception score of a target in [0, 1]: zero if it is out of range or
+/// outside the view cone of full angle `fov` (radians) around `facing`, and
+/// otherwise falling off linearly from one at the observer to zero at
+/// `max_range`.
+pub fn perception_score<V: Components>(
+    observer: &V,
+    facing: &V,
+    target: &V,
+    fov: f32,
+    max_range: f32,
+) -> f32 {
+    let to_target = delta(observer, target);
+    let dist_sq = dot(&to_target, &to_target);
+    if dist_sq > max_range * max_range {
+        return 0.0;
+    }
+    let dist = dist_sq.sqrt();
+    if dist > 0.0 {
+        // Compare cosines instead of computing the angle
+        let facing_len = dot(facing, facing).sqrt();
+        let cos = dot(facing, &to_target) / (facing_len * dist);
+        if cos < (fov * 0.5).cos() {
+            return 0.0;
+        }
+    }
+    if max_range > 0.0 {
+        (1.0 - dist / max_range).max(f32::MIN_POSITIVE)
+    } else {
+        1.0
+    }
+}
+
+/// Whether `target` is within `max_range` of `observer` and inside the view
+/// cone of full angle `fov` (radians) around `facing`.
+pub fn can_perceive<V: Components>(
+    observer: &V,
+    facing: &V,
+    target: &V,
+    fov: f32,
+    max_range: f32,
+) -> bool {
+    perception_score(observer, facing, target, fov, max_range) > 0.0
+}
+
+/// Perception scores of many targets at once, see `perception_score`.
+pub fn perception_scores<V: Components>(
+    observer: &V,
+    facing: &V,
+    targets: &[V],
+    fov: f32,
+    max_range: f32,
+) -> Vec<f32> {
+    targets
+        .iter()
+        .map(|t| perception_score(observer, facing, t, fov, max_range))
+        .collect()
+}
+
+/// Indices of the perceived targets with their scores, most noticeable first.
+pub fn perceived_targets<V: Components>(
+    observer: &V,
+    facing: &V,
+    targets: &[V],
+    fov: f32,
+    max_range: f32,
+) -> Vec<(usize, f32)> {
+    let mut perceived: Vec<(usize, f32)> =
+        perception_scores(observer, facing, targets, fov, max_range)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+    perceived.sort_by(|a, b| b.1.total_cmp(&a.1));
+    perceived
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector2, Vector3};
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_can_perceive() {
+        let observer = Vector2::new(0.0, 0.0);
+        let facing = Vector2::new(0.0, 2.0);
+        let sees = |t: Vector2<f32>| can_perceive(&observer, &facing, &t, FRAC_PI_2, 10.0);
+        assert!(sees(Vector2::new(0.0, 5.0)));
+        assert!(sees(Vector2::new(1.0, 1.1)));
+        assert!(!sees(Vector2::new(1.1, 1.0)));
+        assert!(!sees(Vector2::new(0.0, -5.0)));
+        assert!(!sees(Vector2::new(0.0, 11.0)));
+        assert!(sees(observer));
+    }
+
+    #[test]
+    fn test_perception_scores() {
+        let observer = Vector3::new(0.0, 0.0, 0.0);
+        let facing = Vector3::new(1.0, 0.0, 0.0);
+        let targets = [
+            Vector3::new(5.0, 0.0, 0.0),
+            Vector3::new(-5.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.5),
+        ];
+        let scores = perception_scores(&observer, &facing, &targets, 1.0, 10.0);
+        assert_eq!(scores[0], 0.5);
+        assert_eq!(scores[1], 0.0);
+        assert!(scores[2] > scores[0]);
+
+        let perceived = perceived_targets(&observer, &facing, &targets, 1.0, 10.0);
+        assert_eq!(
+            perceived.iter().map(|p| p.0).collect::<Vec<_>>(),
+            vec![2, 0]
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! Simple polygons given as slices of their vertices, without repeating the
+//! first vertex at the end.
+//!
+//! Winding follows the usual math convention with y pointing up: counter-
+//! clockwise polygons have positive area. With y pointing down, as in screen
+//! coordinates, the directions swap.
+
+use crate::{Float, Vector2};
+
+/// Signed area by the shoelace formula, positive for counter-clockwise
+/// polygons. Zero for fewer than three vertices.
+pub fn signed_area<T: Float>(points: &[Vector2<T>]) -> T {
+    let mut twice_area = T::ZERO;
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        twice_area += a.x * b.y - b.x * a.y;
+    }
+    twice_area / (T::ONE + T::ONE)
+}
+
+/// Whether the polygon winds clockwise. Degenerate polygons with zero area
+/// are neither clockwise nor counter-clockwise.
+pub fn is_clockwise<T: Float>(points: &[Vector2<T>]) -> bool {
+    signed_area(points) < T::ZERO
+}
+
+/// Whether the polygon winds counter-clockwise.
+pub fn is_counter_clockwise<T: Float>(points: &[Vector2<T>]) -> bool {
+    signed_area(points) > T::ZERO
+}
+
+/// Reverses the winding in place while keeping the first vertex first, so
+/// indices referring to it stay valid.
+pub fn reverse_winding<T>(points: &mut [Vector2<T>]) {
+    if points.len() > 1 {
+        points[1..].reverse();
+    }
+}
+
+/// Reverses the winding if needed so the polygon is counter-clockwise, or
+/// clockwise if `clockwise` is set. Returns whether it was reversed.
+pub fn set_winding<T: Float>(points: &mut [Vector2<T>], clockwise: bool) -> bool {
+    let area = signed_area(points);
+    let reverse = if clockwise {
+        area > T::ZERO
+    } else {
+        area < T::ZERO
+    };
+    if reverse {
+        reverse_winding(points);
+    }
+    reverse
+}
+
+// Twice the signed area of the triangle oab, positive for a left turn
+fn cross(o: Vector2<f32>, a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    (a - o).perp_dot(&(b - o))
+}
+
+/// Whether `p` lies inside or on the counter-clockwise triangle `abc`.
+fn in_triangle(p: Vector2<f32>, a: Vector2<f32>, b: Vector2<f32>, c: Vector2<f32>) -> bool {
+    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+}
+
+/// Triangulates a simple polygon by ear clipping. Returns triangles as indices
+/// into `points`, all wound counter-clockwise whatever the input winding.
+/// Runs in O(n^2), which is fine for outlines of up to a few thousand points.
+pub fn triangulate(points: &[Vector2<f32>]) -> Vec<[usize; 3]> {
+    triangulate_with_holes(points, &[])
+}
+
+/// Triangulates a simple polygon with holes, which must lie inside it without
+/// touching each other. Indices refer to the outline followed by the holes in
+/// order, as if all points were concatenated into one slice.
+///
+/// Each hole is joined to the outline by a zero-width bridge from its
+/// rightmost vertex to a visible outline vertex, and the resulting single
+/// polygon is ear clipped.
+pub fn triangulate_with_holes(
+    outline: &[Vector2<f32>],
+    holes: &[&[Vector2<f32>]],
+) -> Vec<[usize; 3]> {
+    let points: Vec<Vector2<f32>> = outline
+        .iter()
+        .chain(holes.iter().flat_map(|h| h.iter()))
+        .copied()
+        .collect();
+
+    let mut polygon: Vec<usize> = (0..outline.len()).collect();
+    if is_clockwise(outline) {
+        polygon.reverse();
+    }
+
+    // Bridge the holes from right to left, so earlier bridges cannot block
+    // later ones
+    let mut start = outline.len();
+    let mut hole_rings: Vec<Vec<usize>> = holes
+        .iter()
+        .map(|h| {
+            let mut ring: Vec<usize> = (start..start + h.len()).collect();
+            start += h.len();
+            // Holes wind opposite to the outline
+            if is_counter_clockwise(h) {
+                ring.reverse();
+            }
+            ring
+        })
+        .filter(|ring| ring.len() >= 3)
+        .collect();
+    let max_x = |ring: &Vec<usize>| ring.iter().map(|&i| points[i].x).fold(f32::MIN, f32::max);
+    hole_rings.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+    for ring in hole_rings {
+        bridge_hole(&points, &mut polygon, &ring);
+    }
+
+    ear_clip(&points, polygon)
+}
+
+/// Splices `hole` into `polygon` through a bridge to a vertex visible from the
+/// hole's rightmost vertex (David Eberly, "Triangulation by Ear Clipping").
+fn bridge_hole(points: &[Vector2<f32>], polygon: &mut Vec<usize>, hole: &[usize]) {
+    let m_pos = (0..hole.len())
+        .max_by(|&a, &b| points[hole[a]].x.total_cmp(&points[hole[b]].x))
+        .unwrap();
+    let m = points[hole[m_pos]];
+
+    // Closest edge hit by a ray from M towards +x
+    let n = polygon.len();
+    let mut hit: Option<(f32, usize)> = None;
+    for i in 0..n {
+        let (a, b) = (points[polygon[i]], points[polygon[(i + 1) % n]]);
+        if (a.y > m.y) == (b.y > m.y) {
+            continue;
+        }
+        let x = a.x + (m.y - a.y) / (b.y - a.y) * (b.x - a.x);
+        if x >= m.x && hit.is_none_or(|(best, _)| x < best) {
+            // Candidate is the endpoint of the edge furthest along the ray
+            let p = if a.x > b.x { i } else { (i + 1) % n };
+            hit = Some((x, p));
+        }
+    }
+    let Some((hit_x, mut p_pos)) = hit else {
+        return;
+    };
+
+    // Reflex vertices inside the triangle M, I, P may block the view of P; the
+    // one closest in angle to the ray is visible
+    let i_point = Vector2::new(hit_x, m.y);
+    let p = points[polygon[p_pos]];
+    let (tri_b, tri_c) = if p.y < m.y {
+        (p, i_point)
+    } else {
+        (i_point, p)
+    };
+    let mut best = (f32::INFINITY, f32::INFINITY);
+    for j in 0..n {
+        let v = points[polygon[j]];
+        let prev = points[polygon[(j + n - 1) % n]];
+        let next = points[polygon[(j + 1) % n]];
+        if j == p_pos || v == m || cross(prev, v, next) > 0.0 || !in_triangle(v, m, tri_b, tri_c) {
+            continue;
+        }
+        let d = v - m;
+        let key = ((d.y / d.x).abs(), d.x);
+        if key < best {
+            best = key;
+            p_pos = j;
+        }
+    }
+
+    let mut spliced = Vec::with_capacity(polygon.len() + hole.len() + 2);
+    spliced.extend_from_slice(&polygon[..=p_pos]);
+    spliced.extend_from_slice(&hole[m_pos..]);
+    spliced.extend_from_slice(&hole[..=m_pos]);
+    spliced.extend_from_slice(&polygon[p_pos..]);
+    *polygon = spliced;
+}
+
+/// Ear clipping of a counter-clockwise polygon given as indices into `points`.
+fn ear_clip(points: &[Vector2<f32>], mut polygon: Vec<usize>) -> Vec<[usize; 3]> {
+    let mut triangles = Vec::with_capacity(polygon.len().saturating_sub(2));
+    let mut i = 0;
+    let mut misses = 0;
+    while polygon.len() > 3 {
+        let n = polygon.len();
+        let (ia, ib, ic) = (polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]);
+        let (a, b, c) = (points[ia], points[ib], points[ic]);
+        // After a full pass without an ear the polygon is degenerate (self
+        // touching or collinear), so accept any non-reflex vertex
+        let is_ear = if misses <= n {
+            cross(a, b, c) > 0.0
+                && !polygon.iter().any(|&j| {
+                    let p = points[j];
+                    p != a && p != b && p != c && in_triangle(p, a, b, c)
+                })
+        } else {
+            cross(a, b, c) >= 0.0 || misses > 2 * n
+        };
+
+        if is_ear {
+            triangles.push([ia, ib, ic]);
+            polygon.remove(i);
+            i = (i + polygon.len() - 1) % polygon.len();
+            misses = 0;
+        } else {
+            i = (i + 1) % n;
+            misses += 1;
+        }
+    }
+    if polygon.len() == 3 {
+        triangles.push([polygon[0], polygon[1], polygon[2]]);
+    }
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Vec<Vector2<f32>> {
+        vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(2.0, 2.0),
+            Vector2::new(0.0, 2.0),
+        ]
+    }
+
+    #[test]
+    fn test_signed_area() {
+        let mut points = square();
+        assert_eq!(signed_area(&points), 4.0);
+        assert!(is_counter_clockwise(&points));
+        assert!(!is_clockwise(&points));
+
+        reverse_winding(&mut points);
+        assert_eq!(points[0], Vector2::new(0.0, 0.0));
+        assert_eq!(signed_area(&points), -4.0);
+        assert!(is_clockwise(&points));
+
+        let line = [Vector2::new(0.0f64, 0.0), Vector2::new(1.0, 1.0)];
+        assert_eq!(signed_area(&line), 0.0);
+        assert!(!is_clockwise(&line) && !is_counter_clockwise(&line));
+    }
+
+    #[test]
+    fn test_set_winding() {
+        let mut points = square();
+        assert!(!set_winding(&mut points, false));
+        assert_eq!(points, square());
+        assert!(set_winding(&mut points, true));
+        assert!(is_clockwise(&points));
+    }
+
+    fn triangles_area(points: &[Vector2<f32>], triangles: &[[usize; 3]]) -> f32 {
+        triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let area = signed_area(&[points[a], points[b], points[c]]);
+                assert!(area >= 0.0);
+                area
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_triangulate() {
+        let points = square();
+        let triangles = triangulate(&points);
+        assert_eq!(triangles.len(), 2);
+        assert_eq!(triangles_area(&points, &triangles), 4.0);
+
+        // Concave L shape, given clockwise
+        let mut l_shape = vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(3.0, 0.0),
+            Vector2::new(3.0, 1.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(1.0, 3.0),
+            Vector2::new(0.0, 3.0),
+        ];
+        l_shape.reverse();
+        let triangles = triangulate(&l_shape);
+        assert_eq!(triangles.len(), 4);
+        assert_eq!(triangles_area(&l_shape, &triangles), 5.0);
+
+        assert!(triangulate(&square()[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_triangulate_with_holes() {
+        let outline = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(10.0, 10.0),
+            Vector2::new(0.0, 10.0),
+        ];
+        let hole_a = [
+            Vector2::new(2.0, 2.0),
+            Vector2::new(4.0, 2.0),
+            Vector2::new(4.0, 4.0),
+            Vector2::new(2.0, 4.0),
+        ];
+        let hole_b = [
+            Vector2::new(6.0, 5.0),
+            Vector2::new(8.0, 6.0),
+            Vector2::new(7.0, 8.0),
+        ];
+        let triangles = triangulate_with_holes(&outline, &[&hole_a, &hole_b]);
+        // n + 2h - 2 triangles for n vertices and h holes
+        assert_eq!(triangles.len(), 11 + 4 - 2);
+
+        let points: Vec<_> = outline
+            .iter()
+            .chain(&hole_a)
+            .chain(&hole_b)
+            .copied()
+            .collect();
+        let expected = 100.0 - 4.0 - signed_area(&hole_b);
+        assert!((triangles_area(&points, &triangles) - expected).abs() < 1e-4);
+    }
+}
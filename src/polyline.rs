@@ -0,0 +1,387 @@
+//! Algorithms over polylines given as slices of points.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::components::distance;
+use crate::grid::segment_clear;
+use crate::{Components, Vector2};
+
+/// Largest distance from a vertex of `a` to the closest vertex of `b`.
+/// Returns `None` if either polyline is empty.
+pub fn hausdorff_directed<V: Components>(a: &[V], b: &[V]) -> Option<f32> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    let farthest = a
+        .iter()
+        .map(|p| {
+            b.iter()
+                .map(|q| distance(p, q))
+                .fold(f32::INFINITY, f32::min)
+        })
+        .fold(0.0, f32::max);
+    Some(farthest)
+}
+
+/// Symmetric Hausdorff distance between the vertex sets of two polylines.
+pub fn hausdorff<V: Components>(a: &[V], b: &[V]) -> Option<f32> {
+    Some(hausdorff_directed(a, b)?.max(hausdorff_directed(b, a)?))
+}
+
+/// Discrete Fréchet distance: the shortest leash that lets two walkers traverse
+/// `a` and `b` vertex by vertex, both only moving forward. Unlike the Hausdorff
+/// distance this respects the order of the points, so a path walked in reverse
+/// does not match. Returns `None` if either polyline is empty.
+pub fn frechet<V: Components>(a: &[V], b: &[V]) -> Option<f32> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    // Dynamic programming over the coupling table, one row at a time
+    let mut prev = vec![0.0f32; b.len()];
+    let mut row = vec![0.0f32; b.len()];
+    for (i, p) in a.iter().enumerate() {
+        for (j, q) in b.iter().enumerate() {
+            let d = distance(p, q);
+            row[j] = match (i, j) {
+                (0, 0) => d,
+                (0, _) => row[j - 1].max(d),
+                (_, 0) => prev[0].max(d),
+                _ => prev[j].min(prev[j - 1]).min(row[j - 1]).max(d),
+            };
+        }
+        std::mem::swap(&mut prev, &mut row);
+    }
+    Some(prev[b.len() - 1])
+}
+
+// Area of the triangle (a, b, c) in any dimension, from |u|^2 |v|^2 - (u.v)^2
+fn triangle_area<V: Components>(a: &V, b: &V, c: &V) -> f32 {
+    let (mut uu, mut vv, mut uv) = (0.0, 0.0, 0.0);
+    for i in 0..V::DIM {
+        let u = b.component(i) - a.component(i);
+        let v = c.component(i) - a.component(i);
+        uu += u * u;
+        vv += v * v;
+        uv += u * v;
+    }
+    0.5 * (uu * vv - uv * uv).max(0.0).sqrt()
+}
+
+#[derive(PartialEq)]
+struct Candidate {
+    area: f32,
+    index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    // Reversed so the max-heap pops the smallest area first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .total_cmp(&self.area)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+// Visvalingam-Whyatt elimination. Returns the effective area of every vertex
+// and the step at which it was removed; endpoints are never removed and get
+// an infinite area and the last steps.
+fn visvalingam_ranks<V: Components>(points: &[V]) -> (Vec<f32>, Vec<usize>) {
+    let n = points.len();
+    let mut areas = vec![f32::INFINITY; n];
+    let mut removed_at = vec![usize::MAX; n];
+    if n < 3 {
+        return (areas, (0..n).collect());
+    }
+
+    let mut prev: Vec<usize> = (0..n).map(|i| i.wrapping_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=n).collect();
+    let mut heap = BinaryHeap::new();
+    for i in 1..n - 1 {
+        areas[i] = triangle_area(&points[i - 1], &points[i], &points[i + 1]);
+        heap.push(Candidate {
+            area: areas[i],
+            index: i,
+        });
+    }
+
+    let mut step = 0;
+    let mut max_area = 0.0f32;
+    while let Some(Candidate { area, index }) = heap.pop() {
+        // Skip entries made stale by a neighbour removal
+        if removed_at[index] != usize::MAX || area != areas[index] {
+            continue;
+        }
+        // Effective areas never decrease, so removal order matches area order
+        max_area = max_area.max(area);
+        areas[index] = max_area;
+        removed_at[index] = step;
+        step += 1;
+
+        let (p, q) = (prev[index], next[index]);
+        next[p] = q;
+        prev[q] = p;
+        for i in [p, q] {
+            if i != 0 && i != n - 1 {
+                areas[i] = triangle_area(&points[prev[i]], &points[i], &points[next[i]]);
+                heap.push(Candidate {
+                    area: areas[i],
+                    index: i,
+                });
+            }
+        }
+    }
+    removed_at[0] = n - 2;
+    removed_at[n - 1] = n - 1;
+    (areas, removed_at)
+}
+
+/// Visvalingam-Whyatt simplification: repeatedly drops the vertex forming the
+/// smallest triangle with its neighbours, until every remaining vertex has an
+/// effective area of at least `min_area`. Endpoints are always kept.
+pub fn simplify_visvalingam<V: Components>(points: &[V], min_area: f32) -> Vec<V> {
+    let (areas, _) = visvalingam_ranks(points);
+    points
+        .iter()
+        .zip(areas)
+        .filter(|(_, area)| *area >= min_area)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+/// Visvalingam-Whyatt simplification down to at most `count` points (at least
+/// the two endpoints are kept).
+pub fn simplify_visvalingam_to_count<V: Components>(points: &[V], count: usize) -> Vec<V> {
+    let (_, removed_at) = visvalingam_ranks(points);
+    let first_kept = points.len().saturating_sub(count.max(2));
+    points
+        .iter()
+        .zip(removed_at)
+        .filter(|(_, step)| *step >= first_kept)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+/// Chaikin corner cutting: each iteration replaces every interior corner by two
+/// points a quarter of the way along its adjacent segments. Endpoints are kept.
+pub fn chaikin(points: &[Vector2<f32>], iterations: u32) -> Vec<Vector2<f32>> {
+    chaikin_constrained(points, iterations, |_, _| true)
+}
+
+// Chaikin corner cutting that only cuts a corner if `allowed` accepts the new
+// chord between the two cut points
+fn chaikin_constrained<F>(points: &[Vector2<f32>], iterations: u32, allowed: F) -> Vec<Vector2<f32>>
+where
+    F: Fn(Vector2<f32>, Vector2<f32>) -> bool,
+{
+    let mut current = points.to_vec();
+    for _ in 0..iterations {
+        if current.len() < 3 {
+            break;
+        }
+        let mut next = vec![current[0]];
+        for w in current.windows(3) {
+            let (a, v, b) = (w[0], w[1], w[2]);
+            let q = v + (a - v) * 0.25;
+            let r = v + (b - v) * 0.25;
+            if allowed(q, r) {
+                next.push(q);
+                next.push(r);
+            } else {
+                next.push(v);
+            }
+        }
+        next.push(current[current.len() - 1]);
+        current = next;
+    }
+    current
+}
+
+/// Turns a path of grid cells (for example from A*) into a smooth polyline.
+///
+/// The path is first shortened by string pulling, skipping waypoints while the
+/// straight line between cell centers stays clear of `blocked` cells, and then
+/// rounded with `iterations` of Chaikin corner cutting, where a corner is only
+/// cut if the shortcut stays clear as well. Output points are cell centers
+/// scaled by `cell_size`.
+pub fn smooth_grid_path<F>(
+    path: &[Vector2<i32>],
+    blocked: F,
+    iterations: u32,
+    cell_size: f32,
+) -> Vec<Vector2<f32>>
+where
+    F: Fn(Vector2<i32>) -> bool,
+{
+    let center = |c: Vector2<i32>| Vector2::new(c.x as f32 + 0.5, c.y as f32 + 0.5);
+    if path.is_empty() {
+        return Vec::new();
+    }
+
+    let mut pulled = vec![center(path[0])];
+    let mut anchor = 0;
+    while anchor < path.len() - 1 {
+        let mut reach = anchor + 1;
+        while reach + 1 < path.len()
+            && segment_clear(center(path[anchor]), center(path[reach + 1]), &blocked)
+        {
+            reach += 1;
+        }
+        pulled.push(center(path[reach]));
+        anchor = reach;
+    }
+
+    chaikin_constrained(&pulled, iterations, |q, r| segment_clear(q, r, &blocked))
+        .into_iter()
+        .map(|p| p * cell_size)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector2, Vector3};
+
+    #[test]
+    fn test_hausdorff() {
+        let a = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(2.0, 0.0),
+        ];
+        let b = [Vector2::new(0.0, 1.0), Vector2::new(2.0, 1.0)];
+        assert_eq!(hausdorff_directed(&b, &a), Some(1.0));
+        assert_eq!(hausdorff_directed(&a, &b), Some(2.0f32.sqrt()));
+        assert_eq!(hausdorff(&a, &b), Some(2.0f32.sqrt()));
+        assert_eq!(hausdorff::<Vector2<f32>>(&a, &[]), None);
+    }
+
+    #[test]
+    fn test_frechet() {
+        let a = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.0),
+        ];
+        let shifted: Vec<Vector3<f32>> =
+            a.iter().map(|p| *p + Vector3::new(0.0, 0.5, 0.0)).collect();
+        assert_eq!(frechet(&a, &shifted), Some(0.5));
+        assert_eq!(frechet(&a, &a), Some(0.0));
+    }
+
+    #[test]
+    fn test_frechet_respects_order() {
+        let a = [Vector2::new(0.0, 0.0), Vector2::new(4.0, 0.0)];
+        let reversed = [Vector2::new(4.0, 0.0), Vector2::new(0.0, 0.0)];
+        assert_eq!(hausdorff(&a, &reversed), Some(0.0));
+        assert_eq!(frechet(&a, &reversed), Some(4.0));
+    }
+
+    fn zigzag() -> Vec<Vector2<f32>> {
+        vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.1),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(3.0, 5.0),
+            Vector2::new(4.0, 0.0),
+            Vector2::new(5.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn test_simplify_visvalingam() {
+        let simplified = simplify_visvalingam(&zigzag(), 1.0);
+        assert_eq!(
+            simplified,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(2.0, 0.0),
+                Vector2::new(3.0, 5.0),
+                Vector2::new(4.0, 0.0),
+                Vector2::new(5.0, 0.0),
+            ]
+        );
+        assert_eq!(simplify_visvalingam(&zigzag(), 0.0), zigzag());
+        assert_eq!(simplify_visvalingam(&zigzag(), f32::MAX).len(), 2);
+    }
+
+    #[test]
+    fn test_simplify_visvalingam_to_count() {
+        let simplified = simplify_visvalingam_to_count(&zigzag(), 3);
+        assert_eq!(
+            simplified,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(3.0, 5.0),
+                Vector2::new(5.0, 0.0)
+            ]
+        );
+        assert_eq!(simplify_visvalingam_to_count(&zigzag(), 0).len(), 2);
+        assert_eq!(simplify_visvalingam_to_count(&zigzag(), 10), zigzag());
+    }
+
+    #[test]
+    fn test_chaikin() {
+        let points = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(4.0, 0.0),
+            Vector2::new(4.0, 4.0),
+        ];
+        assert_eq!(
+            chaikin(&points, 1),
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(3.0, 0.0),
+                Vector2::new(4.0, 1.0),
+                Vector2::new(4.0, 4.0),
+            ]
+        );
+        assert_eq!(chaikin(&points, 3).len(), 10);
+        assert_eq!(chaikin(&points[..2], 3), points[..2].to_vec());
+    }
+
+    #[test]
+    fn test_smooth_grid_path_pulls_string() {
+        // Staircase through an empty grid collapses to a straight line
+        let path = [
+            Vector2::new(0, 0),
+            Vector2::new(1, 0),
+            Vector2::new(1, 1),
+            Vector2::new(2, 1),
+            Vector2::new(2, 2),
+        ];
+        let smoothed = smooth_grid_path(&path, |_| false, 2, 2.0);
+        assert_eq!(
+            smoothed,
+            vec![Vector2::new(1.0, 1.0), Vector2::new(5.0, 5.0)]
+        );
+    }
+
+    #[test]
+    fn test_smooth_grid_path_avoids_obstacles() {
+        // L-shaped path around the blocked cell (1, 1)
+        let blocked = |c: Vector2<i32>| c == Vector2::new(1, 1) || c == Vector2::new(0, 1);
+        let path = [
+            Vector2::new(0, 0),
+            Vector2::new(1, 0),
+            Vector2::new(2, 0),
+            Vector2::new(2, 1),
+            Vector2::new(2, 2),
+        ];
+        let smoothed = smooth_grid_path(&path, blocked, 3, 1.0);
+        assert_eq!(smoothed[0], Vector2::new(0.5, 0.5));
+        assert_eq!(*smoothed.last().unwrap(), Vector2::new(2.5, 2.5));
+        for w in smoothed.windows(2) {
+            assert!(segment_clear(w[0], w[1], &blocked));
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Random sampling of directions, for spread patterns and Monte Carlo
+//! effects. Requires the `rand` feature.
+
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use crate::Vector3;
+
+/// Unit vector uniformly distributed over the cone of directions within
+/// `half_angle` radians of `direction`, e.g. for shotgun spread or soft
+/// shadow rays. `direction` need not be normalized.
+///
+/// Picking the angle from the axis uniformly would bunch samples toward the
+/// axis; instead the cosine of that angle is uniform, which spreads samples
+/// evenly over the spherical cap.
+pub fn random_in_cone<R: Rng + ?Sized>(
+    direction: &Vector3<f32>,
+    half_angle: f32,
+    rng: &mut R,
+) -> Vector3<f32> {
+    let axis = direction.normalize();
+    let cos_max = half_angle.min(std::f32::consts::PI).cos();
+    let cos_theta = 1.0 - rng.gen::<f32>() * (1.0 - cos_max);
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let (sin_phi, cos_phi) = (rng.gen::<f32>() * TAU).sin_cos();
+
+    let u = axis.any_orthogonal().normalize();
+    let v = axis.cross(&u);
+    axis * cos_theta + (u * cos_phi + v * sin_phi) * sin_theta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_random_in_cone() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let direction = Vector3::new(0.0, 0.0, -3.0);
+        let half_angle = 0.2f32;
+        let mut mean = Vector3::new(0.0, 0.0, 0.0);
+        for _ in 0..1000 {
+            let d = random_in_cone(&direction, half_angle, &mut rng);
+            assert!(d.is_normalized(1e-5));
+            assert!(d.angle_between(&direction) <= half_angle + 1e-4);
+            mean += d;
+        }
+        // Centered on the axis
+        let mean = mean * 1e-3;
+        assert!(mean.x.abs() < 0.01 && mean.y.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_uniform_over_cap() {
+        // Half of a hemisphere's area lies above 60 degrees from the pole
+        let mut rng = StdRng::seed_from_u64(1);
+        let up = Vector3::new(0.0, 1.0, 0.0);
+        let n = 4000;
+        let near_pole = (0..n)
+            .filter(|_| random_in_cone(&up, std::f32::consts::FRAC_PI_2, &mut rng).y > 0.5)
+            .count();
+        assert!((near_pole as f32 / n as f32 - 0.5).abs() < 0.03);
+    }
+}
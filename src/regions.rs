@@ -0,0 +1,221 @@
+//! Segmentation of scalar grids, such as heightmaps or moisture maps, into
+//! labeled regions, for procedural biome assignment and terrain analysis.
+//!
+//! Grids are row-major slices `width` cells wide, and cells are addressed as
+//! `Vector2<i32>` with x the column and y the row. Cells connect to their four
+//! edge neighbors. A `width` of zero is an empty grid with no cells.
+
+use std::collections::VecDeque;
+
+use crate::Vector2;
+
+/// Summary of one labeled region, in cell coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Region {
+    /// Number of cells in the region. Zero for a seed that claimed nothing.
+    pub cells: usize,
+    /// Mean cell coordinate.
+    pub centroid: Vector2<f32>,
+    /// Inclusive bounding box of the cells.
+    pub min: Vector2<i32>,
+    pub max: Vector2<i32>,
+}
+
+/// Per-cell labels and the regions they index.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segmentation {
+    pub width: usize,
+    /// Label of each cell, the index into `regions`, or `None` if unclaimed.
+    pub labels: Vec<Option<usize>>,
+    pub regions: Vec<Region>,
+}
+
+impl Segmentation {
+    fn new(width: usize, labels: Vec<Option<usize>>, count: usize) -> Segmentation {
+        let mut sums = vec![(0usize, Vector2::new(0.0, 0.0)); count];
+        let mut regions = vec![
+            Region {
+                cells: 0,
+                centroid: Vector2::default(),
+                min: Vector2::new(i32::MAX, i32::MAX),
+                max: Vector2::new(i32::MIN, i32::MIN),
+            };
+            count
+        ];
+        for (i, label) in labels.iter().enumerate() {
+            let Some(label) = *label else { continue };
+            let (x, y) = ((i % width) as i32, (i / width) as i32);
+            let r = &mut regions[label];
+            r.min = Vector2::new(r.min.x.min(x), r.min.y.min(y));
+            r.max = Vector2::new(r.max.x.max(x), r.max.y.max(y));
+            sums[label].0 += 1;
+            sums[label].1 += Vector2::new(x as f32, y as f32);
+        }
+        for (r, (n, sum)) in regions.iter_mut().zip(sums) {
+            r.cells = n;
+            if n > 0 {
+                r.centroid = sum * (n as f32).recip();
+            } else {
+                r.min = Vector2::default();
+                r.max = Vector2::default();
+            }
+        }
+        Segmentation {
+            width,
+            labels,
+            regions,
+        }
+    }
+
+    /// Label of the cell, `None` if unclaimed or outside the grid.
+    pub fn label(&self, cell: Vector2<i32>) -> Option<usize> {
+        let height = self.labels.len().checked_div(self.width).unwrap_or(0);
+        if cell.x < 0 || cell.y < 0 || cell.x as usize >= self.width || cell.y as usize >= height {
+            return None;
+        }
+        self.labels[cell.y as usize * self.width + cell.x as usize]
+    }
+}
+
+/// Indices of the edge neighbors of cell `i` in a grid of the given size.
+fn neighbors(i: usize, width: usize, height: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = (i % width, i / width);
+    [
+        (x > 0).then(|| i - 1),
+        (x + 1 < width).then_some(i + 1),
+        (y > 0).then(|| i - width),
+        (y + 1 < height).then_some(i + width),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Grows one region from each seed, all at the same pace, adding neighboring
+/// cells whose value is within `tolerance` of the seed's value. Region `k`
+/// belongs to `seeds[k]`; where regions meet, the first to arrive keeps the
+/// cell. Seeds outside the grid or on a claimed cell get an empty region.
+pub fn grow_regions(
+    values: &[f32],
+    width: usize,
+    seeds: &[Vector2<i32>],
+    tolerance: f32,
+) -> Segmentation {
+    if width == 0 {
+        return Segmentation::new(0, Vec::new(), seeds.len());
+    }
+    let height = values.len() / width;
+    let mut labels = vec![None; values.len()];
+    let mut queue = VecDeque::new();
+    for (label, seed) in seeds.iter().enumerate() {
+        let inside = (0..width as i32).contains(&seed.x) && (0..height as i32).contains(&seed.y);
+        if !inside {
+            continue;
+        }
+        let i = seed.y as usize * width + seed.x as usize;
+        if labels[i].is_none() {
+            labels[i] = Some(label);
+            queue.push_back((i, values[i]));
+        }
+    }
+    while let Some((i, reference)) = queue.pop_front() {
+        for n in neighbors(i, width, height) {
+            if labels[n].is_none() && (values[n] - reference).abs() <= tolerance {
+                labels[n] = labels[i];
+                queue.push_back((n, reference));
+            }
+        }
+    }
+    Segmentation::new(width, labels, seeds.len())
+}
+
+/// Splits the grid into catchment basins: every cell drains into the basin of
+/// its lowest already-flooded neighbor as the level rises, and a cell with no
+/// flooded neighbor starts a new basin at a local minimum. Every cell gets a
+/// label; there are no dividing watershed lines. Ties between equal values
+/// are broken by cell index, so a plateau can split into several basins.
+pub fn watershed(values: &[f32], width: usize) -> Segmentation {
+    if width == 0 {
+        return Segmentation::new(0, Vec::new(), 0);
+    }
+    let height = values.len() / width;
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]).then(a.cmp(&b)));
+
+    let mut labels: Vec<Option<usize>> = vec![None; values.len()];
+    let mut count = 0;
+    for i in order {
+        let lowest = neighbors(i, width, height)
+            .filter(|&n| labels[n].is_some())
+            .min_by(|&a, &b| values[a].total_cmp(&values[b]));
+        labels[i] = match lowest {
+            Some(n) => labels[n],
+            None => {
+                count += 1;
+                Some(count - 1)
+            }
+        };
+    }
+    Segmentation::new(width, labels, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    const TERRAIN: [f32; 20] = [
+        1.0, 1.0, 5.0, 2.0, 2.0,
+        1.0, 3.0, 6.0, 0.0, 2.0,
+        4.0, 4.0, 6.0, 2.0, 2.0,
+        4.0, 4.0, 6.0, 9.0, 9.0,
+    ];
+
+    #[test]
+    fn test_grow_regions() {
+        let seeds = [
+            Vector2::new(0, 0),
+            Vector2::new(3, 1),
+            Vector2::new(1, 3),
+            Vector2::new(9, 9),
+        ];
+        let s = grow_regions(&TERRAIN, 5, &seeds, 0.5);
+        assert_eq!(s.regions[0].cells, 3);
+        assert_eq!(s.regions[0].max, Vector2::new(1, 1));
+        assert_eq!(s.regions[1].cells, 1);
+        assert_eq!(s.regions[2].cells, 4);
+        assert_eq!(s.regions[2].centroid, Vector2::new(0.5, 2.5));
+        assert_eq!(s.regions[3].cells, 0);
+        assert_eq!(s.label(Vector2::new(1, 1)), None);
+        assert_eq!(s.label(Vector2::new(0, 1)), Some(0));
+
+        let s = grow_regions(&TERRAIN, 5, &seeds[1..2], 2.0);
+        assert_eq!(s.regions[0].cells, 6);
+        assert_eq!(s.regions[0].min, Vector2::new(3, 0));
+        assert_eq!(s.regions[0].max, Vector2::new(4, 2));
+    }
+
+    #[test]
+    fn test_watershed() {
+        let s = watershed(&TERRAIN, 5);
+        assert_eq!(s.regions.len(), 2);
+        assert!(s.labels.iter().all(|l| l.is_some()));
+        // The right basin drains to the 0 at (3, 1), the left to the 1s
+        let right = s.label(Vector2::new(3, 1));
+        let left = s.label(Vector2::new(0, 0));
+        assert_ne!(right, left);
+        assert_eq!(s.label(Vector2::new(3, 2)), right);
+        assert_eq!(s.label(Vector2::new(1, 3)), left);
+        let cells: usize = s.regions.iter().map(|r| r.cells).sum();
+        assert_eq!(cells, TERRAIN.len());
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let s = grow_regions(&TERRAIN, 0, &[Vector2::new(0, 0)], 1.0);
+        assert!(s.labels.is_empty());
+        assert_eq!(s.regions[0].cells, 0);
+        assert_eq!(s.label(Vector2::new(0, 0)), None);
+        let s = watershed(&TERRAIN, 0);
+        assert!(s.labels.is_empty() && s.regions.is_empty());
+    }
+}
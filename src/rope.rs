@@ -0,0 +1,155 @@
+//! Rope swing constraints for grappling hooks and pendulums, in 2D or 3D.
+//!
+//! The rope is massless and inextensible but can go slack: it only acts when
+//! the body is at full length and moving outward.
+
+use crate::components::{delta, dot};
+use crate::Components;
+
+fn scale<V: Components>(v: &V, s: f32) -> V {
+    V::from_components(|i| v.component(i) * s)
+}
+
+fn add_scaled<V: Components>(a: &V, b: &V, s: f32) -> V {
+    V::from_components(|i| a.component(i) + b.component(i) * s)
+}
+
+/// Unit direction from the body at `position` toward the `anchor`, along which
+/// the rope pulls. `None` if they coincide.
+pub fn tension_direction<V: Components>(anchor: &V, position: &V) -> Option<V> {
+    let d = delta(position, anchor);
+    let len = dot(&d, &d).sqrt();
+    (len > 0.0).then(|| scale(&d, len.recip()))
+}
+
+/// Removes the radial part of `velocity`, leaving the motion tangent to the
+/// circle or sphere around `anchor` through `position`.
+pub fn swing_velocity<V: Components>(anchor: &V, position: &V, velocity: &V) -> V {
+    match tension_direction(anchor, position) {
+        Some(dir) => add_scaled(velocity, &dir, -dot(velocity, &dir)),
+        None => *velocity,
+    }
+}
+
+/// Enforces a rope of `length` after an unconstrained integration step.
+/// Returns the corrected `(position, velocity)`: a body beyond the rope's
+/// reach is pulled back onto the circle or sphere and loses its outward
+/// velocity. A body within reach is left alone since the rope is slack.
+pub fn constrain<V: Components>(anchor: &V, position: &V, velocity: &V, length: f32) -> (V, V) {
+    let out = delta(anchor, position);
+    let dist = dot(&out, &out).sqrt();
+    if dist <= length || dist == 0.0 {
+        return (*position, *velocity);
+    }
+    let dir = scale(&out, dist.recip());
+    let position = add_scaled(anchor, &dir, length);
+    let outward = dot(velocity, &dir);
+    let velocity = if outward > 0.0 {
+        add_scaled(velocity, &dir, -outward)
+    } else {
+        *velocity
+    };
+    (position, velocity)
+}
+
+/// Magnitude of the rope tension on a taut rope: the force needed to keep a
+/// body of `mass` on the circle, `m * (v_t^2 / r + g . r_out)`, with `r_out`
+/// the unit direction from the anchor to the body. Zero if the rope would go
+/// slack.
+pub fn tension<V: Components>(
+    mass: f32,
+    anchor: &V,
+    position: &V,
+    velocity: &V,
+    gravity: &V,
+) -> f32 {
+    let out = delta(anchor, position);
+    let r = dot(&out, &out).sqrt();
+    if r == 0.0 {
+        return 0.0;
+    }
+    let dir = scale(&out, r.recip());
+    let tangent = add_scaled(velocity, &dir, -dot(velocity, &dir));
+    (mass * (dot(&tangent, &tangent) / r + dot(gravity, &dir))).max(0.0)
+}
+
+/// Reels the rope in or out to `new_length`, moving the body radially.
+/// Returns the new `(position, velocity)`. Angular momentum about the anchor
+/// is conserved, so the swing speeds up as the rope shortens, and any radial
+/// velocity is kept.
+pub fn reel<V: Components>(anchor: &V, position: &V, velocity: &V, new_length: f32) -> (V, V) {
+    let out = delta(anchor, position);
+    let r = dot(&out, &out).sqrt();
+    if r == 0.0 || new_length <= 0.0 {
+        return (*position, *velocity);
+    }
+    let dir = scale(&out, r.recip());
+    let radial = dot(velocity, &dir);
+    let tangent = add_scaled(velocity, &dir, -radial);
+    let tangent = scale(&tangent, r / new_length);
+    (
+        add_scaled(anchor, &dir, new_length),
+        add_scaled(&tangent, &dir, radial),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector2, Vector3};
+
+    #[test]
+    fn test_constrain_and_swing() {
+        let anchor = Vector2::new(0.0, 0.0);
+        let velocity = Vector2::new(1.0, -2.0);
+        let (p, v) = constrain(&anchor, &Vector2::new(0.0, -4.0), &velocity, 2.0);
+        assert_eq!(p, Vector2::new(0.0, -2.0));
+        assert_eq!(v, Vector2::new(1.0, 0.0));
+
+        // Slack rope
+        let inside = Vector2::new(0.0, -1.0);
+        assert_eq!(
+            constrain(&anchor, &inside, &velocity, 2.0),
+            (inside, velocity)
+        );
+
+        assert_eq!(
+            swing_velocity(&anchor, &inside, &velocity),
+            Vector2::new(1.0, 0.0)
+        );
+        assert_eq!(
+            tension_direction(&anchor, &inside),
+            Some(Vector2::new(0.0, 1.0))
+        );
+        assert_eq!(tension_direction(&anchor, &anchor), None);
+    }
+
+    #[test]
+    fn test_tension() {
+        let anchor = Vector3::new(0.0, 5.0, 0.0);
+        let bottom = Vector3::new(0.0, 3.0, 0.0);
+        let gravity = Vector3::new(0.0, -10.0, 0.0);
+        // Hanging still: the rope carries the weight
+        let still = Vector3::new(0.0, 0.0, 0.0);
+        assert_eq!(tension(2.0, &anchor, &bottom, &still, &gravity), 20.0);
+        // Swinging through the bottom adds the centripetal force
+        let v = Vector3::new(4.0, 0.0, 0.0);
+        assert_eq!(tension(2.0, &anchor, &bottom, &v, &gravity), 36.0);
+        // Slow at the top of a loop: slack
+        let top = Vector3::new(0.0, 7.0, 0.0);
+        assert_eq!(tension(2.0, &anchor, &top, &v, &gravity), 0.0);
+    }
+
+    #[test]
+    fn test_reel() {
+        let anchor = Vector2::new(0.0, 0.0);
+        let (p, v) = reel(
+            &anchor,
+            &Vector2::new(0.0, -4.0),
+            &Vector2::new(3.0, -1.0),
+            2.0,
+        );
+        assert_eq!(p, Vector2::new(0.0, -2.0));
+        assert_eq!(v, Vector2::new(6.0, -1.0));
+    }
+}
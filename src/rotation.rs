@@ -0,0 +1,165 @@
+//! Rotations: `Rotation2` for the plane, and helpers for unit quaternions
+//! stored in a `Vector4<f32>` as `(x, y, z, w)`, with `w` the scalar part,
+//! until the crate has a dedicated quaternion type.
+
+use std::ops::Mul;
+
+use crate::{Float, Rad, Vector2, Vector3, Vector4};
+
+/// A rotation in the plane stored as the unit complex number `re + im i`,
+/// i.e. `(cos, sin)` of the angle. Composing and applying rotations takes a
+/// few multiplications and no trigonometry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rotation2<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T: Float> Rotation2<T> {
+    pub fn identity() -> Rotation2<T> {
+        Rotation2 {
+            re: T::ONE,
+            im: T::ZERO,
+        }
+    }
+
+    /// Counter-clockwise rotation by `angle`, given as `Rad` or `Deg`.
+    pub fn from_angle(angle: impl Into<Rad<T>>) -> Rotation2<T> {
+        let Rad(angle) = angle.into();
+        Rotation2 {
+            re: angle.cos(),
+            im: angle.sin(),
+        }
+    }
+
+    /// Angle in radians, in `[-pi, pi]`.
+    pub fn angle(&self) -> T {
+        self.im.atan2(self.re)
+    }
+
+    /// The rotation undoing this one.
+    pub fn inverse(&self) -> Rotation2<T> {
+        Rotation2 {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+
+    pub fn rotate(&self, v: &Vector2<T>) -> Vector2<T> {
+        Vector2::new(self.re * v.x - self.im * v.y, self.im * v.x + self.re * v.y)
+    }
+
+    /// Rescales to unit length, to remove drift after many compositions.
+    pub fn renormalize(&self) -> Rotation2<T> {
+        let len = (self.re * self.re + self.im * self.im).sqrt();
+        Rotation2 {
+            re: self.re / len,
+            im: self.im / len,
+        }
+    }
+}
+
+impl<T: Float> Default for Rotation2<T> {
+    fn default() -> Self {
+        Rotation2::identity()
+    }
+}
+
+/// `a * b` is the rotation `b` followed by `a`.
+impl<T: Float> Mul<Rotation2<T>> for Rotation2<T> {
+    type Output = Rotation2<T>;
+
+    fn mul(self, rhs: Rotation2<T>) -> Self::Output {
+        Rotation2 {
+            re: self.re * rhs.re - self.im * rhs.im,
+            im: self.re * rhs.im + self.im * rhs.re,
+        }
+    }
+}
+
+/// Hamilton product `a * b`: the rotation `b` followed by `a`.
+fn mul(a: &Vector4<f32>, b: &Vector4<f32>) -> Vector4<f32> {
+    Vector4::new(
+        a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+        a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+        a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+        a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+    )
+}
+
+/// Splits the rotation `q` into `(swing, twist)` with `q = swing * twist`:
+/// `twist` rotates about the unit `axis` only, and `swing` rotates about an
+/// axis perpendicular to it. Joint limits clamp the two separately, e.g. a
+/// cone limit on the swing and an angle range on the twist of a shoulder.
+///
+/// When `q` swings `axis` exactly around to its opposite, the twist is
+/// undefined and the identity is returned for it.
+pub fn swing_twist(q: &Vector4<f32>, axis: &Vector3<f32>) -> (Vector4<f32>, Vector4<f32>) {
+    let along = Vector3::new(q.x, q.y, q.z).dot(axis);
+    let p = *axis * along;
+    let len = (p.dot(&p) + q.w * q.w).sqrt();
+    let twist = if len > 1e-6 {
+        Vector4::new(p.x, p.y, p.z, q.w) * len.recip()
+    } else {
+        Vector4::new(0.0, 0.0, 0.0, 1.0)
+    };
+    let conjugate = Vector4::new(-twist.x, -twist.y, -twist.z, twist.w);
+    (mul(q, &conjugate), twist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Deg;
+
+    fn axis_angle(axis: Vector3<f32>, angle: f32) -> Vector4<f32> {
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        Vector4::new(axis.x * sin, axis.y * sin, axis.z * sin, cos)
+    }
+
+    fn close(a: Vector4<f32>, b: Vector4<f32>) -> bool {
+        let d = a - b;
+        d.dot(&d) < 1e-10
+    }
+
+    #[test]
+    fn test_rotation2() {
+        let quarter = Rotation2::<f64>::from_angle(Deg(90.0));
+        let v = quarter.rotate(&Vector2::new(1.0, 0.0));
+        assert!((v - Vector2::new(0.0, 1.0)).magnitude() < 1e-12);
+
+        let r = Rotation2::<f64>::from_angle(Rad(0.3)) * Rotation2::from_angle(Rad(0.5));
+        assert!((r.angle() - 0.8).abs() < 1e-12);
+        let back = (r * r.inverse()).rotate(&Vector2::new(2.0, -1.0));
+        assert!((back - Vector2::new(2.0, -1.0)).magnitude() < 1e-12);
+        assert_eq!(Rotation2::<f64>::default(), Rotation2::identity());
+    }
+
+    #[test]
+    fn test_swing_twist() {
+        let up = Vector3::new(0.0, 1.0, 0.0);
+        let twist = axis_angle(up, 0.8);
+        let swing = axis_angle(Vector3::new(0.6, 0.0, 0.8), 0.5);
+        let q = mul(&swing, &twist);
+        let (s, t) = swing_twist(&q, &up);
+        assert!(close(s, swing));
+        assert!(close(t, twist));
+        assert!(close(mul(&s, &t), q));
+
+        // Pure swing and pure twist
+        let (s, t) = swing_twist(&swing, &up);
+        assert!(close(s, swing));
+        assert!(close(t, Vector4::new(0.0, 0.0, 0.0, 1.0)));
+        let (s, t) = swing_twist(&twist, &up);
+        assert!(close(s, Vector4::new(0.0, 0.0, 0.0, 1.0)));
+        assert!(close(t, twist));
+    }
+
+    #[test]
+    fn test_swing_to_opposite() {
+        let flip = axis_angle(Vector3::new(1.0, 0.0, 0.0), std::f32::consts::PI);
+        let (s, t) = swing_twist(&flip, &Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(t, Vector4::new(0.0, 0.0, 0.0, 1.0));
+        assert!(close(s, flip));
+    }
+}
@@ -0,0 +1,106 @@
+//! Signed distance functions of primitive shapes, negative inside, and ways
+//! to combine them, for raymarching and collision queries.
+//!
+//! Shapes are centered at the origin; transform the sample point into the
+//! shape's local space to place them elsewhere, e.g. `sphere(p - center, r)`.
+//! The results are exact distances unless noted, so they can be used as
+//! safe step sizes for sphere tracing.
+
+use crate::{Vector2, Vector3};
+
+/// Sphere of `radius` around the origin.
+pub fn sphere(p: Vector3<f32>, radius: f32) -> f32 {
+    p.magnitude() - radius
+}
+
+/// Box with the given half extents along each axis.
+pub fn cuboid(p: Vector3<f32>, half_extents: Vector3<f32>) -> f32 {
+    let q = Vector3::new(
+        p.x.abs() - half_extents.x,
+        p.y.abs() - half_extents.y,
+        p.z.abs() - half_extents.z,
+    );
+    let outside = Vector3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0));
+    outside.magnitude() + q.x.max(q.y).max(q.z).min(0.0)
+}
+
+/// Segment from `a` to `b` swept by a sphere of `radius`.
+pub fn capsule(p: Vector3<f32>, a: Vector3<f32>, b: Vector3<f32>, radius: f32) -> f32 {
+    let (pa, ba) = (p - a, b - a);
+    let len_sq = ba.dot(&ba);
+    let t = if len_sq > 0.0 {
+        (pa.dot(&ba) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (pa - ba * t).magnitude() - radius
+}
+
+/// Torus around the y axis, with `major` the distance from the center to
+/// the middle of the tube and `minor` the tube radius.
+pub fn torus(p: Vector3<f32>, major: f32, minor: f32) -> f32 {
+    let ring = Vector2::new(p.x, p.z).magnitude() - major;
+    Vector2::new(ring, p.y).magnitude() - minor
+}
+
+/// Inside either shape.
+pub fn union(a: f32, b: f32) -> f32 {
+    a.min(b)
+}
+
+/// Inside both shapes. The result is a bound rather than the exact distance.
+pub fn intersection(a: f32, b: f32) -> f32 {
+    a.max(b)
+}
+
+/// Inside `a` but not `b`. The result is a bound rather than the exact
+/// distance.
+pub fn subtraction(a: f32, b: f32) -> f32 {
+    a.max(-b)
+}
+
+/// Union blended over a distance of about `k`, which fillets the seam
+/// between the shapes (polynomial smooth minimum). `k = 0` is `union`.
+pub fn smooth_min(a: f32, b: f32, k: f32) -> f32 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitives() {
+        let p = Vector3::new(0.0, 3.0, 4.0);
+        assert_eq!(sphere(p, 2.0), 3.0);
+        assert_eq!(sphere(Vector3::default(), 2.0), -2.0);
+
+        let half = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(cuboid(Vector3::new(4.0, 0.0, 0.0), half), 3.0);
+        assert_eq!(cuboid(Vector3::new(4.0, 6.0, 0.0), half), 5.0);
+        assert_eq!(cuboid(Vector3::new(0.5, 0.0, 0.0), half), -0.5);
+
+        let (a, b) = (Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 4.0, 0.0));
+        assert_eq!(capsule(Vector3::new(3.0, 2.0, 0.0), a, b, 1.0), 2.0);
+        assert_eq!(capsule(Vector3::new(0.0, 7.0, 0.0), a, b, 1.0), 2.0);
+        assert_eq!(capsule(Vector3::new(0.0, -2.0, 0.0), a, a, 1.0), 1.0);
+
+        assert_eq!(torus(Vector3::new(3.0, 0.0, 0.0), 3.0, 0.5), -0.5);
+        assert_eq!(torus(Vector3::new(0.0, 4.0, 3.0), 3.0, 0.5), 3.5);
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(union(1.0, -2.0), -2.0);
+        assert_eq!(intersection(1.0, -2.0), 1.0);
+        assert_eq!(subtraction(-1.0, -2.0), 2.0);
+        assert_eq!(smooth_min(1.0, 3.0, 0.0), 1.0);
+        // Far apart values are unaffected, close ones are pulled below both
+        assert_eq!(smooth_min(1.0, 3.0, 0.5), 1.0);
+        assert!(smooth_min(1.0, 1.0, 0.5) < 1.0);
+    }
+}
@@ -0,0 +1,133 @@
+//! Real spherical harmonics up to band 2 (9 coefficients), the usual
+//! representation of low-frequency lighting in ambient probes.
+//!
+//! Coefficients are ordered by band, `[Y00, Y1-1, Y10, Y11, Y2-2, Y2-1, Y20,
+//! Y21, Y22]`, with the constants from Sloan, "Stupid Spherical Harmonics
+//! Tricks". Coefficients can be scalars or colors stored as `Vector3<f32>`.
+
+use std::f32::consts::PI;
+use std::ops::{Add, Mul};
+
+use crate::Vector3;
+
+/// Values of the nine basis functions in the unit direction `dir`.
+pub fn eval_basis_l2(dir: Vector3<f32>) -> [f32; 9] {
+    let Vector3 { x, y, z } = dir;
+    [
+        0.282_095,
+        0.488_603 * y,
+        0.488_603 * z,
+        0.488_603 * x,
+        1.092_548 * x * y,
+        1.092_548 * y * z,
+        0.315_392 * (3.0 * z * z - 1.0),
+        1.092_548 * x * z,
+        0.546_274 * (x * x - y * y),
+    ]
+}
+
+/// Projects a function given by `(direction, value)` samples onto the basis.
+/// The unit directions must be uniformly distributed over the sphere, so each
+/// sample stands for an equal share `4 pi / n` of the solid angle.
+pub fn project<V, I>(samples: I) -> [V; 9]
+where
+    V: Copy + Default + Add<Output = V> + Mul<f32, Output = V>,
+    I: IntoIterator<Item = (Vector3<f32>, V)>,
+{
+    let mut coeffs = [V::default(); 9];
+    let mut n = 0;
+    for (dir, value) in samples {
+        for (c, y) in coeffs.iter_mut().zip(eval_basis_l2(dir)) {
+            *c = *c + value * y;
+        }
+        n += 1;
+    }
+    if n > 0 {
+        let weight = 4.0 * PI / n as f32;
+        for c in &mut coeffs {
+            *c = *c * weight;
+        }
+    }
+    coeffs
+}
+
+/// Value of the function represented by `coeffs` in the unit direction `dir`.
+pub fn reconstruct<V>(coeffs: &[V; 9], dir: Vector3<f32>) -> V
+where
+    V: Copy + Default + Add<Output = V> + Mul<f32, Output = V>,
+{
+    coeffs
+        .iter()
+        .zip(eval_basis_l2(dir))
+        .fold(V::default(), |sum, (&c, y)| sum + c * y)
+}
+
+/// Convolves radiance coefficients with the clamped cosine lobe, turning them
+/// into irradiance: reconstructing the result in a surface normal gives the
+/// light arriving on that surface. Divide by pi for the diffuse radiance of a
+/// white Lambertian surface.
+pub fn convolve_cosine<V>(coeffs: &[V; 9]) -> [V; 9]
+where
+    V: Copy + Mul<f32, Output = V>,
+{
+    const BAND: [f32; 3] = [PI, 2.0 * PI / 3.0, PI / 4.0];
+    let band = |i: usize| match i {
+        0 => BAND[0],
+        1..=3 => BAND[1],
+        _ => BAND[2],
+    };
+    std::array::from_fn(|i| coeffs[i] * band(i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Evenly spread unit directions on a Fibonacci spiral.
+    fn sphere_points(n: usize) -> impl Iterator<Item = Vector3<f32>> {
+        let golden = PI * (3.0 - 5f32.sqrt());
+        (0..n).map(move |i| {
+            let z = 1.0 - (2.0 * i as f32 + 1.0) / n as f32;
+            let r = (1.0 - z * z).sqrt();
+            let (sin, cos) = (golden * i as f32).sin_cos();
+            Vector3::new(r * cos, r * sin, z)
+        })
+    }
+
+    #[test]
+    fn test_basis() {
+        let up = eval_basis_l2(Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(up[2], 0.488_603);
+        assert_eq!(up[6], 0.630_784);
+        assert_eq!(up[3], 0.0);
+        // Orthonormal: the squared basis integrates to one over the sphere
+        let n = 4000;
+        let mut sums = [0.0; 9];
+        for dir in sphere_points(n) {
+            for (s, y) in sums.iter_mut().zip(eval_basis_l2(dir)) {
+                *s += y * y * 4.0 * PI / n as f32;
+            }
+        }
+        for s in sums {
+            assert!((s - 1.0).abs() < 1e-2, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_project_and_reconstruct() {
+        let f = |d: Vector3<f32>| 1.0 + d.x - 0.5 * d.y * d.z;
+        let coeffs = project(sphere_points(4000).map(|d| (d, f(d))));
+        for dir in [
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.6, 0.8),
+            Vector3::new(0.0, -0.8, 0.6),
+        ] {
+            assert!((reconstruct(&coeffs, dir) - f(dir)).abs() < 1e-2);
+        }
+
+        // Uniform white light from every direction: irradiance pi everywhere
+        let sky = project(sphere_points(1000).map(|d| (d, Vector3::new(1.0, 1.0, 1.0))));
+        let irradiance = reconstruct(&convolve_cosine(&sky), Vector3::new(0.0, 1.0, 0.0));
+        assert!((irradiance - Vector3::new(PI, PI, PI)).magnitude() < 1e-3);
+    }
+}
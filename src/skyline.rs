@@ -0,0 +1,148 @@
+//! Side-view silhouettes of voxel grids: collapsing a 3D occupancy grid along
+//! a depth axis into per-column height ranges, and tracing their top or
+//! bottom outline as polylines, for 2D collision and minimap silhouettes.
+//!
+//! The occupancy grid is a slice of `size.x * size.y * size.z` cells indexed
+//! by `x + size.x * (y + size.y * z)`.
+
+use crate::{Vector2, Vector3};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    fn index(self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        }
+    }
+}
+
+/// Collapses the grid along `depth` and reports, for each cell along the
+/// remaining axis other than `up`, the lowest and highest occupied cell
+/// along `up` as `(min, max)`, or `None` for an empty column.
+///
+/// Panics if `depth` and `up` are the same axis.
+pub fn column_extents(
+    occupied: &[bool],
+    size: Vector3<usize>,
+    depth: Axis,
+    up: Axis,
+) -> Vec<Option<(usize, usize)>> {
+    assert_ne!(depth, up, "depth and up must be different axes");
+    let dims = [size.x, size.y, size.z];
+    let (d, u) = (depth.index(), up.index());
+    let c = 3 - d - u;
+    let mut columns = vec![None; dims[c]];
+    for (i, column) in columns.iter_mut().enumerate() {
+        for h in 0..dims[u] {
+            let mut cell = [0; 3];
+            cell[c] = i;
+            cell[u] = h;
+            let filled = (0..dims[d]).any(|k| {
+                cell[d] = k;
+                occupied[cell[0] + size.x * (cell[1] + size.y * cell[2])]
+            });
+            if filled {
+                *column = Some(match *column {
+                    Some((min, _)) => (min, h),
+                    None => (h, h),
+                });
+            }
+        }
+    }
+    columns
+}
+
+/// Traces the stepped outline over the tops (or under the bottoms, with
+/// `top` false) of the columns, in cell units with column `i` spanning
+/// `[i, i + 1]`. Each run of non-empty columns gives one polyline from left
+/// to right, with a point at each corner.
+pub fn skyline(columns: &[Option<(usize, usize)>], top: bool) -> Vec<Vec<Vector2<f32>>> {
+    let mut lines = Vec::new();
+    let mut line: Vec<Vector2<f32>> = Vec::new();
+    for (i, column) in columns.iter().enumerate() {
+        let Some((min, max)) = *column else {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            continue;
+        };
+        let h = if top { max + 1 } else { min } as f32;
+        let x = i as f32;
+        match line.last_mut() {
+            // Same height as the previous column: extend the flat segment
+            Some(last) if last.y == h => last.x = x + 1.0,
+            _ => {
+                line.push(Vector2::new(x, h));
+                line.push(Vector2::new(x + 1.0, h));
+            }
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 4 x 3 x 2 grid with y up.
+    fn grid() -> (Vec<bool>, Vector3<usize>) {
+        let size = Vector3::new(4, 3, 2);
+        let mut occupied = vec![false; 24];
+        let mut fill = |x: usize, y: usize, z: usize| occupied[x + 4 * (y + 3 * z)] = true;
+        fill(0, 0, 0);
+        fill(1, 0, 1);
+        fill(1, 1, 0);
+        fill(1, 2, 1);
+        fill(3, 1, 1);
+        (occupied, size)
+    }
+
+    #[test]
+    fn test_column_extents() {
+        let (occupied, size) = grid();
+        assert_eq!(
+            column_extents(&occupied, size, Axis::Z, Axis::Y),
+            vec![Some((0, 0)), Some((0, 2)), None, Some((1, 1))]
+        );
+        // Seen along x, columns run along z
+        assert_eq!(
+            column_extents(&occupied, size, Axis::X, Axis::Y),
+            vec![Some((0, 1)), Some((0, 2))]
+        );
+    }
+
+    #[test]
+    fn test_skyline() {
+        let (occupied, size) = grid();
+        let columns = column_extents(&occupied, size, Axis::Z, Axis::Y);
+        let top = skyline(&columns, true);
+        assert_eq!(
+            top,
+            vec![
+                vec![
+                    Vector2::new(0.0, 1.0),
+                    Vector2::new(1.0, 1.0),
+                    Vector2::new(1.0, 3.0),
+                    Vector2::new(2.0, 3.0),
+                ],
+                vec![Vector2::new(3.0, 2.0), Vector2::new(4.0, 2.0)],
+            ]
+        );
+        let bottom = skyline(&columns, false);
+        assert_eq!(
+            bottom[0],
+            vec![Vector2::new(0.0, 0.0), Vector2::new(2.0, 0.0)]
+        );
+    }
+}
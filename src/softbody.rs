@@ -0,0 +1,133 @@
+//! Pressure model for soft bodies made of point masses, such as balloons.
+//!
+//! The body is a closed ring of points in 2D or a closed triangle mesh in 3D.
+//! Each step, compute the enclosed volume, turn it into a pressure with
+//! `ideal_gas_pressure`, and add the resulting per-point forces to the spring
+//! forces before integrating.
+
+use crate::polygon::signed_area;
+use crate::{Vector2, Vector3};
+
+/// Pressure of an ideal gas `p = nRT / V`, where `amount` stands for `nRT`.
+/// Zero for an empty or inverted volume.
+pub fn ideal_gas_pressure(amount: f32, volume: f32) -> f32 {
+    if volume > 0.0 {
+        amount / volume
+    } else {
+        0.0
+    }
+}
+
+/// Area enclosed by a closed ring of points, regardless of winding.
+pub fn ring_area(points: &[Vector2<f32>]) -> f32 {
+    signed_area(points).abs()
+}
+
+/// Forces on the points of a closed ring from a uniform internal `pressure`.
+/// Each edge is pushed outward with pressure times its length, split evenly
+/// between its two endpoints. Works with either winding.
+pub fn ring_pressure_forces(points: &[Vector2<f32>], pressure: f32) -> Vec<Vector2<f32>> {
+    let mut forces = vec![Vector2::default(); points.len()];
+    // The outward normal is to the right of counter-clockwise edges
+    let sign = if signed_area(points) < 0.0 { -1.0 } else { 1.0 };
+    for i in 0..points.len() {
+        let j = (i + 1) % points.len();
+        let d = points[j] - points[i];
+        // Unnormalized normal, so its length already scales with the edge
+        let force = Vector2::new(d.y, -d.x) * (0.5 * sign * pressure);
+        forces[i] += force;
+        forces[j] += force;
+    }
+    forces
+}
+
+/// Volume enclosed by a closed triangle mesh, regardless of winding, by
+/// summing signed tetrahedra against the origin.
+pub fn mesh_volume(positions: &[Vector3<f32>], triangles: &[[usize; 3]]) -> f32 {
+    signed_volume(positions, triangles).abs()
+}
+
+fn signed_volume(positions: &[Vector3<f32>], triangles: &[[usize; 3]]) -> f32 {
+    triangles
+        .iter()
+        .map(|&[a, b, c]| positions[a].dot(&positions[b].cross(&positions[c])))
+        .sum::<f32>()
+        / 6.0
+}
+
+/// Forces on the vertices of a closed triangle mesh from a uniform internal
+/// `pressure`. Each face is pushed outward with pressure times its area, split
+/// evenly between its three vertices. The faces must be wound consistently,
+/// either way.
+pub fn mesh_pressure_forces(
+    positions: &[Vector3<f32>],
+    triangles: &[[usize; 3]],
+    pressure: f32,
+) -> Vec<Vector3<f32>> {
+    let mut forces = vec![Vector3::default(); positions.len()];
+    // Counter-clockwise faces seen from outside enclose a positive volume
+    let sign = if signed_volume(positions, triangles) < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    for &[a, b, c] in triangles {
+        // The cross product has twice the face area as its length
+        let normal = (positions[b] - positions[a]).cross(&(positions[c] - positions[a]));
+        let force = normal * (sign * pressure / 6.0);
+        forces[a] += force;
+        forces[b] += force;
+        forces[c] += force;
+    }
+    forces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_pressure() {
+        let mut square = vec![
+            Vector2::new(-1.0, -1.0),
+            Vector2::new(1.0, -1.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(-1.0, 1.0),
+        ];
+        assert_eq!(ring_area(&square), 4.0);
+        let pressure = ideal_gas_pressure(8.0, ring_area(&square));
+        assert_eq!(pressure, 2.0);
+
+        // Each corner gets half of two edges of length 2, pushed diagonally out
+        let forces = ring_pressure_forces(&square, pressure);
+        assert_eq!(forces[0], Vector2::new(-2.0, -2.0));
+        assert_eq!(forces[2], Vector2::new(2.0, 2.0));
+
+        square.reverse();
+        let forces = ring_pressure_forces(&square, pressure);
+        assert_eq!(forces[0], Vector2::new(-2.0, 2.0));
+    }
+
+    #[test]
+    fn test_mesh_pressure() {
+        // Unit right tetrahedron with outward facing triangles
+        let positions = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ];
+        let triangles = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+        assert!((mesh_volume(&positions, &triangles) - 1.0 / 6.0).abs() < 1e-7);
+
+        let forces = mesh_pressure_forces(&positions, &triangles, 3.0);
+        // A closed surface under uniform pressure has no net force
+        let total = forces.iter().fold(Vector3::<f32>::default(), |a, f| a + *f);
+        assert!(total.magnitude() < 1e-6);
+        // The corner at the origin is pushed away from the others
+        assert!(forces[0].x < 0.0 && forces[0].y < 0.0 && forces[0].z < 0.0);
+
+        let flipped: Vec<[usize; 3]> = triangles.iter().map(|&[a, b, c]| [a, c, b]).collect();
+        assert_eq!(mesh_pressure_forces(&positions, &flipped, 3.0), forces);
+    }
+}
@@ -0,0 +1,124 @@
+//! Uniform spatial hash grid, for finding the points near a position without
+//! comparing against every other one.
+//!
+//! Positions are bucketed by the cubic cell holding them, as given by
+//! `Vector3::quantize`. Only occupied cells are stored, so the grid has no
+//! bounds. 2D users can keep `z` at zero.
+
+use std::collections::HashMap;
+
+use crate::Vector3;
+
+/// Items bucketed by the cell of size `cell_size` holding their position.
+#[derive(Clone, Debug)]
+pub struct SpatialHash<T = usize> {
+    cell_size: f32,
+    cells: HashMap<Vector3<i32>, Vec<T>>,
+}
+
+impl<T> SpatialHash<T> {
+    pub fn new(cell_size: f32) -> SpatialHash<T> {
+        SpatialHash {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Cell holding `p`.
+    pub fn cell(&self, p: Vector3<f32>) -> Vector3<i32> {
+        p.quantize(self.cell_size)
+    }
+
+    /// Adds `item` to the cell holding `p`.
+    pub fn insert(&mut self, p: Vector3<f32>, item: T) {
+        self.cells.entry(self.cell(p)).or_default().push(item);
+    }
+
+    /// Items in `cell`, in insertion order.
+    pub fn bucket(&self, cell: Vector3<i32>) -> &[T] {
+        self.cells.get(&cell).map_or(&[], Vec::as_slice)
+    }
+
+    /// Items in the cell holding `p` and the 26 around it, which includes
+    /// every item within `cell_size` of `p`. Cells saturate at the i32 range,
+    /// so neighbors past it are skipped rather than wrapped.
+    pub fn around(&self, p: Vector3<f32>) -> impl Iterator<Item = &T> {
+        let cell = self.cell(p);
+        let offset = |c: i32, d: i32| c.checked_add(d);
+        (-1..=1)
+            .flat_map(|z| (-1..=1).flat_map(move |y| (-1..=1).map(move |x| (x, y, z))))
+            .filter_map(move |(x, y, z)| {
+                Some(Vector3::new(
+                    offset(cell.x, x)?,
+                    offset(cell.y, y)?,
+                    offset(cell.z, z)?,
+                ))
+            })
+            .flat_map(|c| self.bucket(c))
+    }
+}
+
+impl SpatialHash<usize> {
+    /// Grid of the indices of `points`.
+    pub fn from_points(points: &[Vector3<f32>], cell_size: f32) -> SpatialHash {
+        let mut grid = SpatialHash::new(cell_size);
+        for (i, p) in points.iter().enumerate() {
+            grid.insert(*p, i);
+        }
+        grid
+    }
+
+    /// Indices of the points closer than `radius` to `p`, including one at
+    /// `p` itself. `points` must be the slice the grid was built from, and
+    /// `radius` at most the cell size.
+    pub fn within<'a>(
+        &'a self,
+        points: &'a [Vector3<f32>],
+        p: Vector3<f32>,
+        radius: f32,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.around(p)
+            .copied()
+            .filter(move |&i| points[i].distance_squared(&p) < radius * radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_within_matches_brute_force() {
+        let points: Vec<_> = (0..200)
+            .map(|i| {
+                let t = i as f32;
+                Vector3::new((t * 0.37).sin(), (t * 0.53).cos(), (t * 0.11).sin()) * 3.0
+            })
+            .collect();
+        let grid = SpatialHash::from_points(&points, 0.5);
+        for p in &points {
+            let mut found: Vec<_> = grid.within(&points, *p, 0.5).collect();
+            found.sort();
+            let brute: Vec<_> = (0..points.len())
+                .filter(|&i| points[i].distance_squared(p) < 0.25)
+                .collect();
+            assert_eq!(found, brute);
+        }
+    }
+
+    #[test]
+    fn test_around_at_range_limit() {
+        let mut grid = SpatialHash::new(1.0);
+        let far = Vector3::new(1e20, -1e20, 0.0);
+        grid.insert(far, 'a');
+        grid.insert(Vector3::new(-1e20, 1e20, 0.0), 'b');
+        assert_eq!(grid.cell(far), Vector3::new(i32::MAX, i32::MIN, 0));
+        // The neighbors of a saturated cell don't wrap to the opposite end
+        assert_eq!(grid.around(far).collect::<Vec<_>>(), [&'a']);
+        assert_eq!(grid.bucket(Vector3::new(5, 5, 5)), &[] as &[char]);
+    }
+}
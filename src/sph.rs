@@ -0,0 +1,175 @@
+//! Smoothing kernels and neighbor sums for smoothed particle hydrodynamics.
+//!
+//! The kernels are the ones from Müller et al., "Particle-Based Fluid
+//! Simulation for Interactive Applications" (2003). Each is normalized to
+//! integrate to one over the sphere of radius `h` and is zero outside it.
+
+use std::f32::consts::PI;
+use std::ops::Add;
+
+use crate::spatial_hash::SpatialHash;
+use crate::Vector3;
+
+/// Poly6 kernel at squared distance `r_sq`, used for densities.
+pub fn poly6(r_sq: f32, h: f32) -> f32 {
+    let h_sq = h * h;
+    if r_sq >= h_sq {
+        return 0.0;
+    }
+    315.0 / (64.0 * PI * h.powi(9)) * (h_sq - r_sq).powi(3)
+}
+
+/// Gradient of the poly6 kernel at offset `r`.
+pub fn poly6_gradient(r: Vector3<f32>, h: f32) -> Vector3<f32> {
+    let diff = h * h - r.dot(&r);
+    if diff <= 0.0 {
+        return Vector3::default();
+    }
+    r * (-945.0 / (32.0 * PI * h.powi(9)) * diff * diff)
+}
+
+/// Spiky kernel at distance `r`, whose gradient does not vanish at the center
+/// so close particles still repel.
+pub fn spiky(r: f32, h: f32) -> f32 {
+    if r >= h {
+        return 0.0;
+    }
+    15.0 / (PI * h.powi(6)) * (h - r).powi(3)
+}
+
+/// Gradient of the spiky kernel at offset `r`, used for pressure forces. Zero
+/// at `r == 0` where the direction is undefined.
+pub fn spiky_gradient(r: Vector3<f32>, h: f32) -> Vector3<f32> {
+    let len = r.magnitude();
+    if len >= h || len == 0.0 {
+        return Vector3::default();
+    }
+    r * (-45.0 / (PI * h.powi(6)) * (h - len).powi(2) / len)
+}
+
+/// Viscosity kernel at distance `r`.
+pub fn viscosity(r: f32, h: f32) -> f32 {
+    if r >= h || r == 0.0 {
+        return 0.0;
+    }
+    let q = r / h;
+    15.0 / (2.0 * PI * h.powi(3)) * (-0.5 * q.powi(3) + q * q + 0.5 / q - 1.0)
+}
+
+/// Laplacian of the viscosity kernel at distance `r`, used for viscosity
+/// forces. Positive everywhere inside the support.
+pub fn viscosity_laplacian(r: f32, h: f32) -> f32 {
+    if r >= h {
+        return 0.0;
+    }
+    45.0 / (PI * h.powi(6)) * (h - r)
+}
+
+/// Particle indices bucketed in a spatial hash with cells as large as the
+/// kernel radius, so all neighbors of a point lie in the surrounding 27
+/// cells.
+#[derive(Clone, Debug)]
+pub struct NeighborGrid {
+    h: f32,
+    grid: SpatialHash,
+}
+
+impl NeighborGrid {
+    pub fn new(positions: &[Vector3<f32>], h: f32) -> NeighborGrid {
+        NeighborGrid {
+            h,
+            grid: SpatialHash::from_points(positions, h),
+        }
+    }
+
+    /// Sum of `f(j, offset)` over all particles `j` closer than `h` to `point`,
+    /// where `offset` points from particle `j` to `point`. `positions` must be
+    /// the slice the grid was built from. A particle at `point` itself is
+    /// included with a zero offset.
+    pub fn neighbor_sum<T, F>(&self, positions: &[Vector3<f32>], point: Vector3<f32>, mut f: F) -> T
+    where
+        T: Default + Add<Output = T>,
+        F: FnMut(usize, Vector3<f32>) -> T,
+    {
+        self.grid
+            .within(positions, point, self.h)
+            .fold(T::default(), |sum, j| sum + f(j, point - positions[j]))
+    }
+}
+
+/// Density at every particle, summing the poly6 kernel over its neighbors
+/// (including itself).
+pub fn densities(positions: &[Vector3<f32>], masses: &[f32], h: f32) -> Vec<f32> {
+    assert_eq!(positions.len(), masses.len());
+    let grid = NeighborGrid::new(positions, h);
+    positions
+        .iter()
+        .map(|p| grid.neighbor_sum(positions, *p, |j, r| masses[j] * poly6(r.dot(&r), h)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Midpoint rule integral of a radial kernel over its support.
+    fn integrate(kernel: impl Fn(f32) -> f32, h: f32) -> f32 {
+        let steps = 10000;
+        let dr = h / steps as f32;
+        (0..steps)
+            .map(|i| {
+                let r = (i as f32 + 0.5) * dr;
+                4.0 * PI * r * r * kernel(r) * dr
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_kernels_normalized() {
+        let h = 0.7;
+        assert!((integrate(|r| poly6(r * r, h), h) - 1.0).abs() < 1e-3);
+        assert!((integrate(|r| spiky(r, h), h) - 1.0).abs() < 1e-3);
+        assert!((integrate(|r| viscosity(r, h), h) - 1.0).abs() < 1e-3);
+        assert_eq!(poly6(1.0, 0.5), 0.0);
+        assert_eq!(
+            spiky_gradient(Vector3::new(0.0, 0.0, 0.0), h),
+            Vector3::default()
+        );
+    }
+
+    #[test]
+    fn test_gradients() {
+        // Compare against central differences of the kernels along x
+        let h = 1.0;
+        let r = Vector3::new(0.4, 0.2, -0.1);
+        let dx = Vector3::new(1e-3, 0.0, 0.0);
+        let numeric = |k: &dyn Fn(Vector3<f32>) -> f32| (k(r + dx) - k(r - dx)) / 2e-3;
+
+        let g = poly6_gradient(r, h);
+        assert!((g.x - numeric(&|v| poly6(v.dot(&v), h))).abs() < 1e-2);
+        let g = spiky_gradient(r, h);
+        assert!((g.x - numeric(&|v| spiky(v.magnitude(), h))).abs() < 1e-2);
+        assert!(spiky_gradient(r, h).dot(&r) < 0.0);
+    }
+
+    #[test]
+    fn test_neighbor_sum() {
+        let positions: Vec<_> = (0..10)
+            .flat_map(|x| (0..10).map(move |y| Vector3::new(x as f32, y as f32, 0.0) * 0.1))
+            .collect();
+        let h = 0.25;
+        let grid = NeighborGrid::new(&positions, h);
+        for p in &positions {
+            let count = grid.neighbor_sum(&positions, *p, |_, _| 1);
+            let brute = positions
+                .iter()
+                .filter(|q| (*p - **q).magnitude() < h)
+                .count();
+            assert_eq!(count, brute);
+        }
+
+        let rho = densities(&positions, &vec![1.0; positions.len()], h);
+        // Interior particles have more neighbors than corner ones
+        assert!(rho[55] > rho[0]);
+    }
+}
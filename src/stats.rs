@@ -0,0 +1,391 @@
+//! Robust per-component statistics over slices of vectors.
+
+use crate::{Components, VectorError};
+
+// Sorted values of one axis
+fn sorted_axis<V: Components>(points: &[V], axis: usize) -> Vec<f32> {
+    let mut values: Vec<f32> = points.iter().map(|p| p.component(axis)).collect();
+    values.sort_by(|a, b| a.total_cmp(b));
+    values
+}
+
+// Linearly interpolated percentile of sorted, non-empty values
+fn percentile_of_sorted(values: &[f32], percent: f32) -> f32 {
+    let rank = (percent.clamp(0.0, 100.0) / 100.0) * (values.len() - 1) as f32;
+    let lo = rank.floor() as usize;
+    let hi = (lo + 1).min(values.len() - 1);
+    let t = rank - lo as f32;
+    values[lo] + (values[hi] - values[lo]) * t
+}
+
+/// Per-component percentile in [0, 100], interpolating between samples.
+/// Returns `None` for an empty slice.
+pub fn percentile<V: Components>(points: &[V], percent: f32) -> Option<V> {
+    if points.is_empty() {
+        return None;
+    }
+    let axes: Vec<Vec<f32>> = (0..V::DIM).map(|a| sorted_axis(points, a)).collect();
+    Some(V::from_components(|a| {
+        percentile_of_sorted(&axes[a], percent)
+    }))
+}
+
+/// Per-component median. Returns `None` for an empty slice.
+pub fn median<V: Components>(points: &[V]) -> Option<V> {
+    percentile(points, 50.0)
+}
+
+/// Per-component mean after discarding the lowest and highest `trim` fraction
+/// (in [0, 0.5)) of values on each axis, which rejects outliers.
+/// Returns `None` if nothing is left after trimming.
+pub fn trimmed_mean<V: Components>(points: &[V], trim: f32) -> Option<V> {
+    let cut = (points.len() as f32 * trim.clamp(0.0, 0.5)).floor() as usize;
+    if points.len() <= 2 * cut {
+        return None;
+    }
+    let axes: Vec<Vec<f32>> = (0..V::DIM).map(|a| sorted_axis(points, a)).collect();
+    Some(V::from_components(|a| {
+        let kept = &axes[a][cut..points.len() - cut];
+        kept.iter().sum::<f32>() / kept.len() as f32
+    }))
+}
+
+/// Average of `points` with each weighted by the matching entry of
+/// `weights`, e.g. the center of mass of point masses. Weights may be
+/// negative, but must not sum to zero: a sum within rounding error of zero
+/// is `ZeroWeight`, and a result that still overflows is `NonFinite`.
+///
+/// Panics if the slices differ in length.
+pub fn weighted_centroid<V: Components>(points: &[V], weights: &[f32]) -> Result<V, VectorError> {
+    assert_eq!(points.len(), weights.len(), "slices differ in length");
+    // Summed in f64, so the rounding error stays far below an f32 ulp of the
+    // weights' magnitude however many there are. What is left of cancelling
+    // weights is then the noise of their own f32 rounding, not an exact zero
+    let total: f64 = weights.iter().map(|&w| f64::from(w)).sum();
+    let magnitude: f64 = weights.iter().map(|&w| f64::from(w.abs())).sum();
+    if total.abs() <= magnitude * f64::from(f32::EPSILON) {
+        return Err(VectorError::ZeroWeight);
+    }
+    let total = total as f32;
+    let centroid = V::from_components(|a| {
+        points
+            .iter()
+            .zip(weights)
+            .map(|(p, w)| p.component(a) * w)
+            .sum::<f32>()
+            / total
+    });
+    if (0..V::DIM).all(|a| centroid.component(a).is_finite()) {
+        Ok(centroid)
+    } else {
+        Err(VectorError::NonFinite)
+    }
+}
+
+/// Per-component minimum of all vectors, e.g. the lower corner of a mesh's
+/// bounding box. Returns `None` for an empty iterator.
+pub fn component_min<V: Components, I: IntoIterator<Item = V>>(iter: I) -> Option<V> {
+    iter.into_iter()
+        .reduce(|a, b| V::from_components(|i| a.component(i).min(b.component(i))))
+}
+
+/// Per-component maximum of all vectors. Returns `None` for an empty iterator.
+pub fn component_max<V: Components, I: IntoIterator<Item = V>>(iter: I) -> Option<V> {
+    iter.into_iter()
+        .reduce(|a, b| V::from_components(|i| a.component(i).max(b.component(i))))
+}
+
+/// Equal-width histogram of the values of one axis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub min: f32,
+    pub max: f32,
+    pub counts: Vec<u32>,
+}
+
+impl Histogram {
+    pub fn bin_width(&self) -> f32 {
+        (self.max - self.min) / self.counts.len() as f32
+    }
+}
+
+/// One histogram with `bins` bins per axis, each spanning that axis' value range.
+pub fn histograms<V: Components>(points: &[V], bins: usize) -> Vec<Histogram> {
+    (0..V::DIM)
+        .map(|axis| {
+            let (min, max) = points
+                .iter()
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), p| {
+                    (lo.min(p.component(axis)), hi.max(p.component(axis)))
+                });
+            let mut counts = vec![0; bins];
+            if bins > 0 {
+                let scale = if max > min {
+                    bins as f32 / (max - min)
+                } else {
+                    0.0
+                };
+                for p in points {
+                    let bin = ((p.component(axis) - min) * scale) as usize;
+                    counts[bin.min(bins - 1)] += 1;
+                }
+            }
+            Histogram { min, max, counts }
+        })
+        .collect()
+}
+
+/// Streaming count, mean, variance and bounds of vectors, using Welford's
+/// algorithm. Accumulators filled from separate chunks of data can be combined
+/// with `merge`, giving the same result as accumulating everything in one go.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Accumulator<V> {
+    count: u64,
+    mean: V,
+    m2: V,
+    min: V,
+    max: V,
+}
+
+impl<V: Components> Default for Accumulator<V> {
+    fn default() -> Self {
+        Accumulator::new()
+    }
+}
+
+impl<V: Components> Accumulator<V> {
+    pub fn new() -> Accumulator<V> {
+        Accumulator {
+            count: 0,
+            mean: V::from_components(|_| 0.0),
+            m2: V::from_components(|_| 0.0),
+            min: V::from_components(|_| f32::INFINITY),
+            max: V::from_components(|_| f32::NEG_INFINITY),
+        }
+    }
+
+    pub fn push(&mut self, v: V) {
+        self.count += 1;
+        let n = self.count as f32;
+        let mean = self.mean;
+        self.mean =
+            V::from_components(|a| mean.component(a) + (v.component(a) - mean.component(a)) / n);
+        let (new_mean, m2) = (self.mean, self.m2);
+        self.m2 = V::from_components(|a| {
+            m2.component(a)
+                + (v.component(a) - mean.component(a)) * (v.component(a) - new_mean.component(a))
+        });
+        let (min, max) = (self.min, self.max);
+        self.min = V::from_components(|a| min.component(a).min(v.component(a)));
+        self.max = V::from_components(|a| max.component(a).max(v.component(a)));
+    }
+
+    /// Combines the statistics of another accumulator into this one.
+    pub fn merge(&mut self, other: &Accumulator<V>) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let (na, nb) = (self.count as f32, other.count as f32);
+        let n = na + nb;
+        let (a, b) = (*self, *other);
+        let delta = |i: usize| b.mean.component(i) - a.mean.component(i);
+        self.mean = V::from_components(|i| a.mean.component(i) + delta(i) * nb / n);
+        self.m2 = V::from_components(|i| {
+            a.m2.component(i) + b.m2.component(i) + delta(i) * delta(i) * na * nb / n
+        });
+        self.min = V::from_components(|i| a.min.component(i).min(b.min.component(i)));
+        self.max = V::from_components(|i| a.max.component(i).max(b.max.component(i)));
+        self.count += other.count;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<V> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// Per-component population variance.
+    pub fn variance(&self) -> Option<V> {
+        let n = self.count as f32;
+        let m2 = self.m2;
+        (self.count > 0).then(|| V::from_components(|a| m2.component(a) / n))
+    }
+
+    /// Per-component sample variance (with Bessel's correction).
+    pub fn sample_variance(&self) -> Option<V> {
+        let n = self.count as f32;
+        let m2 = self.m2;
+        (self.count > 1).then(|| V::from_components(|a| m2.component(a) / (n - 1.0)))
+    }
+
+    /// Per-component minimum and maximum, the bounding box of all pushed vectors.
+    pub fn bounds(&self) -> Option<(V, V)> {
+        (self.count > 0).then_some((self.min, self.max))
+    }
+}
+
+impl<V: Components> Extend<V> for Accumulator<V> {
+    fn extend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+        for v in iter {
+            self.push(v);
+        }
+    }
+}
+
+impl<V: Components> FromIterator<V> for Accumulator<V> {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        let mut acc = Accumulator::new();
+        acc.extend(iter);
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector2, Vector3};
+
+    fn samples() -> Vec<Vector3<f32>> {
+        vec![
+            Vector3::new(1.0, 10.0, -1.0),
+            Vector3::new(2.0, 20.0, -2.0),
+            Vector3::new(3.0, 30.0, -3.0),
+            Vector3::new(4.0, 40.0, -4.0),
+            Vector3::new(100.0, 50.0, -500.0),
+        ]
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&samples()), Some(Vector3::new(3.0, 30.0, -3.0)));
+        let even = [Vector2::new(1.0, 4.0), Vector2::new(3.0, 2.0)];
+        assert_eq!(median(&even), Some(Vector2::new(2.0, 3.0)));
+        assert_eq!(median::<Vector2<f32>>(&[]), None);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let points = samples();
+        assert_eq!(
+            percentile(&points, 0.0),
+            Some(Vector3::new(1.0, 10.0, -500.0))
+        );
+        assert_eq!(
+            percentile(&points, 100.0),
+            Some(Vector3::new(100.0, 50.0, -1.0))
+        );
+        assert_eq!(
+            percentile(&points, 12.5),
+            Some(Vector3::new(1.5, 15.0, -252.0))
+        );
+    }
+
+    #[test]
+    fn test_trimmed_mean_rejects_outliers() {
+        let mean = trimmed_mean(&samples(), 0.2).unwrap();
+        assert_eq!(mean, Vector3::new(3.0, 30.0, -3.0));
+        assert_eq!(trimmed_mean(&samples()[..2], 0.5), None);
+    }
+
+    #[test]
+    fn test_weighted_centroid() {
+        let points = [Vector2::new(0.0, 0.0), Vector2::new(4.0, 2.0)];
+        assert_eq!(
+            weighted_centroid(&points, &[3.0, 1.0]),
+            Ok(Vector2::new(1.0, 0.5))
+        );
+        assert_eq!(
+            weighted_centroid(&points, &[1.0, -1.0]),
+            Err(VectorError::ZeroWeight)
+        );
+        assert_eq!(
+            weighted_centroid::<Vector2<f32>>(&[], &[]),
+            Err(VectorError::ZeroWeight)
+        );
+        // Sums to rounding noise rather than exactly zero
+        let three = [points[0], points[1], points[1]];
+        assert_ne!(0.3f32 + 0.6 - 0.9, 0.0);
+        assert_eq!(
+            weighted_centroid(&three, &[0.3, 0.6, -0.9]),
+            Err(VectorError::ZeroWeight)
+        );
+        assert_eq!(
+            weighted_centroid(&[Vector2::new(f32::MAX, 0.0); 2], &[1.0, 1.0]),
+            Err(VectorError::NonFinite)
+        );
+        // More weights than 1 / f32::EPSILON must not look like cancellation
+        let n = (1 << 23) + 1;
+        assert_eq!(
+            weighted_centroid(&vec![points[1]; n], &vec![1.0; n]),
+            Ok(points[1])
+        );
+    }
+
+    #[test]
+    fn test_component_min_max() {
+        let points = [
+            Vector3::new(1.0, -2.0, 0.5),
+            Vector3::new(-3.0, 4.0, 0.0),
+            Vector3::new(2.0, 0.0, 1.0),
+        ];
+        assert_eq!(component_min(points), Some(Vector3::new(-3.0, -2.0, 0.0)));
+        assert_eq!(
+            component_max(points.iter().copied()),
+            Some(Vector3::new(2.0, 4.0, 1.0))
+        );
+        assert_eq!(component_min(Vec::<Vector2<f32>>::new()), None);
+    }
+
+    #[test]
+    fn test_histograms() {
+        let hist = histograms(&samples(), 4);
+        assert_eq!(hist.len(), 3);
+        assert_eq!(hist[1].min, 10.0);
+        assert_eq!(hist[1].max, 50.0);
+        assert_eq!(hist[1].bin_width(), 10.0);
+        assert_eq!(hist[1].counts, vec![1, 1, 1, 2]);
+        assert_eq!(hist[0].counts, vec![4, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_accumulator() {
+        let acc: Accumulator<Vector2<f32>> = [
+            Vector2::new(1.0, -2.0),
+            Vector2::new(3.0, 2.0),
+            Vector2::new(5.0, 0.0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(acc.count(), 3);
+        assert_eq!(acc.mean(), Some(Vector2::new(3.0, 0.0)));
+        assert_eq!(acc.sample_variance(), Some(Vector2::new(4.0, 4.0)));
+        assert_eq!(
+            acc.bounds(),
+            Some((Vector2::new(1.0, -2.0), Vector2::new(5.0, 2.0)))
+        );
+        assert_eq!(Accumulator::<Vector2<f32>>::new().mean(), None);
+    }
+
+    #[test]
+    fn test_accumulator_merge() {
+        let points = samples();
+        let whole: Accumulator<Vector3<f32>> = points.iter().copied().collect();
+        let mut left: Accumulator<Vector3<f32>> = points[..2].iter().copied().collect();
+        let right: Accumulator<Vector3<f32>> = points[2..].iter().copied().collect();
+        left.merge(&right);
+        left.merge(&Accumulator::new());
+
+        assert_eq!(left.count(), whole.count());
+        assert_eq!(left.bounds(), whole.bounds());
+        let d = left.mean().unwrap() - whole.mean().unwrap();
+        assert!(d.dot(&d) < 1e-8);
+        let d = left.variance().unwrap() - whole.variance().unwrap();
+        assert!(d.dot(&d).sqrt() < 1e-2);
+    }
+}
@@ -0,0 +1,196 @@
+//! Craig Reynolds' steering behaviors for autonomous agents in 2D.
+//!
+//! Each behavior returns the acceleration to apply this step, limited to
+//! `max_acceleration`. Integrate it into the velocity, then clamp the velocity
+//! to `max_speed`. Behaviors can be combined by adding weighted accelerations.
+
+use crate::Vector2;
+
+/// Limits of the agent being steered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Steering {
+    pub max_speed: f32,
+    pub max_acceleration: f32,
+}
+
+impl Default for Steering {
+    fn default() -> Self {
+        Steering {
+            max_speed: 1.0,
+            max_acceleration: 1.0,
+        }
+    }
+}
+
+/// `v` scaled to length `len`, or zero if `v` is zero.
+fn with_length(v: Vector2<f32>, len: f32) -> Vector2<f32> {
+    let mag = v.magnitude();
+    if mag > 0.0 {
+        v * (len / mag)
+    } else {
+        Vector2::default()
+    }
+}
+
+impl Steering {
+    pub const fn new(max_speed: f32, max_acceleration: f32) -> Steering {
+        Steering {
+            max_speed,
+            max_acceleration,
+        }
+    }
+
+    /// Acceleration turning `velocity` toward `desired`.
+    fn steer(&self, velocity: Vector2<f32>, desired: Vector2<f32>) -> Vector2<f32> {
+        let mut acceleration = desired - velocity;
+        acceleration.clamp_mag(self.max_acceleration);
+        acceleration
+    }
+
+    /// Head for `target` at full speed.
+    pub fn seek(
+        &self,
+        position: Vector2<f32>,
+        velocity: Vector2<f32>,
+        target: Vector2<f32>,
+    ) -> Vector2<f32> {
+        self.steer(velocity, with_length(target - position, self.max_speed))
+    }
+
+    /// Run away from `threat` at full speed.
+    pub fn flee(
+        &self,
+        position: Vector2<f32>,
+        velocity: Vector2<f32>,
+        threat: Vector2<f32>,
+    ) -> Vector2<f32> {
+        self.steer(velocity, with_length(position - threat, self.max_speed))
+    }
+
+    /// Head for `target` and come to a stop on it, slowing down linearly
+    /// inside `slowing_radius`.
+    pub fn arrive(
+        &self,
+        position: Vector2<f32>,
+        velocity: Vector2<f32>,
+        target: Vector2<f32>,
+        slowing_radius: f32,
+    ) -> Vector2<f32> {
+        let offset = target - position;
+        let dist = offset.magnitude();
+        let speed = if dist < slowing_radius {
+            self.max_speed * dist / slowing_radius
+        } else {
+            self.max_speed
+        };
+        self.steer(velocity, with_length(offset, speed))
+    }
+}
+
+/// Wander behavior: seeks a point on a circle projected ahead of the agent,
+/// whose angle drifts randomly each step, giving smooth meandering motion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Wander {
+    /// Radius of the circle ahead of the agent.
+    pub radius: f32,
+    /// Distance of the circle center ahead of the agent.
+    pub distance: f32,
+    /// Largest change of the angle on the circle per step, in radians.
+    pub jitter: f32,
+    /// Current angle on the circle relative to the heading, in radians.
+    pub angle: f32,
+}
+
+impl Wander {
+    pub const fn new(radius: f32, distance: f32, jitter: f32) -> Wander {
+        Wander {
+            radius,
+            distance,
+            jitter,
+            angle: 0.0,
+        }
+    }
+
+    /// Advances the wander angle by `jitter * random`, where `random` is a
+    /// random number in [-1, 1] supplied by the caller (for example
+    /// `2.0 * hash::hash21(..) - 1.0`), and returns the acceleration toward
+    /// the new point on the circle. An agent at rest wanders off along +x.
+    pub fn steer(
+        &mut self,
+        steering: &Steering,
+        velocity: Vector2<f32>,
+        random: f32,
+    ) -> Vector2<f32> {
+        self.angle += self.jitter * random.clamp(-1.0, 1.0);
+        let heading = if velocity.magnitude() > 0.0 {
+            velocity.normalize()
+        } else {
+            Vector2::new(1.0, 0.0)
+        };
+        let (sin, cos) = self.angle.sin_cos();
+        let around = Vector2::new(
+            heading.x * cos - heading.y * sin,
+            heading.x * sin + heading.y * cos,
+        );
+        let point = heading * self.distance + around * self.radius;
+        steering.steer(velocity, with_length(point, steering.max_speed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seek_and_flee() {
+        let s = Steering::new(2.0, 1.0);
+        let position = Vector2::new(0.0, 0.0);
+        let target = Vector2::new(10.0, 0.0);
+        let at_rest = Vector2::new(0.0, 0.0);
+        assert_eq!(s.seek(position, at_rest, target), Vector2::new(1.0, 0.0));
+        assert_eq!(s.flee(position, at_rest, target), Vector2::new(-1.0, 0.0));
+
+        // Already at full speed toward the target: nothing to do
+        let cruising = Vector2::new(2.0, 0.0);
+        assert_eq!(s.seek(position, cruising, target), Vector2::new(0.0, 0.0));
+        // Moving sideways: steer back toward the target
+        let sideways = s.seek(position, Vector2::new(0.0, 2.0), target);
+        assert!(sideways.x > 0.0 && sideways.y < 0.0);
+        assert!((sideways.magnitude() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_arrive() {
+        let s = Steering::new(4.0, 10.0);
+        let target = Vector2::new(10.0, 0.0);
+        let moving = Vector2::new(4.0, 0.0);
+        // Outside the slowing radius arrive behaves like seek
+        let far = Vector2::new(0.0, 0.0);
+        assert_eq!(
+            s.arrive(far, moving, target, 5.0),
+            s.seek(far, moving, target)
+        );
+        // Halfway into the slowing radius: brake to half speed
+        let near = Vector2::new(7.5, 0.0);
+        assert_eq!(s.arrive(near, moving, target, 5.0), Vector2::new(-2.0, 0.0));
+        // On the target: stop
+        assert_eq!(
+            s.arrive(target, moving, target, 5.0),
+            Vector2::new(-4.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_wander() {
+        let s = Steering::new(1.0, 1.0);
+        let mut wander = Wander::new(1.0, 2.0, 0.5);
+        let velocity = Vector2::new(1.0, 0.0);
+        assert_eq!(wander.steer(&s, velocity, 0.0), Vector2::new(0.0, 0.0));
+        let turn = wander.steer(&s, velocity, 1.0);
+        assert_eq!(wander.angle, 0.5);
+        assert!(turn.y > 0.0);
+        let turn = wander.steer(&s, velocity, -3.0);
+        assert_eq!(wander.angle, 0.0);
+        assert_eq!(turn, Vector2::new(0.0, 0.0));
+    }
+}
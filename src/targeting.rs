@@ -0,0 +1,112 @@
+//! Leading moving targets with constant speed projectiles, in 2D or 3D.
+
+use crate::components::{delta, dot};
+use crate::Components;
+
+/// Earliest time at which a projectile fired now from `shooter` with speed
+/// `projectile_speed` can hit a target at `target` moving with constant
+/// velocity `target_velocity`. Returns `None` if the target cannot be caught.
+pub fn intercept_time<V: Components>(
+    shooter: &V,
+    projectile_speed: f32,
+    target: &V,
+    target_velocity: &V,
+) -> Option<f32> {
+    let d = delta(shooter, target);
+    // |d + v t| = s t  =>  (v.v - s^2) t^2 + 2 (d.v) t + d.d = 0
+    let a = dot(target_velocity, target_velocity) - projectile_speed * projectile_speed;
+    let b = 2.0 * dot(&d, target_velocity);
+    let c = dot(&d, &d);
+    if c == 0.0 {
+        return Some(0.0);
+    }
+
+    if a.abs() < 1e-6 * (b.abs() + c).max(1.0) {
+        // Target as fast as the projectile: the equation is linear
+        let t = -c / b;
+        return (b < 0.0).then_some(t);
+    }
+
+    let disc = b * b - 4.0 * a * c;
+    if disc < 0.0 {
+        return None;
+    }
+    // Numerically stable roots, avoiding cancellation in -b + sqrt(disc)
+    let q = -0.5 * (b + b.signum() * disc.sqrt());
+    let (t0, t1) = (q / a, c / q);
+    let (lo, hi) = (t0.min(t1), t0.max(t1));
+    if lo > 0.0 {
+        Some(lo)
+    } else if hi > 0.0 {
+        Some(hi)
+    } else {
+        None
+    }
+}
+
+/// Unit direction to fire in to hit the target, see `intercept_time`.
+pub fn aim_direction<V: Components>(
+    shooter: &V,
+    projectile_speed: f32,
+    target: &V,
+    target_velocity: &V,
+) -> Option<V> {
+    let t = intercept_time(shooter, projectile_speed, target, target_velocity)?;
+    let aim = V::from_components(|i| {
+        target.component(i) + target_velocity.component(i) * t - shooter.component(i)
+    });
+    let len = dot(&aim, &aim).sqrt();
+    (len > 0.0).then(|| V::from_components(|i| aim.component(i) / len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector2, Vector3};
+
+    #[test]
+    fn test_intercept_stationary_target() {
+        let t = intercept_time(
+            &Vector2::new(0.0, 0.0),
+            5.0,
+            &Vector2::new(10.0, 0.0),
+            &Vector2::new(0.0, 0.0),
+        );
+        assert_eq!(t, Some(2.0));
+    }
+
+    #[test]
+    fn test_intercept_moving_target() {
+        // Target crossing at 3 m/s, 4 m/s sideways from the projectile path
+        let shooter = Vector2::new(0.0, 0.0);
+        let target = Vector2::new(0.0, 12.0);
+        let velocity = Vector2::new(3.0, 0.0);
+        let t = intercept_time(&shooter, 5.0, &target, &velocity).unwrap();
+        assert!((t - 3.0).abs() < 1e-5);
+        let dir = aim_direction(&shooter, 5.0, &target, &velocity).unwrap();
+        assert!((dir.x - 0.6).abs() < 1e-5 && (dir.y - 0.8).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_intercept_impossible() {
+        let t = intercept_time(
+            &Vector3::new(0.0, 0.0, 0.0),
+            1.0,
+            &Vector3::new(10.0, 0.0, 0.0),
+            &Vector3::new(2.0, 0.0, 0.0),
+        );
+        assert_eq!(t, None);
+    }
+
+    #[test]
+    fn test_intercept_equal_speed() {
+        // Target approaching at the projectile speed: meet halfway
+        let t = intercept_time(
+            &Vector3::new(0.0, 0.0, 0.0),
+            2.0,
+            &Vector3::new(8.0, 0.0, 0.0),
+            &Vector3::new(-2.0, 0.0, 0.0),
+        );
+        assert_eq!(t, Some(2.0));
+    }
+}
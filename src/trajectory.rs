@@ -0,0 +1,183 @@
+//! Closed-form paths under constant gravity: drag-free projectile arcs and
+//! the catenary curve of a hanging chain. For drag and wind see the
+//! `ballistics` module.
+//!
+//! Gravity is a vector, e.g. `Vector3::new(0.0, -9.81, 0.0)`.
+
+use crate::Vector3;
+
+/// Position at time `t` of a projectile launched from `p0` with velocity `v0`.
+pub fn ballistic_position(
+    p0: Vector3<f32>,
+    v0: Vector3<f32>,
+    gravity: Vector3<f32>,
+    t: f32,
+) -> Vector3<f32> {
+    p0 + v0 * t + gravity * (0.5 * t * t)
+}
+
+/// Velocity at time `t` of a projectile launched with velocity `v0`.
+pub fn ballistic_velocity(v0: Vector3<f32>, gravity: Vector3<f32>, t: f32) -> Vector3<f32> {
+    v0 + gravity * t
+}
+
+/// Launch velocity that takes a projectile from `origin` to `target` in
+/// exactly `time` seconds.
+pub fn launch_velocity(
+    origin: Vector3<f32>,
+    target: Vector3<f32>,
+    gravity: Vector3<f32>,
+    time: f32,
+) -> Vector3<f32> {
+    (target - origin) * time.recip() - gravity * (0.5 * time)
+}
+
+/// Flight times `[low, high]` of the flat and the lobbed arc from `origin`
+/// to `target` at launch speed `speed`, equal when the target is at the edge
+/// of the range. Returns `None` if the target is out of range.
+pub fn time_to_target(
+    origin: Vector3<f32>,
+    target: Vector3<f32>,
+    speed: f32,
+    gravity: Vector3<f32>,
+) -> Option<[f32; 2]> {
+    let d = target - origin;
+    // |d / t - g t / 2| = s  =>  (g.g / 4) t^4 - (d.g + s^2) t^2 + d.d = 0
+    let a = 0.25 * gravity.dot(&gravity);
+    let b = d.dot(&gravity) + speed * speed;
+    let c = d.dot(&d);
+    if c == 0.0 {
+        return Some([0.0, 0.0]);
+    }
+    if a == 0.0 {
+        // No gravity: a straight line at full speed
+        return (speed > 0.0).then(|| [c.sqrt() / speed; 2]);
+    }
+    let disc = b * b - 4.0 * a * c;
+    if disc < 0.0 || b <= 0.0 {
+        return None;
+    }
+    // Roots for t^2, avoiding cancellation for the smaller one
+    let q = 0.5 * (b + disc.sqrt());
+    Some([(c / q).sqrt(), (q / a).sqrt()])
+}
+
+/// Launch velocities of the flat and the lobbed arc, see `time_to_target`.
+pub fn launch_velocities(
+    origin: Vector3<f32>,
+    target: Vector3<f32>,
+    speed: f32,
+    gravity: Vector3<f32>,
+) -> Option<[Vector3<f32>; 2]> {
+    let times = time_to_target(origin, target, speed, gravity)?;
+    if times[0] == 0.0 {
+        return None;
+    }
+    Some(times.map(|t| launch_velocity(origin, target, gravity, t)))
+}
+
+/// `segments + 1` points along a chain of `length` hanging between `a` and
+/// `b` under gravity along -y. A chain too short to span the gap, or hanging
+/// straight down, is returned as the straight line between the ends.
+pub fn catenary(
+    a: Vector3<f32>,
+    b: Vector3<f32>,
+    length: f32,
+    segments: usize,
+) -> Vec<Vector3<f32>> {
+    let segments = segments.max(1);
+    let along = |t: f32| a + (b - a) * t;
+    let horizontal = Vector3::new(b.x - a.x, 0.0, b.z - a.z);
+    let h = horizontal.magnitude() as f64;
+    let dv = (b.y - a.y) as f64;
+    let length = length as f64;
+    if h < 1e-6 || length * length <= h * h + dv * dv {
+        return (0..=segments)
+            .map(|i| along(i as f32 / segments as f32))
+            .collect();
+    }
+
+    // Solve sinh(k) / k = sqrt(L^2 - dv^2) / h for k = h / (2 * scale)
+    let ratio = (length * length - dv * dv).sqrt() / h;
+    let f = |k: f64| k.sinh() / k - ratio;
+    let mut hi = 1.0;
+    while f(hi) < 0.0 {
+        hi *= 2.0;
+    }
+    let mut lo = 0.0;
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if f(mid) < 0.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    let scale = h / (2.0 * hi);
+
+    // y(x) = scale * cosh((x - x0) / scale) + c through (0, 0) and (h, dv)
+    let x0 = 0.5 * h - scale * (dv / length).atanh();
+    let c = -scale * (-x0 / scale).cosh();
+    let dir = horizontal * (h as f32).recip();
+    (0..=segments)
+        .map(|i| {
+            let x = h * i as f64 / segments as f64;
+            let y = scale * ((x - x0) / scale).cosh() + c;
+            a + dir * x as f32 + Vector3::new(0.0, y as f32, 0.0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const G: Vector3<f32> = Vector3::new(0.0, -9.81, 0.0);
+
+    fn close(a: Vector3<f32>, b: Vector3<f32>, eps: f32) -> bool {
+        (a - b).magnitude() < eps
+    }
+
+    #[test]
+    fn test_launch_velocity() {
+        let origin = Vector3::new(1.0, 2.0, 3.0);
+        let target = Vector3::new(11.0, 0.0, -2.0);
+        let v = launch_velocity(origin, target, G, 1.5);
+        assert!(close(ballistic_position(origin, v, G, 1.5), target, 1e-4));
+        assert_eq!(ballistic_velocity(v, G, 0.0), v);
+    }
+
+    #[test]
+    fn test_launch_velocities() {
+        let origin = Vector3::new(0.0, 0.0, 0.0);
+        let target = Vector3::new(20.0, 3.0, 0.0);
+        let [low, high] = launch_velocities(origin, target, 20.0, G).unwrap();
+        let [t_low, t_high] = time_to_target(origin, target, 20.0, G).unwrap();
+        assert!(t_low < t_high);
+        for (v, t) in [(low, t_low), (high, t_high)] {
+            assert!((v.magnitude() - 20.0).abs() < 1e-3);
+            assert!(close(ballistic_position(origin, v, G, t), target, 1e-3));
+        }
+        assert!(high.y > low.y);
+        assert_eq!(
+            time_to_target(origin, Vector3::new(100.0, 0.0, 0.0), 20.0, G),
+            None
+        );
+    }
+
+    #[test]
+    fn test_catenary() {
+        let a = Vector3::new(0.0, 0.0, 0.0);
+        let b = Vector3::new(0.0, 1.0, 4.0);
+        let points = catenary(a, b, 6.0, 200);
+        assert!(close(points[0], a, 1e-5));
+        assert!(close(points[200], b, 1e-4));
+        let length: f32 = points.windows(2).map(|w| (w[1] - w[0]).magnitude()).sum();
+        assert!((length - 6.0).abs() < 1e-3);
+        assert!(points[100].y < 0.0);
+
+        // Taut chain
+        let taut = catenary(a, b, 1.0, 2);
+        assert_eq!(taut[1], Vector3::new(0.0, 0.5, 2.0));
+    }
+}
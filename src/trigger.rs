@@ -0,0 +1,145 @@
+//! Trigger volumes reporting when tracked points enter, stay in and leave a
+//! shape from one frame to the next.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::culling::{Aabb, Frustum, Sphere};
+use crate::Vector3;
+
+/// A shape that can tell whether it contains a point.
+pub trait Volume {
+    fn contains(&self, point: &Vector3<f32>) -> bool;
+}
+
+impl Volume for Aabb {
+    fn contains(&self, point: &Vector3<f32>) -> bool {
+        Aabb::contains(self, point)
+    }
+}
+
+impl Volume for Sphere {
+    fn contains(&self, point: &Vector3<f32>) -> bool {
+        Sphere::contains(self, point)
+    }
+}
+
+impl Volume for Frustum {
+    fn contains(&self, point: &Vector3<f32>) -> bool {
+        Frustum::contains(self, point)
+    }
+}
+
+/// Transition of a tracked point, identified by its key, during an update.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TriggerEvent<K> {
+    /// Outside (or untracked) last update, inside now.
+    Enter(K),
+    /// Inside last update and still inside.
+    Stay(K),
+    /// Inside last update, outside or no longer tracked now.
+    Exit(K),
+}
+
+/// A shape together with the keys of the points that were inside it at the
+/// last update.
+#[derive(Clone, Debug)]
+pub struct TriggerVolume<S, K> {
+    pub shape: S,
+    inside: Vec<K>,
+}
+
+impl<S: Volume, K: Copy + Eq + Hash> TriggerVolume<S, K> {
+    pub fn new(shape: S) -> TriggerVolume<S, K> {
+        TriggerVolume {
+            shape,
+            inside: Vec::new(),
+        }
+    }
+
+    /// Keys of the points inside at the last update, in the order they were
+    /// given to it.
+    pub fn inside(&self) -> &[K] {
+        &self.inside
+    }
+
+    /// Tests this frame's `(key, position)` pairs against the shape and
+    /// returns the transitions since the last update. Enter and stay events
+    /// follow the order of `points`; exits come last, in the previous order.
+    /// Points inside last time but missing from `points` exit, so despawned
+    /// objects need no special handling.
+    pub fn update<I>(&mut self, points: I) -> Vec<TriggerEvent<K>>
+    where
+        I: IntoIterator<Item = (K, Vector3<f32>)>,
+    {
+        let previous: HashSet<K> = self.inside.iter().copied().collect();
+        let mut events = Vec::new();
+        let mut inside = Vec::new();
+        for (key, position) in points {
+            if self.shape.contains(&position) {
+                events.push(if previous.contains(&key) {
+                    TriggerEvent::Stay(key)
+                } else {
+                    TriggerEvent::Enter(key)
+                });
+                inside.push(key);
+            }
+        }
+        let current: HashSet<K> = inside.iter().copied().collect();
+        events.extend(
+            self.inside
+                .iter()
+                .filter(|key| !current.contains(key))
+                .map(|&key| TriggerEvent::Exit(key)),
+        );
+        self.inside = inside;
+        events
+    }
+
+    /// Forgets all points, returning exit events for those inside.
+    pub fn clear(&mut self) -> Vec<TriggerEvent<K>> {
+        self.inside.drain(..).map(TriggerEvent::Exit).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enter_stay_exit() {
+        let mut zone = TriggerVolume::new(Sphere::new(Vector3::new(0.0, 0.0, 0.0), 2.0));
+        let near = Vector3::new(1.0, 0.0, 0.0);
+        let far = Vector3::new(5.0, 0.0, 0.0);
+        assert_eq!(
+            zone.update([(1, near), (2, far)]),
+            vec![TriggerEvent::Enter(1)]
+        );
+        assert_eq!(
+            zone.update([(1, near), (2, near), (3, far)]),
+            vec![TriggerEvent::Stay(1), TriggerEvent::Enter(2)]
+        );
+        // 1 moves out, 2 is no longer tracked
+        assert_eq!(
+            zone.update([(1, far), (3, near)]),
+            vec![
+                TriggerEvent::Enter(3),
+                TriggerEvent::Exit(1),
+                TriggerEvent::Exit(2)
+            ]
+        );
+        assert_eq!(zone.inside(), &[3]);
+        assert_eq!(zone.clear(), vec![TriggerEvent::Exit(3)]);
+        assert_eq!(zone.update([]), vec![]);
+    }
+
+    #[test]
+    fn test_shapes_contain() {
+        let aabb = Aabb::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 2.0, 3.0));
+        assert!(Volume::contains(&aabb, &Vector3::new(1.0, 1.0, 1.0)));
+        assert!(!Volume::contains(&aabb, &Vector3::new(1.0, 1.0, 3.5)));
+        let sphere = Sphere::new(Vector3::new(1.0, 1.0, 1.0), 1.0);
+        assert!(Volume::contains(&sphere, &Vector3::new(1.0, 2.0, 1.0)));
+        assert!(!Volume::contains(&sphere, &Vector3::new(2.0, 2.0, 1.0)));
+    }
+}
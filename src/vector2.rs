@@ -1,10 +1,10 @@
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
-use crate::Vector4;
+use crate::{AsPrimitive, Float, Rad, Rounding, Vector4, VectorError, WrongLength};
 
 use super::Vector3;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[repr(C)]
 pub struct Vector2<T> {
     pub x: T,
@@ -18,6 +18,17 @@ where
     pub const fn new(x: T, y: T) -> Vector2<T> {
         Vector2 { x, y }
     }
+
+    /// Calculate the dot product of two vectors.
+    pub fn dot(&self, other: &Vector2<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Z component of the cross product of the vectors extended to 3D, positive
+    /// when `other` is counter-clockwise from self.
+    pub fn perp_dot(&self, other: &Vector2<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
 }
 
 impl<T> From<[T; 2]> for Vector2<T>
@@ -29,6 +40,20 @@ where
     }
 }
 
+impl<T: Copy> TryFrom<&[T]> for Vector2<T> {
+    type Error = WrongLength;
+
+    fn try_from(v: &[T]) -> Result<Self, Self::Error> {
+        match *v {
+            [x, y] => Ok(Vector2 { x, y }),
+            _ => Err(WrongLength {
+                expected: 2,
+                found: v.len(),
+            }),
+        }
+    }
+}
+
 impl<T> From<Vector3<T>> for Vector2<T>
 where
     T: Default + Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
@@ -52,6 +77,10 @@ impl Vector2<f32> {
         (self.x.powi(2) + self.y.powi(2)).sqrt()
     }
 
+    pub fn magnitude_recip(&self) -> f32 {
+        self.magnitude().recip()
+    }
+
     pub fn normalize(&self) -> Vector2<f32> {
         let inv_sqrt = self.magnitude().recip();
         Vector2 {
@@ -60,24 +89,32 @@ impl Vector2<f32> {
         }
     }
 
-    pub fn clamp_mag(&mut self, limit: f32) {
-        let mag = self.magnitude();
-        if mag > limit {
-            let inv_mag = mag.recip();
-            self.x *= inv_mag * limit;
-            self.y *= inv_mag * limit;
+    /// Normalize using an approximate reciprocal square root, trading a
+    /// relative error of about 1e-5 for speed.
+    #[cfg(feature = "fast-rsqrt")]
+    pub fn normalize_fast(&self) -> Vector2<f32> {
+        let inv_sqrt = crate::float::rsqrt_fast(self.x * self.x + self.y * self.y);
+        Vector2 {
+            x: self.x * inv_sqrt,
+            y: self.y * inv_sqrt,
         }
     }
 
-    pub fn distance(&self, other: &Vector2<f32>) -> f32 {
-        (self.x - other.x).hypot(self.y - other.y)
+    pub fn set_rotation(&mut self, angle: impl Into<Rad<f32>>) {
+        let Rad(angle) = angle.into();
+        let (sin, cos) = angle.sin_cos();
+        *self = Vector2 {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        };
     }
 
-    pub fn set_rotation(&mut self, angle: f32) {
-        let sin = angle.sin();
-        let cos = angle.cos();
-        self.x = self.x * cos - self.y * sin;
-        self.y = self.x * sin + self.y * cos;
+    /// Integer coordinates of the grid point `snap` would round to.
+    pub fn snap_to_grid(&self, grid_size: f32) -> Vector2<i32> {
+        Vector2 {
+            x: (self.x / grid_size).round() as i32,
+            y: (self.y / grid_size).round() as i32,
+        }
     }
 }
 
@@ -86,6 +123,10 @@ impl Vector2<f64> {
         (self.x.powi(2) + self.y.powi(2)).sqrt()
     }
 
+    pub fn magnitude_recip(&self) -> f64 {
+        self.magnitude().recip()
+    }
+
     pub fn normalize(&self) -> Vector2<f64> {
         let inv_sqrt = self.magnitude().recip();
         Vector2 {
@@ -93,6 +134,391 @@ impl Vector2<f64> {
             y: self.y * inv_sqrt,
         }
     }
+
+    /// Integer coordinates of the grid point `snap` would round to.
+    pub fn snap_to_grid(&self, grid_size: f64) -> Vector2<i32> {
+        Vector2 {
+            x: (self.x / grid_size).round() as i32,
+            y: (self.y / grid_size).round() as i32,
+        }
+    }
+}
+
+impl<T: Float> Vector2<T> {
+    /// Squared length, cheaper than magnitude for comparisons.
+    pub fn magnitude_squared(&self) -> T {
+        self.x * self.x + self.y * self.y
+    }
+
+    /// Uses `hypot`, so far apart points don't overflow to infinity.
+    pub fn distance(&self, other: &Vector2<T>) -> T {
+        let d = *other - *self;
+        d.x.hypot(d.y)
+    }
+
+    pub fn distance_squared(&self, other: &Vector2<T>) -> T {
+        (*other - *self).magnitude_squared()
+    }
+
+    /// Shorten to at most `limit` length, keeping the direction.
+    pub fn clamped_mag(&self, limit: T) -> Vector2<T> {
+        let mag_sq = self.magnitude_squared();
+        if mag_sq > limit * limit {
+            *self * (limit / mag_sq.sqrt())
+        } else {
+            *self
+        }
+    }
+
+    pub fn clamp_mag(&mut self, limit: T) {
+        *self = self.clamped_mag(limit);
+    }
+
+    /// Clamp each component to [0, 1].
+    pub fn saturate(&self) -> Vector2<T> {
+        Vector2 {
+            x: self.x.max(T::ZERO).min(T::ONE),
+            y: self.y.max(T::ZERO).min(T::ONE),
+        }
+    }
+
+    /// 0 for each component below the edge, 1 otherwise (like GLSL step).
+    pub fn step(&self, edge: &Vector2<T>) -> Vector2<T> {
+        let step = |e: T, v: T| if v < e { T::ZERO } else { T::ONE };
+        Vector2 {
+            x: step(edge.x, self.x),
+            y: step(edge.y, self.y),
+        }
+    }
+
+    /// Component-wise square root.
+    pub fn sqrt(&self) -> Vector2<T> {
+        Vector2 {
+            x: self.x.sqrt(),
+            y: self.y.sqrt(),
+        }
+    }
+
+    /// Component-wise e^x.
+    pub fn exp(&self) -> Vector2<T> {
+        Vector2 {
+            x: self.x.exp(),
+            y: self.y.exp(),
+        }
+    }
+
+    /// Component-wise natural logarithm.
+    pub fn ln(&self) -> Vector2<T> {
+        Vector2 {
+            x: self.x.ln(),
+            y: self.y.ln(),
+        }
+    }
+
+    /// Raise each component to the power n.
+    pub fn powf(&self, n: T) -> Vector2<T> {
+        Vector2 {
+            x: self.x.powf(n),
+            y: self.y.powf(n),
+        }
+    }
+
+    /// Component-wise sine (radians).
+    pub fn sin(&self) -> Vector2<T> {
+        Vector2 {
+            x: self.x.sin(),
+            y: self.y.sin(),
+        }
+    }
+
+    /// Component-wise cosine (radians).
+    pub fn cos(&self) -> Vector2<T> {
+        Vector2 {
+            x: self.x.cos(),
+            y: self.y.cos(),
+        }
+    }
+
+    /// Convert each component from radians to degrees.
+    pub fn to_degrees(&self) -> Vector2<T> {
+        Vector2 {
+            x: self.x.to_degrees(),
+            y: self.y.to_degrees(),
+        }
+    }
+
+    /// Convert each component from degrees to radians.
+    pub fn to_radians(&self) -> Vector2<T> {
+        Vector2 {
+            x: self.x.to_radians(),
+            y: self.y.to_radians(),
+        }
+    }
+
+    /// Unit vector pointing at the given angle counter-clockwise from +x.
+    pub fn from_angle(angle: impl Into<Rad<T>>) -> Vector2<T> {
+        let Rad(angle) = angle.into();
+        Vector2 {
+            x: angle.cos(),
+            y: angle.sin(),
+        }
+    }
+
+    /// Unsigned angle in radians between two vectors, in [0, pi].
+    pub fn angle_between(&self, other: &Vector2<T>) -> T {
+        let dot = self.x * other.x + self.y * other.y;
+        let cross = self.x * other.y - self.y * other.x;
+        cross.abs().atan2(dot)
+    }
+
+    /// Angle in radians to rotate self onto `other`, in [-pi, pi]. Positive is
+    /// counter-clockwise, e.g. `other` lies to the left when facing along self.
+    pub fn signed_angle(&self, other: &Vector2<T>) -> T {
+        let dot = self.x * other.x + self.y * other.y;
+        let cross = self.x * other.y - self.y * other.x;
+        cross.atan2(dot)
+    }
+
+    /// Whether every component is within epsilon of the one of `other`.
+    pub fn approx_eq(&self, other: &Vector2<T>, epsilon: T) -> bool {
+        (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon
+    }
+
+    /// Whether the magnitude is within epsilon of 1.
+    pub fn is_normalized(&self, epsilon: T) -> bool {
+        let len_sq = self.x * self.x + self.y * self.y;
+        (len_sq.sqrt() - T::ONE).abs() <= epsilon
+    }
+
+    /// Rotate toward the direction of `target` by at most `max_angle`,
+    /// keeping the magnitude of self.
+    pub fn rotate_towards(&self, target: &Vector2<T>, max_angle: impl Into<Rad<T>>) -> Vector2<T> {
+        let Rad(max_radians) = max_angle.into();
+        let dot = self.x * target.x + self.y * target.y;
+        let cross = self.x * target.y - self.y * target.x;
+        let angle = cross.atan2(dot);
+        let step = angle.max(-max_radians).min(max_radians);
+        let (sin, cos) = (step.sin(), step.cos());
+        Vector2 {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        }
+    }
+
+    /// Response of a velocity hitting a surface with the given normal: the normal
+    /// part is reflected and scaled by `restitution`, the tangential part is
+    /// scaled by `1 - friction`. Velocities moving away from the surface are
+    /// returned unchanged.
+    pub fn bounce(&self, normal: &Vector2<T>, restitution: T, friction: T) -> Vector2<T> {
+        let along = self.x * normal.x + self.y * normal.y;
+        if along >= T::ZERO {
+            return *self;
+        }
+        let parallel = *normal * (along / (normal.x * normal.x + normal.y * normal.y));
+        let tangential = *self - parallel;
+        tangential * (T::ONE - friction) - parallel * restitution
+    }
+
+    /// Wrap each component into [min, max), as on a torus: leaving one side
+    /// re-enters from the other.
+    pub fn wrap(&self, min: &Vector2<T>, max: &Vector2<T>) -> Vector2<T> {
+        let wrap = |v: T, lo: T, hi: T| {
+            let size = hi - lo;
+            let w = v - size * ((v - lo) / size).floor();
+            // Tiny offsets below `lo` round up to exactly `hi`
+            if w >= hi {
+                lo
+            } else {
+                w
+            }
+        };
+        Vector2 {
+            x: wrap(self.x, min.x, max.x),
+            y: wrap(self.y, min.y, max.y),
+        }
+    }
+
+    /// Round each component to the nearest multiple of `grid_size`.
+    pub fn snap(&self, grid_size: T) -> Vector2<T> {
+        Vector2 {
+            x: (self.x / grid_size).round() * grid_size,
+            y: (self.y / grid_size).round() * grid_size,
+        }
+    }
+}
+
+impl<T: Copy> Vector2<T> {
+    /// Vector3 with `z` appended.
+    pub fn extend(&self, z: T) -> Vector3<T> {
+        Vector3 {
+            x: self.x,
+            y: self.y,
+            z,
+        }
+    }
+
+    /// Per component, the one of `b` where `mask` is set, else the one of `a`.
+    pub fn select(a: &Vector2<T>, b: &Vector2<T>, mask: &Vector2<bool>) -> Vector2<T> {
+        Vector2 {
+            x: if mask.x { b.x } else { a.x },
+            y: if mask.y { b.y } else { a.y },
+        }
+    }
+
+    /// Convert each component to another numeric type, like `as`.
+    pub fn cast<U>(&self) -> Vector2<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        Vector2 {
+            x: self.x.as_(),
+            y: self.y.as_(),
+        }
+    }
+
+    /// Like `TryFrom<&[T]>`, reporting a wrong length as a `VectorError`.
+    pub fn try_from_slice(v: &[T]) -> Result<Vector2<T>, VectorError> {
+        Ok(Vector2::try_from(v)?)
+    }
+
+    pub fn as_f32(&self) -> Vector2<f32>
+    where
+        T: AsPrimitive<f32>,
+    {
+        self.cast()
+    }
+
+    pub fn as_f64(&self) -> Vector2<f64>
+    where
+        T: AsPrimitive<f64>,
+    {
+        self.cast()
+    }
+
+    /// Convert to i32 components; floats are truncated toward zero.
+    pub fn as_i32(&self) -> Vector2<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast()
+    }
+}
+
+impl<T: Float> Vector2<T> {
+    /// Interpolate each component from `a` to `b` by its own weight, e.g. to
+    /// smooth some axes faster than others.
+    pub fn blend(a: &Vector2<T>, b: &Vector2<T>, weights: &Vector2<T>) -> Vector2<T> {
+        Vector2 {
+            x: a.x + (b.x - a.x) * weights.x,
+            y: a.y + (b.y - a.y) * weights.y,
+        }
+    }
+
+    /// Order by magnitude, e.g. `points.sort_by(|a, b| a.cmp_by_magnitude(b))`.
+    /// NaN magnitudes sort after all others.
+    pub fn cmp_by_magnitude(&self, other: &Vector2<T>) -> std::cmp::Ordering {
+        let a = self.x * self.x + self.y * self.y;
+        let b = other.x * other.x + other.y * other.y;
+        a.total_cmp(&b)
+    }
+
+    // Err unless every component is finite
+    fn check_finite(&self) -> Result<(), VectorError> {
+        if self.x.is_finite() && self.y.is_finite() {
+            Ok(())
+        } else {
+            Err(VectorError::NonFinite)
+        }
+    }
+
+    /// Unit vector in the same direction, or an error instead of NaN
+    /// components for zero length or non-finite input. Components are scaled
+    /// by the largest one first, so the squares can't overflow or underflow.
+    pub fn try_normalize(&self) -> Result<Vector2<T>, VectorError> {
+        self.check_finite()?;
+        let scale = self.x.abs().max(self.y.abs());
+        if scale == T::ZERO {
+            return Err(VectorError::ZeroLength);
+        }
+        let v = *self * scale.recip();
+        let inv = v.dot(&v).sqrt().recip();
+        Ok(v * inv)
+    }
+
+    /// Like `angle_between`, but an error if either vector has zero length
+    /// or a non-finite component, where the angle is meaningless.
+    pub fn try_angle_between(&self, other: &Vector2<T>) -> Result<T, VectorError> {
+        self.check_finite()?;
+        other.check_finite()?;
+        if *self == Vector2::default() || *other == Vector2::default() {
+            return Err(VectorError::ZeroLength);
+        }
+        Ok(self.angle_between(other))
+    }
+
+    /// Round each component with the given mode, then convert it like `as`.
+    pub fn cast_rounded<U>(&self, mode: Rounding) -> Vector2<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        Vector2 {
+            x: mode.apply(self.x).as_(),
+            y: mode.apply(self.y).as_(),
+        }
+    }
+
+    /// Integer vector of the cell containing each component, e.g. world
+    /// position to tile coordinates.
+    pub fn floor_as_ivec(&self) -> Vector2<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast_rounded(Rounding::Floor)
+    }
+
+    pub fn ceil_as_ivec(&self) -> Vector2<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast_rounded(Rounding::Ceil)
+    }
+
+    /// Nearest integers, halfway cases away from zero.
+    pub fn round_as_ivec(&self) -> Vector2<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast_rounded(Rounding::Round)
+    }
+
+    /// Index of the cell of size `step` containing each component, for
+    /// bucketing positions when hashing or deduplicating. Division and floor
+    /// are correctly rounded IEEE operations, so the result is the same on
+    /// every platform. Out of range values saturate and NaN becomes 0, like `as`.
+    pub fn quantize(&self, step: T) -> Vector2<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        Vector2 {
+            x: (self.x / step).floor().as_(),
+            y: (self.y / step).floor().as_(),
+        }
+    }
+
+    /// Center of the cell `quantize` returned. Being half a cell from either
+    /// edge, it quantizes back to `cell` for every i32 with f64, and while
+    /// |cell| is below 2^22 with f32, where a float can no longer tell the
+    /// center from the edges.
+    pub fn dequantize(cell: &Vector2<i32>, step: T) -> Vector2<T>
+    where
+        i32: AsPrimitive<T>,
+    {
+        let half = (T::ONE + T::ONE).recip();
+        Vector2 {
+            x: (cell.x.as_() + half) * step,
+            y: (cell.y.as_() + half) * step,
+        }
+    }
 }
 
 impl<T: Add<Output = T>> Add<Vector2<T>> for Vector2<T> {
@@ -142,6 +568,18 @@ impl<T: Mul<Output = T> + Copy> Mul<T> for Vector2<T> {
     }
 }
 
+/// Component-wise product.
+impl<T: Mul<Output = T>> Mul<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn mul(self, rhs: Vector2<T>) -> Self::Output {
+        Vector2 {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+        }
+    }
+}
+
 impl<T: MulAssign + Copy> MulAssign<T> for Vector2<T> {
     fn mul_assign(&mut self, rhs: T) {
         self.x *= rhs;
@@ -149,9 +587,61 @@ impl<T: MulAssign + Copy> MulAssign<T> for Vector2<T> {
     }
 }
 
+/// Adds the scalar to every component, e.g. `rect.max + margin` to grow a
+/// rectangle.
+impl<T: Add<Output = T> + Copy> Add<T> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn add(self, rhs: T) -> Self::Output {
+        Vector2 {
+            x: self.x + rhs,
+            y: self.y + rhs,
+        }
+    }
+}
+
+impl<T: AddAssign + Copy> AddAssign<T> for Vector2<T> {
+    fn add_assign(&mut self, rhs: T) {
+        self.x += rhs;
+        self.y += rhs;
+    }
+}
+
+/// Subtracts the scalar from every component, e.g. `rect.min - margin`.
+impl<T: Sub<Output = T> + Copy> Sub<T> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        Vector2 {
+            x: self.x - rhs,
+            y: self.y - rhs,
+        }
+    }
+}
+
+impl<T: SubAssign + Copy> SubAssign<T> for Vector2<T> {
+    fn sub_assign(&mut self, rhs: T) {
+        self.x -= rhs;
+        self.y -= rhs;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Deg;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn test_vector2_distance_large() {
+        let a = Vector2::new(1e20f32, 0.0);
+        let b = Vector2::new(-1e20f32, 0.0);
+        assert_eq!(a.distance(&b), 2e20);
+        assert_eq!(
+            Vector2::new(3.0f64, 0.0).distance(&Vector2::new(0.0, 4.0)),
+            5.0
+        );
+    }
 
     #[test]
     fn test_vector2_new() {
@@ -237,4 +727,234 @@ mod tests {
         assert_eq!(vector.x, 2.0);
         assert_eq!(vector.y, 4.0);
     }
+
+    #[test]
+    fn test_vector2_saturate() {
+        let vector = Vector2::new(-0.5, 1.5).saturate();
+        assert_eq!(vector, Vector2::new(0.0, 1.0));
+        let vector = Vector2::new(0.25, 0.75).saturate();
+        assert_eq!(vector, Vector2::new(0.25, 0.75));
+    }
+
+    #[test]
+    fn test_vector2_step() {
+        let vector = Vector2::new(0.2, 0.8).step(&Vector2::new(0.5, 0.5));
+        assert_eq!(vector, Vector2::new(0.0, 1.0));
+        let vector = Vector2::new(0.5, 0.5).step(&Vector2::new(0.5, 0.5));
+        assert_eq!(vector, Vector2::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn test_vector2_transcendental() {
+        let vector = Vector2::new(4.0, 9.0);
+        assert_eq!(vector.sqrt(), Vector2::new(2.0, 3.0));
+        assert_eq!(vector.powf(0.5), Vector2::new(2.0, 3.0));
+        assert_eq!(
+            Vector2::new(0.0, 1.0).exp(),
+            Vector2::new(1.0, std::f64::consts::E)
+        );
+        assert_eq!(
+            Vector2::new(1.0, std::f64::consts::E).ln(),
+            Vector2::new(0.0, 1.0)
+        );
+        assert_eq!(Vector2::new(0.0, 0.0).sin(), Vector2::new(0.0, 0.0));
+        assert_eq!(Vector2::new(0.0, 0.0).cos(), Vector2::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn test_vector2_degrees_radians() {
+        let vector = Vector2::new(180.0, 90.0);
+        assert_eq!(vector.to_radians(), Vector2::new(PI, FRAC_PI_2));
+        assert_eq!(vector.to_radians().to_degrees(), vector);
+    }
+
+    #[test]
+    fn test_vector2_dot_and_perp_dot() {
+        let (a, b) = (Vector2::new(2, 1), Vector2::new(-1, 3));
+        assert_eq!(a.dot(&b), 1);
+        assert_eq!(a.perp_dot(&b), 7);
+        assert_eq!(b.perp_dot(&a), -7);
+    }
+
+    #[test]
+    fn test_vector2_from_angle() {
+        let vector = Vector2::from_angle(Rad(0.0));
+        assert_eq!(vector, Vector2::new(1.0, 0.0));
+        let vector = Vector2::from_angle(Deg(90.0f64));
+        assert!(vector.x.abs() < 1e-12);
+        assert_eq!(vector.y, 1.0);
+    }
+
+    #[test]
+    fn test_vector2_set_rotation() {
+        let mut v = Vector2::new(2.0f32, 0.0);
+        v.set_rotation(Deg(90.0));
+        assert!(v.x.abs() < 1e-6);
+        assert!((v.y - 2.0).abs() < 1e-6);
+        let mut v = Vector2::new(1.0f32, 1.0);
+        v.set_rotation(Rad(std::f32::consts::PI));
+        assert!((v - Vector2::new(-1.0, -1.0)).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn test_vector2_angle_between() {
+        let a = Vector2::new(1.0, 0.0);
+        assert_eq!(a.angle_between(&Vector2::new(0.0, 2.0)), FRAC_PI_2);
+        assert_eq!(a.angle_between(&Vector2::new(0.0, -2.0)), FRAC_PI_2);
+        assert_eq!(a.angle_between(&Vector2::new(-3.0, 0.0)), PI);
+        assert_eq!(a.angle_between(&a), 0.0);
+    }
+
+    #[test]
+    fn test_vector2_is_normalized() {
+        assert!(Vector2::new(0.6, 0.8).is_normalized(1e-6));
+        assert!(Vector2::<f64>::new(3.0, 4.0)
+            .normalize()
+            .is_normalized(1e-6));
+        assert!(!Vector2::new(3.0, 4.0).is_normalized(1e-6));
+        assert!(Vector2::new(1.05, 0.0).is_normalized(0.1));
+    }
+
+    #[test]
+    fn test_vector2_magnitude_recip() {
+        assert_eq!(Vector2::<f32>::new(3.0, 4.0).magnitude_recip(), 0.2);
+        assert_eq!(Vector2::<f64>::new(3.0, 4.0).magnitude_recip(), 0.2);
+    }
+
+    #[cfg(feature = "fast-rsqrt")]
+    #[test]
+    fn test_vector2_normalize_fast() {
+        let vector = Vector2::<f32>::new(3.0, 4.0).normalize_fast();
+        assert!((vector.x - 0.6).abs() < 1e-4);
+        assert!((vector.y - 0.8).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_vector2_rotate_towards() {
+        let a = Vector2::new(2.0, 0.0);
+        let rotated = a.rotate_towards(&Vector2::new(0.0, 1.0), Rad(FRAC_PI_4));
+        assert!((rotated.x - 2.0f64.sqrt()).abs() < 1e-12);
+        assert!((rotated.y - 2.0f64.sqrt()).abs() < 1e-12);
+        let rotated = a.rotate_towards(&Vector2::new(0.0, -1.0), Deg(180.0));
+        assert!(rotated.x.abs() < 1e-12);
+        assert!((rotated.y + 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_vector2_bounce() {
+        let v = Vector2::new(4.0, -2.0);
+        let n = Vector2::new(0.0, 1.0);
+        assert_eq!(v.bounce(&n, 1.0, 0.0), Vector2::new(4.0, 2.0));
+        assert_eq!(v.bounce(&n, 0.5, 0.25), Vector2::new(3.0, 1.0));
+        assert_eq!(
+            Vector2::new(1.0, 1.0).bounce(&n, 0.5, 0.5),
+            Vector2::new(1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_vector2_wrap_and_snap() {
+        let min = Vector2::new(0.0, -10.0);
+        let max = Vector2::new(100.0, 10.0);
+        assert_eq!(
+            Vector2::new(105.0, -12.5).wrap(&min, &max),
+            Vector2::new(5.0, 7.5)
+        );
+        assert_eq!(
+            Vector2::new(100.0, 3.0).wrap(&min, &max),
+            Vector2::new(0.0, 3.0)
+        );
+        // Just below `min` would round to `max`, outside the range
+        let (min, max) = (Vector2::new(0.0f32, 0.0), Vector2::new(100.0, 1.0));
+        assert_eq!(
+            Vector2::new(-1e-9, 0.5).wrap(&min, &max),
+            Vector2::new(0.0, 0.5)
+        );
+        let v = Vector2::new(3.7f32, -1.2);
+        assert_eq!(v.snap(0.5), Vector2::new(3.5, -1.0));
+        assert_eq!(v.snap_to_grid(0.5), Vector2::new(7, -2));
+        assert_eq!(
+            Vector2::new(-0.25f64, 1.0).snap_to_grid(0.5),
+            Vector2::new(-1, 2)
+        );
+    }
+
+    #[test]
+    fn test_vector2_to_ivec() {
+        let v = Vector2::new(-0.5f32, 3.5);
+        assert_eq!(v.floor_as_ivec(), Vector2::new(-1, 3));
+        assert_eq!(v.ceil_as_ivec(), Vector2::new(0, 4));
+        assert_eq!(v.round_as_ivec(), Vector2::new(-1, 4));
+        assert_eq!(
+            Vector2::new(f32::NAN, 1e20).floor_as_ivec(),
+            Vector2::new(0, i32::MAX)
+        );
+        assert_eq!(v.as_i32(), Vector2::new(0, 3));
+    }
+
+    #[test]
+    fn test_vector2_try_ops() {
+        let v = Vector2::new(3.0f64, -4.0);
+        assert_eq!(v.try_normalize(), Ok(v.normalize()));
+        let zero = Vector2::new(0.0, 0.0);
+        assert_eq!(zero.try_normalize(), Err(VectorError::ZeroLength));
+        assert_eq!(
+            Vector2::new(f64::INFINITY, 1.0).try_normalize(),
+            Err(VectorError::NonFinite)
+        );
+        assert_eq!(
+            v.try_angle_between(&Vector2::new(4.0, 3.0)),
+            Ok(v.angle_between(&Vector2::new(4.0, 3.0)))
+        );
+        assert_eq!(v.try_angle_between(&zero), Err(VectorError::ZeroLength));
+        assert_eq!(
+            Vector2::try_from_slice(&[1.0, 2.0, 3.0]),
+            Err(VectorError::WrongDimension {
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_vector2_ordering() {
+        let mut points = vec![Vector2::new(2, 1), Vector2::new(1, 5), Vector2::new(2, 0)];
+        points.sort();
+        assert_eq!(
+            points,
+            vec![Vector2::new(1, 5), Vector2::new(2, 0), Vector2::new(2, 1)]
+        );
+        assert!(Vector2::new(0.5, 9.0) < Vector2::new(1.0, 0.0));
+
+        let mut v = [
+            Vector2::new(3.0, 4.0),
+            Vector2::new(-1.0, 0.0),
+            Vector2::new(0.0, 2.0),
+        ];
+        v.sort_by(|a, b| a.cmp_by_magnitude(b));
+        assert_eq!(v[0], Vector2::new(-1.0, 0.0));
+        assert_eq!(v[2], Vector2::new(3.0, 4.0));
+    }
+
+    #[test]
+    fn test_vector2_signed_angle() {
+        let forward = Vector2::new(1.0, 0.0);
+        assert!((forward.signed_angle(&Vector2::new(0.0, 2.0)) - FRAC_PI_2).abs() < 1e-12);
+        assert!((forward.signed_angle(&Vector2::new(1.0, -1.0)) + FRAC_PI_4).abs() < 1e-12);
+        assert_eq!(forward.signed_angle(&forward), 0.0);
+    }
+
+    #[test]
+    fn test_vector2_mul_componentwise() {
+        let v = Vector2::new(2.0, -3.0) * Vector2::new(0.5, 2.0);
+        assert_eq!(v, Vector2::new(1.0, -6.0));
+    }
+
+    #[test]
+    fn test_vector2_approx_eq() {
+        let v = Vector2::new(0.1 + 0.2, 1.0);
+        assert_ne!(v, Vector2::new(0.3, 1.0));
+        assert!(v.approx_eq(&Vector2::new(0.3, 1.0), 1e-12));
+        assert!(!v.approx_eq(&Vector2::new(0.3, 1.1), 1e-3));
+    }
 }
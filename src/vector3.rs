@@ -1,10 +1,10 @@
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
-use crate::Vector2;
+use crate::{AsPrimitive, Float, Rad, Rounding, Vector2, VectorError, WrongLength};
 
 use super::Vector4;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[repr(C)]
 pub struct Vector3<T> {
     pub x: T,
@@ -24,12 +24,12 @@ where
         Vector3 { x, y, z }
     }
 
-    // Calculate the dot product of two vectors
+    /// Calculate the dot product of two vectors.
     pub fn dot(&self, other: &Vector3<T>) -> T {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
 
-    // Calculate the cross product of two vectors
+    /// Calculate the cross product of two vectors.
     pub fn cross(&self, other: &Vector3<T>) -> Vector3<T> {
         Vector3 {
             x: self.y * other.z - self.z * other.y,
@@ -52,6 +52,20 @@ where
     }
 }
 
+impl<T: Copy> TryFrom<&[T]> for Vector3<T> {
+    type Error = WrongLength;
+
+    fn try_from(v: &[T]) -> Result<Self, Self::Error> {
+        match *v {
+            [x, y, z] => Ok(Vector3 { x, y, z }),
+            _ => Err(WrongLength {
+                expected: 3,
+                found: v.len(),
+            }),
+        }
+    }
+}
+
 impl<T> From<Vector2<T>> for Vector3<T>
 where
     T: Default
@@ -91,6 +105,10 @@ impl Vector3<f32> {
         (self.x.powi(2) + self.y.powi(2) + self.z.powi(2)).sqrt()
     }
 
+    pub fn magnitude_recip(&self) -> f32 {
+        self.magnitude().recip()
+    }
+
     pub fn normalize(&self) -> Vector3<f32> {
         let inv_sqrt = self.magnitude().recip();
         Vector3 {
@@ -99,6 +117,19 @@ impl Vector3<f32> {
             z: self.z * inv_sqrt,
         }
     }
+
+    /// Normalize using an approximate reciprocal square root, trading a
+    /// relative error of about 1e-5 for speed.
+    #[cfg(feature = "fast-rsqrt")]
+    pub fn normalize_fast(&self) -> Vector3<f32> {
+        let inv_sqrt =
+            crate::float::rsqrt_fast(self.x * self.x + self.y * self.y + self.z * self.z);
+        Vector3 {
+            x: self.x * inv_sqrt,
+            y: self.y * inv_sqrt,
+            z: self.z * inv_sqrt,
+        }
+    }
 }
 
 impl Vector3<f64> {
@@ -106,6 +137,10 @@ impl Vector3<f64> {
         (self.x.powi(2) + self.y.powi(2) + self.z.powi(2)).sqrt()
     }
 
+    pub fn magnitude_recip(&self) -> f64 {
+        self.magnitude().recip()
+    }
+
     pub fn normalize(&self) -> Vector3<f64> {
         let inv_sqrt = self.magnitude().recip();
         Vector3 {
@@ -116,6 +151,451 @@ impl Vector3<f64> {
     }
 }
 
+impl<T: Float> Vector3<T> {
+    /// Squared length, cheaper than magnitude for comparisons.
+    pub fn magnitude_squared(&self) -> T {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+
+    /// Uses `hypot`, so far apart points don't overflow to infinity.
+    pub fn distance(&self, other: &Vector3<T>) -> T {
+        let d = *other - *self;
+        d.x.hypot(d.y).hypot(d.z)
+    }
+
+    pub fn distance_squared(&self, other: &Vector3<T>) -> T {
+        (*other - *self).magnitude_squared()
+    }
+
+    /// Shorten to at most `limit` length, keeping the direction.
+    pub fn clamped_mag(&self, limit: T) -> Vector3<T> {
+        let mag_sq = self.magnitude_squared();
+        if mag_sq > limit * limit {
+            *self * (limit / mag_sq.sqrt())
+        } else {
+            *self
+        }
+    }
+
+    pub fn clamp_mag(&mut self, limit: T) {
+        *self = self.clamped_mag(limit);
+    }
+
+    /// Clamp each component to [0, 1].
+    pub fn saturate(&self) -> Vector3<T> {
+        Vector3 {
+            x: self.x.max(T::ZERO).min(T::ONE),
+            y: self.y.max(T::ZERO).min(T::ONE),
+            z: self.z.max(T::ZERO).min(T::ONE),
+        }
+    }
+
+    /// 0 for each component below the edge, 1 otherwise (like GLSL step).
+    pub fn step(&self, edge: &Vector3<T>) -> Vector3<T> {
+        let step = |e: T, v: T| if v < e { T::ZERO } else { T::ONE };
+        Vector3 {
+            x: step(edge.x, self.x),
+            y: step(edge.y, self.y),
+            z: step(edge.z, self.z),
+        }
+    }
+
+    /// Component-wise square root.
+    pub fn sqrt(&self) -> Vector3<T> {
+        Vector3 {
+            x: self.x.sqrt(),
+            y: self.y.sqrt(),
+            z: self.z.sqrt(),
+        }
+    }
+
+    /// Component-wise e^x.
+    pub fn exp(&self) -> Vector3<T> {
+        Vector3 {
+            x: self.x.exp(),
+            y: self.y.exp(),
+            z: self.z.exp(),
+        }
+    }
+
+    /// Component-wise natural logarithm.
+    pub fn ln(&self) -> Vector3<T> {
+        Vector3 {
+            x: self.x.ln(),
+            y: self.y.ln(),
+            z: self.z.ln(),
+        }
+    }
+
+    /// Raise each component to the power n.
+    pub fn powf(&self, n: T) -> Vector3<T> {
+        Vector3 {
+            x: self.x.powf(n),
+            y: self.y.powf(n),
+            z: self.z.powf(n),
+        }
+    }
+
+    /// Component-wise sine (radians).
+    pub fn sin(&self) -> Vector3<T> {
+        Vector3 {
+            x: self.x.sin(),
+            y: self.y.sin(),
+            z: self.z.sin(),
+        }
+    }
+
+    /// Component-wise cosine (radians).
+    pub fn cos(&self) -> Vector3<T> {
+        Vector3 {
+            x: self.x.cos(),
+            y: self.y.cos(),
+            z: self.z.cos(),
+        }
+    }
+
+    /// Convert each component from radians to degrees.
+    pub fn to_degrees(&self) -> Vector3<T> {
+        Vector3 {
+            x: self.x.to_degrees(),
+            y: self.y.to_degrees(),
+            z: self.z.to_degrees(),
+        }
+    }
+
+    /// Convert each component from degrees to radians.
+    pub fn to_radians(&self) -> Vector3<T> {
+        Vector3 {
+            x: self.x.to_radians(),
+            y: self.y.to_radians(),
+            z: self.z.to_radians(),
+        }
+    }
+
+    /// Unsigned angle in radians between two vectors, in [0, pi].
+    pub fn angle_between(&self, other: &Vector3<T>) -> T {
+        let cross = self.cross(other);
+        let sin = (cross.x * cross.x + cross.y * cross.y + cross.z * cross.z).sqrt();
+        sin.atan2(self.dot(other))
+    }
+
+    /// Angle in radians to rotate self onto `other` about `axis`, in [-pi, pi].
+    /// Positive is counter-clockwise seen from the tip of `axis`, so with +y as
+    /// the axis the sign tells left from right turns.
+    pub fn signed_angle(&self, other: &Vector3<T>, axis: &Vector3<T>) -> T {
+        let angle = self.angle_between(other);
+        if self.cross(other).dot(axis) < T::ZERO {
+            -angle
+        } else {
+            angle
+        }
+    }
+
+    /// Whether every component is within epsilon of the one of `other`.
+    pub fn approx_eq(&self, other: &Vector3<T>, epsilon: T) -> bool {
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+    }
+
+    /// Whether the magnitude is within epsilon of 1.
+    pub fn is_normalized(&self, epsilon: T) -> bool {
+        let len_sq = self.dot(self);
+        (len_sq.sqrt() - T::ONE).abs() <= epsilon
+    }
+
+    /// Split into the part along `normal` and the part perpendicular to it,
+    /// returned as (parallel, perpendicular). `normal` does not need to be unit length.
+    pub fn decompose(&self, normal: &Vector3<T>) -> (Vector3<T>, Vector3<T>) {
+        let parallel = *normal * (self.dot(normal) / normal.dot(normal));
+        (parallel, *self - parallel)
+    }
+
+    /// Remove the component along the plane normal, leaving the part lying in the plane.
+    pub fn project_onto_plane(&self, normal: &Vector3<T>) -> Vector3<T> {
+        self.decompose(normal).1
+    }
+
+    /// Rotate toward the direction of `target` by at most `max_angle`,
+    /// keeping the magnitude of self.
+    pub fn rotate_towards(&self, target: &Vector3<T>, max_angle: impl Into<Rad<T>>) -> Vector3<T> {
+        let Rad(max_radians) = max_angle.into();
+        let len = self.dot(self).sqrt();
+        let target_len = target.dot(target).sqrt();
+        if len == T::ZERO || target_len == T::ZERO {
+            return *self;
+        }
+        let from = *self * len.recip();
+        let to = *target * target_len.recip();
+        let angle = from.angle_between(&to);
+        if angle <= max_radians {
+            return to * len;
+        }
+
+        // Unit vector perpendicular to `from` in the plane of rotation. Opposite
+        // directions leave the plane undefined, so any perpendicular works
+        let perp = to - from * from.dot(&to);
+        let perp = if perp.dot(&perp) > T::ZERO {
+            perp * perp.dot(&perp).sqrt().recip()
+        } else {
+            from.any_orthogonal()
+        };
+        (from * max_radians.cos() + perp * max_radians.sin()) * len
+    }
+
+    /// Rotate counter-clockwise about the unit `axis` by `angle` (Rodrigues'
+    /// rotation formula).
+    pub fn rotate_around(&self, axis: &Vector3<T>, angle: impl Into<Rad<T>>) -> Vector3<T> {
+        let Rad(angle) = angle.into();
+        let (sin, cos) = (angle.sin(), angle.cos());
+        *self * cos + axis.cross(self) * sin + *axis * (axis.dot(self) * (T::ONE - cos))
+    }
+
+    /// Some unit vector perpendicular to self. The zero vector and non-finite
+    /// input yield the x axis.
+    pub fn any_orthogonal(&self) -> Vector3<T> {
+        // Swap in the larger of x and z, so the result is only zero when self
+        // is. `try_normalize` scales first, so tiny inputs don't underflow
+        let v = if self.x.abs() > self.z.abs() {
+            Vector3::new(-self.y, self.x, T::ZERO)
+        } else {
+            Vector3::new(T::ZERO, -self.z, self.y)
+        };
+        v.try_normalize()
+            .unwrap_or(Vector3::new(T::ONE, T::ZERO, T::ZERO))
+    }
+
+    /// Response of a velocity hitting a surface with the given normal: the normal
+    /// part is reflected and scaled by `restitution`, the tangential part is
+    /// scaled by `1 - friction`. Velocities moving away from the surface are
+    /// returned unchanged.
+    pub fn bounce(&self, normal: &Vector3<T>, restitution: T, friction: T) -> Vector3<T> {
+        if self.dot(normal) >= T::ZERO {
+            return *self;
+        }
+        let (parallel, tangential) = self.decompose(normal);
+        tangential * (T::ONE - friction) - parallel * restitution
+    }
+
+    /// Wrap each component into [min, max), as on a torus: leaving one side
+    /// re-enters from the other.
+    pub fn wrap(&self, min: &Vector3<T>, max: &Vector3<T>) -> Vector3<T> {
+        let wrap = |v: T, lo: T, hi: T| {
+            let size = hi - lo;
+            let w = v - size * ((v - lo) / size).floor();
+            // Tiny offsets below `lo` round up to exactly `hi`
+            if w >= hi {
+                lo
+            } else {
+                w
+            }
+        };
+        Vector3 {
+            x: wrap(self.x, min.x, max.x),
+            y: wrap(self.y, min.y, max.y),
+            z: wrap(self.z, min.z, max.z),
+        }
+    }
+
+    /// Round each component to the nearest multiple of `grid_size`.
+    pub fn snap(&self, grid_size: T) -> Vector3<T> {
+        Vector3 {
+            x: (self.x / grid_size).round() * grid_size,
+            y: (self.y / grid_size).round() * grid_size,
+            z: (self.z / grid_size).round() * grid_size,
+        }
+    }
+}
+
+impl<T: Copy> Vector3<T> {
+    /// Vector4 with `w` appended, e.g. 1 for points and 0 for directions.
+    pub fn extend(&self, w: T) -> Vector4<T> {
+        Vector4 {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            w,
+        }
+    }
+
+    /// Vector2 of x and y, dropping z.
+    pub fn truncate(&self) -> Vector2<T> {
+        Vector2 {
+            x: self.x,
+            y: self.y,
+        }
+    }
+
+    /// Per component, the one of `b` where `mask` is set, else the one of `a`.
+    pub fn select(a: &Vector3<T>, b: &Vector3<T>, mask: &Vector3<bool>) -> Vector3<T> {
+        Vector3 {
+            x: if mask.x { b.x } else { a.x },
+            y: if mask.y { b.y } else { a.y },
+            z: if mask.z { b.z } else { a.z },
+        }
+    }
+
+    /// Convert each component to another numeric type, like `as`.
+    pub fn cast<U>(&self) -> Vector3<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        Vector3 {
+            x: self.x.as_(),
+            y: self.y.as_(),
+            z: self.z.as_(),
+        }
+    }
+
+    /// Like `TryFrom<&[T]>`, reporting a wrong length as a `VectorError`.
+    pub fn try_from_slice(v: &[T]) -> Result<Vector3<T>, VectorError> {
+        Ok(Vector3::try_from(v)?)
+    }
+
+    pub fn as_f32(&self) -> Vector3<f32>
+    where
+        T: AsPrimitive<f32>,
+    {
+        self.cast()
+    }
+
+    pub fn as_f64(&self) -> Vector3<f64>
+    where
+        T: AsPrimitive<f64>,
+    {
+        self.cast()
+    }
+
+    /// Convert to i32 components; floats are truncated toward zero.
+    pub fn as_i32(&self) -> Vector3<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast()
+    }
+}
+
+impl<T: Float> Vector3<T> {
+    /// Interpolate each component from `a` to `b` by its own weight, e.g. to
+    /// smooth some axes faster than others.
+    pub fn blend(a: &Vector3<T>, b: &Vector3<T>, weights: &Vector3<T>) -> Vector3<T> {
+        Vector3 {
+            x: a.x + (b.x - a.x) * weights.x,
+            y: a.y + (b.y - a.y) * weights.y,
+            z: a.z + (b.z - a.z) * weights.z,
+        }
+    }
+
+    /// Order by magnitude, e.g. `points.sort_by(|a, b| a.cmp_by_magnitude(b))`.
+    /// NaN magnitudes sort after all others.
+    pub fn cmp_by_magnitude(&self, other: &Vector3<T>) -> std::cmp::Ordering {
+        let a = self.x * self.x + self.y * self.y + self.z * self.z;
+        let b = other.x * other.x + other.y * other.y + other.z * other.z;
+        a.total_cmp(&b)
+    }
+
+    // Err unless every component is finite
+    fn check_finite(&self) -> Result<(), VectorError> {
+        if self.x.is_finite() && self.y.is_finite() && self.z.is_finite() {
+            Ok(())
+        } else {
+            Err(VectorError::NonFinite)
+        }
+    }
+
+    /// Unit vector in the same direction, or an error instead of NaN
+    /// components for zero length or non-finite input. Components are scaled
+    /// by the largest one first, so the squares can't overflow or underflow.
+    pub fn try_normalize(&self) -> Result<Vector3<T>, VectorError> {
+        self.check_finite()?;
+        let scale = self.x.abs().max(self.y.abs()).max(self.z.abs());
+        if scale == T::ZERO {
+            return Err(VectorError::ZeroLength);
+        }
+        let v = *self * scale.recip();
+        let inv = v.dot(&v).sqrt().recip();
+        Ok(v * inv)
+    }
+
+    /// Like `angle_between`, but an error if either vector has zero length
+    /// or a non-finite component, where the angle is meaningless.
+    pub fn try_angle_between(&self, other: &Vector3<T>) -> Result<T, VectorError> {
+        self.check_finite()?;
+        other.check_finite()?;
+        if *self == Vector3::default() || *other == Vector3::default() {
+            return Err(VectorError::ZeroLength);
+        }
+        Ok(self.angle_between(other))
+    }
+
+    /// Round each component with the given mode, then convert it like `as`.
+    pub fn cast_rounded<U>(&self, mode: Rounding) -> Vector3<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        Vector3 {
+            x: mode.apply(self.x).as_(),
+            y: mode.apply(self.y).as_(),
+            z: mode.apply(self.z).as_(),
+        }
+    }
+
+    /// Integer vector of the cell containing each component, e.g. world
+    /// position to tile coordinates.
+    pub fn floor_as_ivec(&self) -> Vector3<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast_rounded(Rounding::Floor)
+    }
+
+    pub fn ceil_as_ivec(&self) -> Vector3<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast_rounded(Rounding::Ceil)
+    }
+
+    /// Nearest integers, halfway cases away from zero.
+    pub fn round_as_ivec(&self) -> Vector3<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast_rounded(Rounding::Round)
+    }
+
+    /// Index of the cell of size `step` containing each component, for
+    /// bucketing positions when hashing or deduplicating. Division and floor
+    /// are correctly rounded IEEE operations, so the result is the same on
+    /// every platform. Out of range values saturate and NaN becomes 0, like `as`.
+    pub fn quantize(&self, step: T) -> Vector3<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        Vector3 {
+            x: (self.x / step).floor().as_(),
+            y: (self.y / step).floor().as_(),
+            z: (self.z / step).floor().as_(),
+        }
+    }
+
+    /// Center of the cell `quantize` returned. Being half a cell from either
+    /// edge, it quantizes back to `cell` for every i32 with f64, and while
+    /// |cell| is below 2^22 with f32, where a float can no longer tell the
+    /// center from the edges.
+    pub fn dequantize(cell: &Vector3<i32>, step: T) -> Vector3<T>
+    where
+        i32: AsPrimitive<T>,
+    {
+        let half = (T::ONE + T::ONE).recip();
+        Vector3 {
+            x: (cell.x.as_() + half) * step,
+            y: (cell.y.as_() + half) * step,
+            z: (cell.z.as_() + half) * step,
+        }
+    }
+}
+
 impl<T: Add<Output = T>> Add<Vector3<T>> for Vector3<T> {
     type Output = Vector3<T>;
 
@@ -168,6 +648,19 @@ impl<T: Mul<Output = T> + Copy> Mul<T> for Vector3<T> {
     }
 }
 
+/// Component-wise product.
+impl<T: Mul<Output = T>> Mul<Vector3<T>> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn mul(self, rhs: Vector3<T>) -> Self::Output {
+        Vector3 {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+            z: self.z * rhs.z,
+        }
+    }
+}
+
 impl<T: MulAssign + Copy> MulAssign<T> for Vector3<T> {
     fn mul_assign(&mut self, rhs: T) {
         self.x *= rhs;
@@ -176,9 +669,65 @@ impl<T: MulAssign + Copy> MulAssign<T> for Vector3<T> {
     }
 }
 
+/// Adds the scalar to every component, e.g. `aabb.max + margin` to grow a
+/// bounding box.
+impl<T: Add<Output = T> + Copy> Add<T> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn add(self, rhs: T) -> Self::Output {
+        Vector3 {
+            x: self.x + rhs,
+            y: self.y + rhs,
+            z: self.z + rhs,
+        }
+    }
+}
+
+impl<T: AddAssign + Copy> AddAssign<T> for Vector3<T> {
+    fn add_assign(&mut self, rhs: T) {
+        self.x += rhs;
+        self.y += rhs;
+        self.z += rhs;
+    }
+}
+
+/// Subtracts the scalar from every component, e.g. `aabb.min - margin`.
+impl<T: Sub<Output = T> + Copy> Sub<T> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        Vector3 {
+            x: self.x - rhs,
+            y: self.y - rhs,
+            z: self.z - rhs,
+        }
+    }
+}
+
+impl<T: SubAssign + Copy> SubAssign<T> for Vector3<T> {
+    fn sub_assign(&mut self, rhs: T) {
+        self.x -= rhs;
+        self.y -= rhs;
+        self.z -= rhs;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Deg, Rad};
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn test_vector3_distance_large() {
+        let a = Vector3::new(1e20f32, 0.0, 0.0);
+        let b = Vector3::new(-1e20f32, 0.0, 0.0);
+        assert_eq!(a.distance(&b), 2e20);
+        assert_eq!(
+            Vector3::new(3.0f64, 0.0, 0.0).distance(&Vector3::new(0.0, 4.0, 0.0)),
+            5.0
+        );
+    }
 
     #[test]
     fn test_vector3_new() {
@@ -280,4 +829,294 @@ mod tests {
         assert_eq!(v1.y, 4.0);
         assert_eq!(v1.z, 6.0);
     }
+
+    #[test]
+    fn test_vector3_saturate() {
+        let v = Vector3::new(-0.5, 0.5, 1.5).saturate();
+        assert_eq!(v, Vector3::new(0.0, 0.5, 1.0));
+    }
+
+    #[test]
+    fn test_vector3_step() {
+        let v = Vector3::new(0.2, 0.5, 0.8).step(&Vector3::new(0.5, 0.5, 0.5));
+        assert_eq!(v, Vector3::new(0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_vector3_transcendental() {
+        let v = Vector3::new(1.0, 4.0, 16.0);
+        assert_eq!(v.sqrt(), Vector3::new(1.0, 2.0, 4.0));
+        assert_eq!(v.powf(2.0), Vector3::new(1.0, 16.0, 256.0));
+        assert_eq!(
+            Vector3::new(0.0, 0.0, 0.0).exp(),
+            Vector3::new(1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            Vector3::new(1.0, 1.0, 1.0).ln(),
+            Vector3::new(0.0, 0.0, 0.0)
+        );
+        let angles = Vector3::new(0.0, std::f64::consts::FRAC_PI_2, 0.0);
+        assert_eq!(angles.sin(), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(angles.cos().x, 1.0);
+    }
+
+    #[test]
+    fn test_vector3_degrees_radians() {
+        let v = Vector3::new(180.0, 90.0, 0.0);
+        assert_eq!(v.to_radians(), Vector3::new(PI, FRAC_PI_2, 0.0));
+        assert_eq!(v.to_radians().to_degrees(), v);
+    }
+
+    #[test]
+    fn test_vector3_angle_between() {
+        let a = Vector3::new(1.0, 0.0, 0.0);
+        assert_eq!(a.angle_between(&Vector3::new(0.0, 0.0, 5.0)), FRAC_PI_2);
+        assert_eq!(a.angle_between(&Vector3::new(-1.0, 0.0, 0.0)), PI);
+        assert_eq!(a.angle_between(&a), 0.0);
+    }
+
+    #[test]
+    fn test_vector3_is_normalized() {
+        assert!(Vector3::new(0.0, 0.6, 0.8).is_normalized(1e-6));
+        assert!(Vector3::<f64>::new(1.0, 2.0, 3.0)
+            .normalize()
+            .is_normalized(1e-6));
+        assert!(!Vector3::new(1.0, 2.0, 3.0).is_normalized(1e-6));
+    }
+
+    #[test]
+    fn test_vector3_magnitude_recip() {
+        assert_eq!(
+            Vector3::<f32>::new(2.0, 3.0, 6.0).magnitude_recip(),
+            1.0 / 7.0
+        );
+        assert_eq!(
+            Vector3::<f64>::new(2.0, 3.0, 6.0).magnitude_recip(),
+            1.0 / 7.0
+        );
+    }
+
+    #[cfg(feature = "fast-rsqrt")]
+    #[test]
+    fn test_vector3_normalize_fast() {
+        let v = Vector3::<f32>::new(2.0, 3.0, 6.0).normalize_fast();
+        let exact = Vector3::<f32>::new(2.0, 3.0, 6.0).normalize();
+        let d = v - exact;
+        assert!(d.dot(&d).sqrt() < 1e-4);
+    }
+
+    #[test]
+    fn test_vector3_try_normalize_extreme() {
+        let x = Vector3::new(1.0f32, 0.0, 0.0);
+        assert_eq!(Vector3::new(1e20f32, 0.0, 0.0).try_normalize(), Ok(x));
+        assert_eq!(Vector3::new(1e-30f32, 0.0, 0.0).try_normalize(), Ok(x));
+        let n = Vector3::new(3e30f32, 4e30, 0.0).try_normalize().unwrap();
+        assert!((n - Vector3::new(0.6, 0.8, 0.0)).magnitude() < 1e-6);
+        assert_eq!(
+            Vector3::new(f32::MAX, f32::MAX, 0.0)
+                .try_normalize()
+                .map(|n| n.x),
+            Ok(std::f32::consts::FRAC_1_SQRT_2)
+        );
+    }
+
+    #[test]
+    fn test_vector3_decompose() {
+        let v = Vector3::new(1.0, -2.0, 3.0);
+        let (parallel, perpendicular) = v.decompose(&Vector3::new(0.0, 2.0, 0.0));
+        assert_eq!(parallel, Vector3::new(0.0, -2.0, 0.0));
+        assert_eq!(perpendicular, Vector3::new(1.0, 0.0, 3.0));
+        assert_eq!(parallel + perpendicular, v);
+    }
+
+    #[test]
+    fn test_vector3_project_onto_plane() {
+        let v = Vector3::new(2.0, 2.0, 0.0);
+        let n = Vector3::new(1.0, 1.0, 1.0);
+        let p = v.project_onto_plane(&n);
+        assert!(p.dot(&n).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_vector3_rotate_towards() {
+        let a = Vector3::new(0.0, 0.0, 3.0);
+        let b = Vector3::new(1.0, 0.0, 0.0);
+        let rotated = a.rotate_towards(&b, Rad(FRAC_PI_4));
+        assert!((rotated.angle_between(&a) - FRAC_PI_4).abs() < 1e-12);
+        assert!((rotated.dot(&rotated).sqrt() - 3.0).abs() < 1e-12);
+        assert_eq!(a.rotate_towards(&b, Rad(PI)), Vector3::new(3.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_vector3_rotate_towards_opposite() {
+        let a = Vector3::new(1.0, 0.0, 0.0);
+        let rotated = a.rotate_towards(&Vector3::new(-1.0, 0.0, 0.0), Rad(FRAC_PI_2));
+        assert!(rotated.x.abs() < 1e-12);
+        assert!((rotated.dot(&rotated) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_vector3_any_orthogonal() {
+        let inputs = [
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, -1.0),
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(1e-20, 1e-20, 1e-20),
+            Vector3::new(-5.0, 0.1, 5.0),
+        ];
+        for v in inputs {
+            let o = v.any_orthogonal();
+            assert!(o.is_normalized(1e-12));
+            assert!(o.dot(&v).abs() < 1e-12);
+        }
+        assert!(Vector3::<f64>::default()
+            .any_orthogonal()
+            .is_normalized(1e-12));
+
+        let tiny = Vector3::new(1e-30f32, 1e-30, 1e-30);
+        let o = tiny.any_orthogonal();
+        assert!(o.is_normalized(1e-6));
+        assert_eq!(o.dot(&Vector3::new(1.0, 1.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    fn test_vector3_bounce() {
+        let v = Vector3::new(4.0, -2.0, 2.0);
+        let n = Vector3::new(0.0, 2.0, 0.0);
+        assert_eq!(v.bounce(&n, 1.0, 0.0), Vector3::new(4.0, 2.0, 2.0));
+        assert_eq!(v.bounce(&n, 0.0, 1.0), Vector3::new(0.0, 0.0, 0.0));
+        assert_eq!(v.bounce(&(n * -1.0), 0.5, 0.5), v);
+    }
+
+    #[test]
+    fn test_vector3_wrap_and_snap() {
+        let min = Vector3::new(-1.0, -1.0, -1.0);
+        let max = Vector3::new(1.0, 1.0, 1.0);
+        let v = Vector3::new(1.5, -2.5, 0.25);
+        assert_eq!(v.wrap(&min, &max), Vector3::new(-0.5, -0.5, 0.25));
+        // Just below zero would round to 100, outside the range
+        let w = Vector3::new(-1e-9f32, 0.5, 0.5)
+            .wrap(&Vector3::new(0.0, 0.0, 0.0), &Vector3::new(100.0, 1.0, 1.0));
+        assert_eq!(w, Vector3::new(0.0, 0.5, 0.5));
+        assert_eq!(v.snap(2.0), Vector3::new(2.0, -2.0, 0.0));
+    }
+
+    #[test]
+    fn test_vector3_cast() {
+        let pixel = Vector3::new(640u32, 360, 1);
+        assert_eq!(pixel.as_f32(), Vector3::new(640.0f32, 360.0, 1.0));
+        assert_eq!(pixel.cast::<u8>(), Vector3::new(128, 104, 1));
+
+        let v = Vector3::new(-1.5f64, 2.5, 2.7);
+        assert_eq!(v.as_i32(), Vector3::new(-1, 2, 2));
+        assert_eq!(v.as_f32(), Vector3::new(-1.5f32, 2.5, 2.7));
+        assert_eq!(
+            v.cast_rounded::<i32>(Rounding::Floor),
+            Vector3::new(-2, 2, 2)
+        );
+        assert_eq!(
+            v.cast_rounded::<i32>(Rounding::Ceil),
+            Vector3::new(-1, 3, 3)
+        );
+        assert_eq!(
+            v.cast_rounded::<i64>(Rounding::Round),
+            Vector3::new(-2, 3, 3)
+        );
+        assert_eq!(v.floor_as_ivec(), Vector3::new(-2, 2, 2));
+        assert_eq!(v.ceil_as_ivec(), Vector3::new(-1, 3, 3));
+        assert_eq!(v.round_as_ivec(), Vector3::new(-2, 3, 3));
+        assert_eq!(
+            Vector3::new(1e10f32, -1.0, f32::NAN).cast::<u16>(),
+            Vector3::new(u16::MAX, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_vector3_scalar_add_sub() {
+        let v = Vector3::new(1.0, -2.0, 3.5);
+        assert_eq!(v + 0.5, Vector3::new(1.5, -1.5, 4.0));
+        assert_eq!(v - 1.0, Vector3::new(0.0, -3.0, 2.5));
+
+        let mut i = Vector3::new(1, 2, 3);
+        i += 1;
+        i -= 3;
+        assert_eq!(i, Vector3::new(-1, 0, 1));
+    }
+
+    #[test]
+    fn test_vector3_rotate_around() {
+        let v = Vector3::new(1.0, 0.0, 2.0);
+        let up = Vector3::new(0.0, 0.0, 1.0);
+        let r = v.rotate_around(&up, Deg(90.0));
+        assert!((r - Vector3::new(0.0, 1.0, 2.0)).dot(&(r - Vector3::new(0.0, 1.0, 2.0))) < 1e-12);
+        assert_eq!(v.rotate_around(&up, Rad(0.0)), v);
+    }
+
+    #[test]
+    fn test_vector3_signed_angle() {
+        let up = Vector3::new(0.0, 1.0, 0.0);
+        let forward = Vector3::new(0.0, 0.0, -1.0);
+        let left = Vector3::new(-1.0, 0.0, 0.0);
+        assert!((forward.signed_angle(&left, &up) - FRAC_PI_2).abs() < 1e-12);
+        assert!((left.signed_angle(&forward, &up) + FRAC_PI_2).abs() < 1e-12);
+        assert!(
+            (forward.signed_angle(&left, &Vector3::new(0.0, -1.0, 0.0)) + FRAC_PI_2).abs() < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_vector3_select_blend() {
+        let a = Vector3::new(0.0, 10.0, -4.0);
+        let b = Vector3::new(2.0, 20.0, 4.0);
+        let mask = Vector3 {
+            x: true,
+            y: false,
+            z: true,
+        };
+        assert_eq!(Vector3::select(&a, &b, &mask), Vector3::new(2.0, 10.0, 4.0));
+        let weights = Vector3::new(0.5, 0.0, 0.25);
+        assert_eq!(
+            Vector3::blend(&a, &b, &weights),
+            Vector3::new(1.0, 10.0, -2.0)
+        );
+    }
+
+    #[test]
+    fn test_vector3_quantize() {
+        let v = Vector3::new(0.26f32, -0.01, 1.0);
+        let cell = v.quantize(0.25);
+        assert_eq!(cell, Vector3::new(1, -1, 4));
+        let center = Vector3::dequantize(&cell, 0.25f32);
+        assert_eq!(center, Vector3::new(0.375, -0.125, 1.125));
+        assert_eq!(center.quantize(0.25), cell);
+        // Centers quantize back to their own cell with steps that aren't
+        // exact in binary, up to the documented limit
+        for i in (-2000..2000).chain([(1 << 22) - 1, -(1 << 22)]) {
+            let cell = Vector3::new(i, -i, 7);
+            assert_eq!(Vector3::dequantize(&cell, 0.1f32).quantize(0.1), cell);
+            assert_eq!(Vector3::dequantize(&cell, 0.1f64).quantize(0.1), cell);
+        }
+        let far = Vector3::new(i32::MAX, i32::MIN, 0);
+        assert_eq!(Vector3::dequantize(&far, 0.1f64).quantize(0.1), far);
+    }
+
+    #[test]
+    fn test_vector3_clamp_mag() {
+        let v = Vector3::new(0.0f64, 6.0, 8.0);
+        assert_eq!(v.clamped_mag(5.0), Vector3::new(0.0, 3.0, 4.0));
+        assert_eq!(v.clamped_mag(20.0), v);
+        let mut v = Vector3::new(2.0f32, 0.0, 0.0);
+        v.clamp_mag(0.5);
+        assert_eq!(v, Vector3::new(0.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_vector3_extend_truncate() {
+        let v = Vector2::new(1, 2).extend(3);
+        assert_eq!(v, Vector3::new(1, 2, 3));
+        assert_eq!(v.extend(1), Vector4::new(1, 2, 3, 1));
+        assert_eq!(v.extend(1).truncate(), v);
+        assert_eq!(v.truncate(), Vector2::new(1, 2));
+    }
 }
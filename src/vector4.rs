@@ -1,8 +1,8 @@
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
-use crate::{Vector2, Vector3};
+use crate::{AsPrimitive, Float, Rounding, Vector2, Vector3, VectorError, WrongLength};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[repr(C)]
 pub struct Vector4<T> {
     pub x: T,
@@ -23,7 +23,7 @@ where
         Vector4 { x, y, z, w }
     }
 
-    // Implement the dot product of two vectors
+    /// Implement the dot product of two vectors.
     pub fn dot(&self, other: &Vector4<T>) -> T {
         self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
     }
@@ -42,6 +42,20 @@ where
     }
 }
 
+impl<T: Copy> TryFrom<&[T]> for Vector4<T> {
+    type Error = WrongLength;
+
+    fn try_from(v: &[T]) -> Result<Self, Self::Error> {
+        match *v {
+            [x, y, z, w] => Ok(Vector4 { x, y, z, w }),
+            _ => Err(WrongLength {
+                expected: 4,
+                found: v.len(),
+            }),
+        }
+    }
+}
+
 impl<T> From<Vector2<T>> for Vector4<T>
 where
     T: Default
@@ -68,6 +82,424 @@ where
     }
 }
 
+impl Vector4<f32> {
+    pub fn magnitude(&self) -> f32 {
+        (self.x.powi(2) + self.y.powi(2) + self.z.powi(2) + self.w.powi(2)).sqrt()
+    }
+
+    pub fn magnitude_recip(&self) -> f32 {
+        self.magnitude().recip()
+    }
+
+    pub fn normalize(&self) -> Vector4<f32> {
+        let inv_sqrt = self.magnitude().recip();
+        Vector4 {
+            x: self.x * inv_sqrt,
+            y: self.y * inv_sqrt,
+            z: self.z * inv_sqrt,
+            w: self.w * inv_sqrt,
+        }
+    }
+}
+
+impl Vector4<f64> {
+    pub fn magnitude(&self) -> f64 {
+        (self.x.powi(2) + self.y.powi(2) + self.z.powi(2) + self.w.powi(2)).sqrt()
+    }
+
+    pub fn magnitude_recip(&self) -> f64 {
+        self.magnitude().recip()
+    }
+
+    pub fn normalize(&self) -> Vector4<f64> {
+        let inv_sqrt = self.magnitude().recip();
+        Vector4 {
+            x: self.x * inv_sqrt,
+            y: self.y * inv_sqrt,
+            z: self.z * inv_sqrt,
+            w: self.w * inv_sqrt,
+        }
+    }
+}
+
+impl<T: Float> Vector4<T> {
+    /// Homogeneous coordinates of a position (w = 1), which matrices translate.
+    pub fn from_point(p: Vector3<T>) -> Vector4<T> {
+        p.extend(T::ONE)
+    }
+
+    /// Homogeneous coordinates of a direction (w = 0), which matrices do not translate.
+    pub fn from_direction(d: Vector3<T>) -> Vector4<T> {
+        d.extend(T::ZERO)
+    }
+
+    /// Whether this is a position, i.e. w is nonzero. After a projection w
+    /// need not be 1 anymore.
+    pub fn is_point(&self) -> bool {
+        self.w != T::ZERO
+    }
+
+    /// Whether this is a direction, i.e. w is zero.
+    pub fn is_direction(&self) -> bool {
+        self.w == T::ZERO
+    }
+
+    /// Squared length, cheaper than magnitude for comparisons.
+    pub fn magnitude_squared(&self) -> T {
+        self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w
+    }
+
+    /// Uses `hypot`, so far apart points don't overflow to infinity.
+    pub fn distance(&self, other: &Vector4<T>) -> T {
+        let d = *other - *self;
+        d.x.hypot(d.y).hypot(d.z).hypot(d.w)
+    }
+
+    pub fn distance_squared(&self, other: &Vector4<T>) -> T {
+        (*other - *self).magnitude_squared()
+    }
+
+    /// Shorten to at most `limit` length, keeping the direction.
+    pub fn clamped_mag(&self, limit: T) -> Vector4<T> {
+        let mag_sq = self.magnitude_squared();
+        if mag_sq > limit * limit {
+            *self * (limit / mag_sq.sqrt())
+        } else {
+            *self
+        }
+    }
+
+    pub fn clamp_mag(&mut self, limit: T) {
+        *self = self.clamped_mag(limit);
+    }
+
+    /// Clamp each component to [0, 1].
+    pub fn saturate(&self) -> Vector4<T> {
+        Vector4 {
+            x: self.x.max(T::ZERO).min(T::ONE),
+            y: self.y.max(T::ZERO).min(T::ONE),
+            z: self.z.max(T::ZERO).min(T::ONE),
+            w: self.w.max(T::ZERO).min(T::ONE),
+        }
+    }
+
+    /// 0 for each component below the edge, 1 otherwise (like GLSL step).
+    pub fn step(&self, edge: &Vector4<T>) -> Vector4<T> {
+        let step = |e: T, v: T| if v < e { T::ZERO } else { T::ONE };
+        Vector4 {
+            x: step(edge.x, self.x),
+            y: step(edge.y, self.y),
+            z: step(edge.z, self.z),
+            w: step(edge.w, self.w),
+        }
+    }
+
+    /// Component-wise square root.
+    pub fn sqrt(&self) -> Vector4<T> {
+        Vector4 {
+            x: self.x.sqrt(),
+            y: self.y.sqrt(),
+            z: self.z.sqrt(),
+            w: self.w.sqrt(),
+        }
+    }
+
+    /// Component-wise e^x.
+    pub fn exp(&self) -> Vector4<T> {
+        Vector4 {
+            x: self.x.exp(),
+            y: self.y.exp(),
+            z: self.z.exp(),
+            w: self.w.exp(),
+        }
+    }
+
+    /// Component-wise natural logarithm.
+    pub fn ln(&self) -> Vector4<T> {
+        Vector4 {
+            x: self.x.ln(),
+            y: self.y.ln(),
+            z: self.z.ln(),
+            w: self.w.ln(),
+        }
+    }
+
+    /// Raise each component to the power n.
+    pub fn powf(&self, n: T) -> Vector4<T> {
+        Vector4 {
+            x: self.x.powf(n),
+            y: self.y.powf(n),
+            z: self.z.powf(n),
+            w: self.w.powf(n),
+        }
+    }
+
+    /// Component-wise sine (radians).
+    pub fn sin(&self) -> Vector4<T> {
+        Vector4 {
+            x: self.x.sin(),
+            y: self.y.sin(),
+            z: self.z.sin(),
+            w: self.w.sin(),
+        }
+    }
+
+    /// Component-wise cosine (radians).
+    pub fn cos(&self) -> Vector4<T> {
+        Vector4 {
+            x: self.x.cos(),
+            y: self.y.cos(),
+            z: self.z.cos(),
+            w: self.w.cos(),
+        }
+    }
+
+    /// Convert each component from radians to degrees.
+    pub fn to_degrees(&self) -> Vector4<T> {
+        Vector4 {
+            x: self.x.to_degrees(),
+            y: self.y.to_degrees(),
+            z: self.z.to_degrees(),
+            w: self.w.to_degrees(),
+        }
+    }
+
+    /// Convert each component from degrees to radians.
+    pub fn to_radians(&self) -> Vector4<T> {
+        Vector4 {
+            x: self.x.to_radians(),
+            y: self.y.to_radians(),
+            z: self.z.to_radians(),
+            w: self.w.to_radians(),
+        }
+    }
+
+    /// Whether every component is within epsilon of the one of `other`.
+    pub fn approx_eq(&self, other: &Vector4<T>, epsilon: T) -> bool {
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+            && (self.w - other.w).abs() <= epsilon
+    }
+
+    /// Whether the magnitude is within epsilon of 1.
+    pub fn is_normalized(&self, epsilon: T) -> bool {
+        let len_sq = self.dot(self);
+        (len_sq.sqrt() - T::ONE).abs() <= epsilon
+    }
+
+    /// Wrap each component into [min, max), as on a torus: leaving one side
+    /// re-enters from the other.
+    pub fn wrap(&self, min: &Vector4<T>, max: &Vector4<T>) -> Vector4<T> {
+        let wrap = |v: T, lo: T, hi: T| {
+            let size = hi - lo;
+            let w = v - size * ((v - lo) / size).floor();
+            // Tiny offsets below `lo` round up to exactly `hi`
+            if w >= hi {
+                lo
+            } else {
+                w
+            }
+        };
+        Vector4 {
+            x: wrap(self.x, min.x, max.x),
+            y: wrap(self.y, min.y, max.y),
+            z: wrap(self.z, min.z, max.z),
+            w: wrap(self.w, min.w, max.w),
+        }
+    }
+
+    /// Round each component to the nearest multiple of `grid_size`.
+    pub fn snap(&self, grid_size: T) -> Vector4<T> {
+        Vector4 {
+            x: (self.x / grid_size).round() * grid_size,
+            y: (self.y / grid_size).round() * grid_size,
+            z: (self.z / grid_size).round() * grid_size,
+            w: (self.w / grid_size).round() * grid_size,
+        }
+    }
+}
+
+impl<T: Copy> Vector4<T> {
+    /// Vector3 of x, y and z, dropping w.
+    pub fn truncate(&self) -> Vector3<T> {
+        Vector3 {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+
+    /// Per component, the one of `b` where `mask` is set, else the one of `a`.
+    pub fn select(a: &Vector4<T>, b: &Vector4<T>, mask: &Vector4<bool>) -> Vector4<T> {
+        Vector4 {
+            x: if mask.x { b.x } else { a.x },
+            y: if mask.y { b.y } else { a.y },
+            z: if mask.z { b.z } else { a.z },
+            w: if mask.w { b.w } else { a.w },
+        }
+    }
+
+    /// Convert each component to another numeric type, like `as`.
+    pub fn cast<U>(&self) -> Vector4<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        Vector4 {
+            x: self.x.as_(),
+            y: self.y.as_(),
+            z: self.z.as_(),
+            w: self.w.as_(),
+        }
+    }
+
+    /// Like `TryFrom<&[T]>`, reporting a wrong length as a `VectorError`.
+    pub fn try_from_slice(v: &[T]) -> Result<Vector4<T>, VectorError> {
+        Ok(Vector4::try_from(v)?)
+    }
+
+    pub fn as_f32(&self) -> Vector4<f32>
+    where
+        T: AsPrimitive<f32>,
+    {
+        self.cast()
+    }
+
+    pub fn as_f64(&self) -> Vector4<f64>
+    where
+        T: AsPrimitive<f64>,
+    {
+        self.cast()
+    }
+
+    /// Convert to i32 components; floats are truncated toward zero.
+    pub fn as_i32(&self) -> Vector4<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast()
+    }
+}
+
+impl<T: Float> Vector4<T> {
+    /// Interpolate each component from `a` to `b` by its own weight, e.g. to
+    /// smooth some axes faster than others.
+    pub fn blend(a: &Vector4<T>, b: &Vector4<T>, weights: &Vector4<T>) -> Vector4<T> {
+        Vector4 {
+            x: a.x + (b.x - a.x) * weights.x,
+            y: a.y + (b.y - a.y) * weights.y,
+            z: a.z + (b.z - a.z) * weights.z,
+            w: a.w + (b.w - a.w) * weights.w,
+        }
+    }
+
+    /// Order by magnitude, e.g. `points.sort_by(|a, b| a.cmp_by_magnitude(b))`.
+    /// NaN magnitudes sort after all others.
+    pub fn cmp_by_magnitude(&self, other: &Vector4<T>) -> std::cmp::Ordering {
+        let a = self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w;
+        let b = other.x * other.x + other.y * other.y + other.z * other.z + other.w * other.w;
+        a.total_cmp(&b)
+    }
+
+    // Err unless every component is finite
+    fn check_finite(&self) -> Result<(), VectorError> {
+        if self.x.is_finite() && self.y.is_finite() && self.z.is_finite() && self.w.is_finite() {
+            Ok(())
+        } else {
+            Err(VectorError::NonFinite)
+        }
+    }
+
+    /// Unit vector in the same direction, or an error instead of NaN
+    /// components for zero length or non-finite input. Components are scaled
+    /// by the largest one first, so the squares can't overflow or underflow.
+    pub fn try_normalize(&self) -> Result<Vector4<T>, VectorError> {
+        self.check_finite()?;
+        let scale = self
+            .x
+            .abs()
+            .max(self.y.abs())
+            .max(self.z.abs())
+            .max(self.w.abs());
+        if scale == T::ZERO {
+            return Err(VectorError::ZeroLength);
+        }
+        let v = *self * scale.recip();
+        let inv = v.dot(&v).sqrt().recip();
+        Ok(v * inv)
+    }
+
+    /// Round each component with the given mode, then convert it like `as`.
+    pub fn cast_rounded<U>(&self, mode: Rounding) -> Vector4<U>
+    where
+        T: AsPrimitive<U>,
+    {
+        Vector4 {
+            x: mode.apply(self.x).as_(),
+            y: mode.apply(self.y).as_(),
+            z: mode.apply(self.z).as_(),
+            w: mode.apply(self.w).as_(),
+        }
+    }
+
+    /// Integer vector of the cell containing each component, e.g. world
+    /// position to tile coordinates.
+    pub fn floor_as_ivec(&self) -> Vector4<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast_rounded(Rounding::Floor)
+    }
+
+    pub fn ceil_as_ivec(&self) -> Vector4<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast_rounded(Rounding::Ceil)
+    }
+
+    /// Nearest integers, halfway cases away from zero.
+    pub fn round_as_ivec(&self) -> Vector4<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        self.cast_rounded(Rounding::Round)
+    }
+
+    /// Index of the cell of size `step` containing each component, for
+    /// bucketing positions when hashing or deduplicating. Division and floor
+    /// are correctly rounded IEEE operations, so the result is the same on
+    /// every platform. Out of range values saturate and NaN becomes 0, like `as`.
+    pub fn quantize(&self, step: T) -> Vector4<i32>
+    where
+        T: AsPrimitive<i32>,
+    {
+        Vector4 {
+            x: (self.x / step).floor().as_(),
+            y: (self.y / step).floor().as_(),
+            z: (self.z / step).floor().as_(),
+            w: (self.w / step).floor().as_(),
+        }
+    }
+
+    /// Center of the cell `quantize` returned. Being half a cell from either
+    /// edge, it quantizes back to `cell` for every i32 with f64, and while
+    /// |cell| is below 2^22 with f32, where a float can no longer tell the
+    /// center from the edges.
+    pub fn dequantize(cell: &Vector4<i32>, step: T) -> Vector4<T>
+    where
+        i32: AsPrimitive<T>,
+    {
+        let half = (T::ONE + T::ONE).recip();
+        Vector4 {
+            x: (cell.x.as_() + half) * step,
+            y: (cell.y.as_() + half) * step,
+            z: (cell.z.as_() + half) * step,
+            w: (cell.w.as_() + half) * step,
+        }
+    }
+}
+
 impl<T: Add<Output = T>> Add<Vector4<T>> for Vector4<T> {
     type Output = Vector4<T>;
 
@@ -125,6 +557,20 @@ impl<T: Mul<Output = T> + Copy> Mul<T> for Vector4<T> {
     }
 }
 
+/// Component-wise product.
+impl<T: Mul<Output = T>> Mul<Vector4<T>> for Vector4<T> {
+    type Output = Vector4<T>;
+
+    fn mul(self, rhs: Vector4<T>) -> Self::Output {
+        Vector4 {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+            z: self.z * rhs.z,
+            w: self.w * rhs.w,
+        }
+    }
+}
+
 impl<T: MulAssign + Copy> MulAssign<T> for Vector4<T> {
     fn mul_assign(&mut self, rhs: T) {
         self.x *= rhs;
@@ -134,9 +580,56 @@ impl<T: MulAssign + Copy> MulAssign<T> for Vector4<T> {
     }
 }
 
+/// Adds the scalar to every component, `w` included.
+impl<T: Add<Output = T> + Copy> Add<T> for Vector4<T> {
+    type Output = Vector4<T>;
+
+    fn add(self, rhs: T) -> Self::Output {
+        Vector4 {
+            x: self.x + rhs,
+            y: self.y + rhs,
+            z: self.z + rhs,
+            w: self.w + rhs,
+        }
+    }
+}
+
+impl<T: AddAssign + Copy> AddAssign<T> for Vector4<T> {
+    fn add_assign(&mut self, rhs: T) {
+        self.x += rhs;
+        self.y += rhs;
+        self.z += rhs;
+        self.w += rhs;
+    }
+}
+
+/// Subtracts the scalar from every component, `w` included.
+impl<T: Sub<Output = T> + Copy> Sub<T> for Vector4<T> {
+    type Output = Vector4<T>;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        Vector4 {
+            x: self.x - rhs,
+            y: self.y - rhs,
+            z: self.z - rhs,
+            w: self.w - rhs,
+        }
+    }
+}
+
+impl<T: SubAssign + Copy> SubAssign<T> for Vector4<T> {
+    fn sub_assign(&mut self, rhs: T) {
+        self.x -= rhs;
+        self.y -= rhs;
+        self.z -= rhs;
+        self.w -= rhs;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
 
     #[test]
     fn test_vector4_new() {
@@ -228,4 +721,109 @@ mod tests {
         assert_eq!(vector4.z, 6.0);
         assert_eq!(vector4.w, 8.0);
     }
+
+    #[test]
+    fn test_vector4_saturate() {
+        let v = Vector4::new(-0.5, 0.5, 1.5, 1.0).saturate();
+        assert_eq!(v, Vector4::new(0.0, 0.5, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_vector4_step() {
+        let v = Vector4::new(0.2, 0.5, 0.8, -1.0).step(&Vector4::new(0.5, 0.5, 0.5, 0.0));
+        assert_eq!(v, Vector4::new(0.0, 1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_vector4_transcendental() {
+        let v = Vector4::new(1.0, 4.0, 9.0, 16.0);
+        assert_eq!(v.sqrt(), Vector4::new(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(v.powf(1.0), v);
+        assert_eq!(
+            Vector4::new(0.0, 0.0, 0.0, 0.0).exp(),
+            Vector4::new(1.0, 1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            Vector4::new(1.0, 1.0, 1.0, 1.0).ln(),
+            Vector4::new(0.0, 0.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            Vector4::new(0.0, 0.0, 0.0, 0.0).cos(),
+            Vector4::new(1.0, 1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            Vector4::new(0.0, 0.0, 0.0, 0.0).sin(),
+            Vector4::new(0.0, 0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_vector4_degrees_radians() {
+        let v = Vector4::new(180.0, 90.0, 0.0, -180.0);
+        assert_eq!(v.to_radians(), Vector4::new(PI, FRAC_PI_2, 0.0, -PI));
+        assert_eq!(v.to_radians().to_degrees(), v);
+    }
+
+    #[test]
+    fn test_vector4_is_normalized() {
+        assert!(Vector4::new(0.5, 0.5, 0.5, 0.5).is_normalized(1e-6));
+        assert!(!Vector4::new(1.0, 1.0, 0.0, 0.0).is_normalized(1e-6));
+    }
+
+    #[test]
+    fn test_vector4_try_from_slice() {
+        let buffer = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let colors: Vec<Vector4<f32>> = buffer
+            .chunks(4)
+            .map(|c| Vector4::try_from(c).unwrap())
+            .collect();
+        assert_eq!(colors[1], Vector4::new(5.0, 6.0, 7.0, 8.0));
+        let err = Vector4::try_from(&buffer[..3]).unwrap_err();
+        assert_eq!(
+            err,
+            WrongLength {
+                expected: 4,
+                found: 3
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "expected a slice of length 4, found length 3"
+        );
+        assert!(Vector2::try_from(&[1, 2][..]).is_ok());
+        assert!(Vector3::try_from(&[1, 2][..]).is_err());
+    }
+
+    #[test]
+    fn test_vector4_magnitude_normalize() {
+        let v = Vector4::new(1.0f32, 1.0, 1.0, 1.0);
+        assert_eq!(v.magnitude_squared(), 4.0);
+        assert_eq!(v.magnitude(), 2.0);
+        assert_eq!(v.normalize(), Vector4::new(0.5, 0.5, 0.5, 0.5));
+        let v = Vector4::new(0.0f64, 3.0, 0.0, 4.0);
+        assert_eq!(v.magnitude_recip(), 0.2);
+        assert!(v.normalize().is_normalized(1e-12));
+    }
+
+    #[test]
+    fn test_vector4_distance() {
+        let a = Vector4::new(1.0f64, 2.0, 3.0, 4.0);
+        let b = Vector4::new(2.0, 3.0, 4.0, 5.0);
+        assert_eq!(a.distance_squared(&b), 4.0);
+        assert_eq!(a.distance(&b), 2.0);
+        assert_eq!(
+            Vector3::new(0.0f32, 3.0, 0.0).distance(&Vector3::new(4.0, 0.0, 0.0)),
+            5.0
+        );
+    }
+
+    #[test]
+    fn test_vector4_homogeneous() {
+        let p = Vector4::from_point(Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(p, Vector4::new(1.0, 2.0, 3.0, 1.0));
+        assert!(p.is_point() && !p.is_direction());
+        let d = Vector4::from_direction(Vector3::new(0.0f32, 1.0, 0.0));
+        assert_eq!(d.w, 0.0);
+        assert!(d.is_direction() && !d.is_point());
+    }
 }
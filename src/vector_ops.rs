@@ -0,0 +1,184 @@
+//! Operations over slices of vectors, written for throughput when thousands
+//! of vectors are processed per frame.
+//!
+//! The in-place routines mirror BLAS level 1 and work on any vector or
+//! scalar type, updating particle buffers without allocating temporaries.
+//! They panic if the slices differ in length.
+
+use std::ops::{AddAssign, Mul, MulAssign};
+
+use crate::{Vector2, Vector3, Vector4, WrongLength};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// `f32` vectors laid out as consecutive components without padding, which
+/// lets slices of them be viewed as flat `f32` buffers. Sealed, since the
+/// views rely on the layout of the crate's `#[repr(C)]` vector types.
+pub trait PackedF32: Copy + sealed::Sealed {
+    const DIM: usize;
+}
+
+macro_rules! impl_packed_f32 {
+    ($($v:ident => $dim:expr),*) => {
+        $(
+            impl sealed::Sealed for $v<f32> {}
+
+            impl PackedF32 for $v<f32> {
+                const DIM: usize = $dim;
+            }
+        )*
+    };
+}
+
+impl_packed_f32!(Vector2 => 2, Vector3 => 3, Vector4 => 4);
+
+/// Views vectors as their interleaved components, e.g. to upload a vertex
+/// buffer, without copying.
+pub fn as_f32_slice<V: PackedF32>(v: &[V]) -> &[f32] {
+    // SAFETY: V is one of the #[repr(C)] vector types of f32 (sealed), so it
+    // is exactly DIM f32 values with f32 alignment and no padding
+    unsafe { std::slice::from_raw_parts(v.as_ptr().cast::<f32>(), v.len() * V::DIM) }
+}
+
+/// Views interleaved components as vectors without copying. Fails if the
+/// length is not a multiple of the dimension, with `expected` the length
+/// rounded up to the next multiple.
+pub fn try_as_vectors<V: PackedF32>(flat: &[f32]) -> Result<&[V], WrongLength> {
+    if !flat.len().is_multiple_of(V::DIM) {
+        return Err(WrongLength {
+            expected: flat.len().next_multiple_of(V::DIM),
+            found: flat.len(),
+        });
+    }
+    // SAFETY: as in as_f32_slice, V has the layout of [f32; DIM], and the
+    // length was checked to cover whole vectors
+    Ok(unsafe { std::slice::from_raw_parts(flat.as_ptr().cast::<V>(), flat.len() / V::DIM) })
+}
+
+/// Number of elements processed together. Eight f32 lanes fill an AVX
+/// register and two SSE or NEON registers.
+const LANES: usize = 8;
+
+/// Writes `a[i].dot(&b[i])` into `out[i]` for every element.
+///
+/// The slices are walked in fixed-size chunks whose components are first
+/// gathered into per-axis lane arrays, which lets the compiler turn the
+/// products into SIMD instructions across elements instead of one dot
+/// product at a time.
+///
+/// Panics if the three slices differ in length.
+pub fn dot_batch(a: &[Vector3<f32>], b: &[Vector3<f32>], out: &mut [f32]) {
+    assert_eq!(a.len(), b.len(), "input slices differ in length");
+    assert_eq!(a.len(), out.len(), "output slice differs in length");
+
+    let chunks = a
+        .chunks_exact(LANES)
+        .zip(b.chunks_exact(LANES))
+        .zip(out.chunks_exact_mut(LANES));
+    for ((a, b), out) in chunks {
+        let ax: [f32; LANES] = std::array::from_fn(|i| a[i].x);
+        let ay: [f32; LANES] = std::array::from_fn(|i| a[i].y);
+        let az: [f32; LANES] = std::array::from_fn(|i| a[i].z);
+        let bx: [f32; LANES] = std::array::from_fn(|i| b[i].x);
+        let by: [f32; LANES] = std::array::from_fn(|i| b[i].y);
+        let bz: [f32; LANES] = std::array::from_fn(|i| b[i].z);
+        for i in 0..LANES {
+            out[i] = ax[i] * bx[i] + ay[i] * by[i] + az[i] * bz[i];
+        }
+    }
+
+    let tail = a.len() - a.len() % LANES;
+    for ((a, b), out) in a[tail..].iter().zip(&b[tail..]).zip(&mut out[tail..]) {
+        *out = a.dot(b);
+    }
+}
+
+/// `a[i] += b[i]` for every element.
+pub fn add_assign_slices<V: Copy + AddAssign>(a: &mut [V], b: &[V]) {
+    assert_eq!(a.len(), b.len(), "slices differ in length");
+    for (a, &b) in a.iter_mut().zip(b) {
+        *a += b;
+    }
+}
+
+/// `v[i] *= s` for every element.
+pub fn scale_slice<V: MulAssign<T>, T: Copy>(v: &mut [V], s: T) {
+    for v in v {
+        *v *= s;
+    }
+}
+
+/// `y[i] += a * x[i]` for every element, e.g. `axpy(positions, dt,
+/// velocities)` for an Euler step.
+pub fn axpy<V, T>(y: &mut [V], a: T, x: &[V])
+where
+    V: Copy + AddAssign + Mul<T, Output = V>,
+    T: Copy,
+{
+    assert_eq!(y.len(), x.len(), "slices differ in length");
+    for (y, &x) in y.iter_mut().zip(x) {
+        *y += x * a;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot_batch() {
+        let a: Vec<_> = (0..19).map(|i| Vector3::new(i as f32, 1.0, -2.0)).collect();
+        let b: Vec<_> = (0..19)
+            .map(|i| Vector3::new(0.5, i as f32, 0.25 * i as f32))
+            .collect();
+        let mut out = vec![0.0; 19];
+        dot_batch(&a, &b, &mut out);
+        for i in 0..19 {
+            assert_eq!(out[i], a[i].dot(&b[i]));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dot_batch_length_mismatch() {
+        let v = [Vector3::new(1.0, 0.0, 0.0); 3];
+        dot_batch(&v, &v, &mut [0.0; 2]);
+    }
+
+    #[test]
+    fn test_slice_arithmetic() {
+        let mut positions = vec![Vector3::new(0.0, 1.0, 0.0), Vector3::new(2.0, 0.0, 0.0)];
+        let velocities = [Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, -4.0, 2.0)];
+        axpy(&mut positions, 0.5, &velocities);
+        assert_eq!(
+            positions,
+            [Vector3::new(0.5, 1.0, 0.0), Vector3::new(2.0, -2.0, 1.0)]
+        );
+        add_assign_slices(&mut positions, &velocities);
+        assert_eq!(positions[1], Vector3::new(2.0, -6.0, 3.0));
+        scale_slice(&mut positions, 2.0);
+        assert_eq!(positions[0], Vector3::new(3.0, 2.0, 0.0));
+
+        let mut scalars = [1.0f64, 2.0];
+        axpy(&mut scalars, 3.0, &[1.0, -1.0]);
+        assert_eq!(scalars, [4.0, -1.0]);
+    }
+
+    #[test]
+    fn test_flat_views() {
+        let v = [Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0)];
+        let flat = as_f32_slice(&v);
+        assert_eq!(flat, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let pairs: &[Vector2<f32>] = try_as_vectors(flat).unwrap();
+        assert_eq!(pairs[2], Vector2::new(5.0, 6.0));
+        assert_eq!(
+            try_as_vectors::<Vector4<f32>>(flat),
+            Err(WrongLength {
+                expected: 8,
+                found: 6
+            })
+        );
+    }
+}
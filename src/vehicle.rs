@@ -0,0 +1,176 @@
+//! Raycast vehicle math: each wheel casts a ray down from its mount point,
+//! the hit distance drives a spring-damper suspension, and the tire forces
+//! are limited by a friction ellipse on the contact plane.
+
+use crate::Vector3;
+
+/// Spring-damper suspension of one wheel, measured along its ray.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Suspension {
+    /// Ray length from the mount point to the wheel contact at rest.
+    pub rest_length: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+}
+
+impl Suspension {
+    pub const fn new(rest_length: f32, stiffness: f32, damping: f32) -> Suspension {
+        Suspension {
+            rest_length,
+            stiffness,
+            damping,
+        }
+    }
+
+    /// Magnitude of the suspension force for a ray hit at `hit_distance`,
+    /// with `compression_speed` positive while the spring compresses. Zero
+    /// when the wheel is in the air, and never negative so the suspension
+    /// does not pull the car down onto the ground.
+    pub fn force(&self, hit_distance: f32, compression_speed: f32) -> f32 {
+        if hit_distance >= self.rest_length {
+            return 0.0;
+        }
+        let compression = self.rest_length - hit_distance;
+        (self.stiffness * compression + self.damping * compression_speed).max(0.0)
+    }
+
+    /// Suspension force vector, pushing the mount point back along the ray
+    /// direction `ray_dir` (unit length, usually the car's down axis).
+    pub fn force_vector(
+        &self,
+        ray_dir: Vector3<f32>,
+        hit_distance: f32,
+        compression_speed: f32,
+    ) -> Vector3<f32> {
+        ray_dir * -self.force(hit_distance, compression_speed)
+    }
+}
+
+/// Rate of compression from the hit distances of two consecutive steps.
+pub fn compression_speed(previous_distance: f32, distance: f32, dt: f32) -> f32 {
+    (previous_distance - distance) / dt
+}
+
+/// Limits a tire force to the friction ellipse. The force is first projected
+/// onto the contact plane with `normal`, then split into the part along the
+/// wheel's `forward` axis and the lateral part. If the combination exceeds
+/// `(f_long / (mu_long * load))^2 + (f_lat / (mu_lat * load))^2 <= 1` it is
+/// scaled back onto the ellipse, keeping its direction.
+pub fn friction_ellipse(
+    force: Vector3<f32>,
+    normal: Vector3<f32>,
+    forward: Vector3<f32>,
+    normal_load: f32,
+    mu_long: f32,
+    mu_lat: f32,
+) -> Vector3<f32> {
+    let planar = force.project_onto_plane(&normal);
+    let forward = forward.project_onto_plane(&normal);
+    let max_long = mu_long * normal_load;
+    let max_lat = mu_lat * normal_load;
+    if max_long <= 0.0 || max_lat <= 0.0 {
+        return Vector3::default();
+    }
+    let (long, lat) = planar.decompose(&forward);
+    let ratio_sq = long.dot(&long) / (max_long * max_long) + lat.dot(&lat) / (max_lat * max_lat);
+    if ratio_sq > 1.0 {
+        planar * ratio_sq.sqrt().recip()
+    } else {
+        planar
+    }
+}
+
+/// Static axle loads `(front, rear)` of a car of `mass` under `gravity`
+/// (magnitude), with the center of mass `cg_to_front` behind the front axle
+/// and `cg_to_rear` ahead of the rear axle.
+pub fn axle_loads(mass: f32, gravity: f32, cg_to_front: f32, cg_to_rear: f32) -> (f32, f32) {
+    let weight = mass * gravity;
+    let wheelbase = cg_to_front + cg_to_rear;
+    (
+        weight * cg_to_rear / wheelbase,
+        weight * cg_to_front / wheelbase,
+    )
+}
+
+/// Load moved from the front to the rear axle while accelerating forward at
+/// `acceleration` (negative when braking).
+pub fn longitudinal_weight_transfer(
+    mass: f32,
+    acceleration: f32,
+    cg_height: f32,
+    wheelbase: f32,
+) -> f32 {
+    mass * acceleration * cg_height / wheelbase
+}
+
+/// Load moved from the inner to the outer wheels of an axle while cornering
+/// with `lateral_acceleration`.
+pub fn lateral_weight_transfer(
+    mass: f32,
+    lateral_acceleration: f32,
+    cg_height: f32,
+    track_width: f32,
+) -> f32 {
+    mass * lateral_acceleration * cg_height / track_width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suspension() {
+        let s = Suspension::new(0.5, 20000.0, 1000.0);
+        assert_eq!(s.force(0.6, 0.0), 0.0);
+        assert_eq!(s.force(0.375, 0.0), 2500.0);
+        assert_eq!(s.force(0.375, 1.0), 3500.0);
+        // Rebounding quickly cannot pull the wheel down
+        assert_eq!(s.force(0.45, -5.0), 0.0);
+
+        let speed = compression_speed(0.42, 0.4, 0.02);
+        assert!((speed - 1.0).abs() < 1e-5);
+        let f = s.force_vector(Vector3::new(0.0, -1.0, 0.0), 0.375, 0.0);
+        assert_eq!(f, Vector3::new(0.0, 2500.0, 0.0));
+    }
+
+    #[test]
+    fn test_friction_ellipse() {
+        let up = Vector3::new(0.0, 1.0, 0.0);
+        let forward = Vector3::new(0.0, 0.0, 1.0);
+        // Inside the ellipse the force is only projected onto the ground
+        let f = friction_ellipse(
+            Vector3::new(100.0, 50.0, 200.0),
+            up,
+            forward,
+            1000.0,
+            1.0,
+            0.5,
+        );
+        assert_eq!(f, Vector3::new(100.0, 0.0, 200.0));
+
+        // Pure lateral force beyond the grip is clamped to mu_lat * load
+        let f = friction_ellipse(Vector3::new(900.0, 0.0, 0.0), up, forward, 1000.0, 1.0, 0.5);
+        assert!((f - Vector3::new(500.0, 0.0, 0.0)).magnitude() < 1e-3);
+
+        // Combined force ends up on the ellipse
+        let f = friction_ellipse(
+            Vector3::new(600.0, 0.0, 1600.0),
+            up,
+            forward,
+            1000.0,
+            1.0,
+            0.5,
+        );
+        let ratio = (f.z / 1000.0).powi(2) + (f.x / 500.0).powi(2);
+        assert!((ratio - 1.0).abs() < 1e-5);
+        assert!((f.x / f.z - 600.0 / 1600.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_weight_transfer() {
+        let (front, rear) = axle_loads(1000.0, 10.0, 1.0, 1.5);
+        assert_eq!((front, rear), (6000.0, 4000.0));
+        assert_eq!(longitudinal_weight_transfer(1000.0, 5.0, 0.5, 2.5), 1000.0);
+        assert_eq!(lateral_weight_transfer(1000.0, 8.0, 0.5, 1.6), 2500.0);
+    }
+}
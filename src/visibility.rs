@@ -0,0 +1,131 @@
+//! Line of sight among 2D wall segments, for stealth and AI perception in
+//! top-down games.
+
+use crate::geometry2d::{segment_segment, Segment2};
+use crate::{Rad, Vector2};
+
+/// Whether the straight line from `from` to `to` crosses none of the
+/// `segments`. Touching a segment, e.g. at its end point, blocks the view.
+pub fn can_see(from: Vector2<f32>, to: Vector2<f32>, segments: &[Segment2]) -> bool {
+    let sight = Segment2::new(from, to);
+    segments
+        .iter()
+        .all(|s| segment_segment(&sight, s).is_none())
+}
+
+// Distance along the ray from `origin` in unit direction `dir` to the first
+// segment it hits, if any
+fn cast(origin: Vector2<f32>, dir: Vector2<f32>, segments: &[Segment2]) -> Option<f32> {
+    segments
+        .iter()
+        .filter_map(|s| {
+            let edge = s.b - s.a;
+            let denom = dir.perp_dot(&edge);
+            if denom == 0.0 {
+                return None;
+            }
+            let q = s.a - origin;
+            let t = q.perp_dot(&edge) / denom;
+            let u = q.perp_dot(&dir) / denom;
+            (t >= 0.0 && (0.0..=1.0).contains(&u)).then_some(t)
+        })
+        .min_by(|a, b| a.total_cmp(b))
+}
+
+/// Number of evenly spaced rays `visibility_polygon` casts in addition to the
+/// ones toward segment end points.
+pub const BOUNDARY_RAYS: usize = 32;
+
+/// Region visible from `origin`, as polygon vertices sorted counter-clockwise
+/// by angle. Rays are cast toward every segment end point and slightly to
+/// either side of it, to see past corners. Rays that hit nothing stop at
+/// `max_distance`, and `BOUNDARY_RAYS` evenly spaced extra rays bound the open
+/// directions, so open space is approximated by a regular polygon of that
+/// radius. For an exact polygon enclose the scene in walls.
+pub fn visibility_polygon(
+    origin: Vector2<f32>,
+    segments: &[Segment2],
+    max_distance: f32,
+) -> Vec<Vector2<f32>> {
+    const NUDGE: f32 = 1e-4;
+    let mut angles: Vec<f32> = segments
+        .iter()
+        .flat_map(|s| [s.a, s.b])
+        .flat_map(|p| {
+            let angle = (p.y - origin.y).atan2(p.x - origin.x);
+            [angle - NUDGE, angle, angle + NUDGE]
+        })
+        .collect();
+    // Without walls all around, also bound the open directions
+    let spacing = std::f32::consts::TAU / BOUNDARY_RAYS as f32;
+    angles.extend((0..BOUNDARY_RAYS).map(|i| i as f32 * spacing - std::f32::consts::PI));
+    angles.sort_by(|a, b| a.total_cmp(b));
+    angles.dedup();
+
+    angles
+        .into_iter()
+        .map(|angle| {
+            let dir = Vector2::from_angle(Rad(angle));
+            let t = cast(origin, dir, segments).map_or(max_distance, |t| t.min(max_distance));
+            origin + dir * t
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_room() -> Vec<Segment2> {
+        let c = [
+            Vector2::new(-5.0, -5.0),
+            Vector2::new(5.0, -5.0),
+            Vector2::new(5.0, 5.0),
+            Vector2::new(-5.0, 5.0),
+        ];
+        (0..4)
+            .map(|i| Segment2::new(c[i], c[(i + 1) % 4]))
+            .collect()
+    }
+
+    #[test]
+    fn test_can_see() {
+        let mut walls = square_room();
+        walls.push(Segment2::new(
+            Vector2::new(1.0, -2.0),
+            Vector2::new(1.0, 2.0),
+        ));
+        let guard = Vector2::new(-3.0, 0.0);
+        assert!(!can_see(guard, Vector2::new(3.0, 0.0), &walls));
+        assert!(can_see(guard, Vector2::new(3.0, 4.0), &walls));
+        assert!(can_see(guard, Vector2::new(0.0, 0.0), &[]));
+    }
+
+    #[test]
+    fn test_visibility_polygon() {
+        let mut walls = square_room();
+        let polygon = visibility_polygon(Vector2::new(0.0, 0.0), &walls, 100.0);
+        // Every vertex lies on the room's walls
+        for p in &polygon {
+            assert!((p.x.abs().max(p.y.abs()) - 5.0).abs() < 1e-3, "{:?}", p);
+        }
+
+        // A pillar east of the viewer hides the wall behind it
+        walls.push(Segment2::new(
+            Vector2::new(2.0, -1.0),
+            Vector2::new(2.0, 1.0),
+        ));
+        let polygon = visibility_polygon(Vector2::new(0.0, 0.0), &walls, 100.0);
+        let east: Vec<_> = polygon
+            .iter()
+            .filter(|p| p.y.abs() < 0.9 && p.x > 0.0)
+            .collect();
+        assert!(!east.is_empty());
+        assert!(east.iter().all(|p| (p.x - 2.0).abs() < 1e-3));
+
+        // Open space is bounded by max_distance
+        let open = visibility_polygon(Vector2::new(0.0, 0.0), &[], 3.0);
+        assert_eq!(open.len(), BOUNDARY_RAYS);
+        assert!(open.iter().all(|p| (p.magnitude() - 3.0).abs() < 1e-5));
+    }
+}
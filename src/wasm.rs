@@ -0,0 +1,21 @@
+//! Exchanging vectors with JavaScript as `Float32Array`s, the format WebGL
+//! buffers and most web geometry code use. Requires the `wasm` feature.
+//!
+//! Vectors are stored interleaved, e.g. `x0, y0, z0, x1, y1, z1, ...`.
+
+use js_sys::Float32Array;
+
+use crate::vector_ops::{as_f32_slice, try_as_vectors, PackedF32};
+use crate::WrongLength;
+
+/// Copies the vectors into a new `Float32Array` in one call.
+pub fn to_float32_array<V: PackedF32>(v: &[V]) -> Float32Array {
+    Float32Array::from(as_f32_slice(v))
+}
+
+/// Copies the vectors of an interleaved `Float32Array` out of JavaScript
+/// memory. Fails if its length is not a multiple of the dimension.
+pub fn from_float32_array<V: PackedF32>(array: &Float32Array) -> Result<Vec<V>, WrongLength> {
+    let flat = array.to_vec();
+    Ok(try_as_vectors(&flat)?.to_vec())
+}